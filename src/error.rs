@@ -0,0 +1,38 @@
+use pinocchio::program_error::ProgramError;
+
+/// Program specific failures, returned as `ProgramError::Custom(code)`
+#[repr(u32)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum MultisigError {
+    ProposalExpired = 0,
+    ProposalNotActive = 1,
+    NotAMember = 2,
+    AlreadyVoted = 3,
+    InvalidVoteChoice = 4,
+    ThresholdNotMet = 5,
+    AlreadyExecuted = 6,
+    NoVoteToChange = 7,
+    NotDelegated = 8,
+    VoteDelegated = 9,
+    VotingNotStarted = 10,
+    VetoDisabled = 11,
+    ArithmeticOverflow = 12,
+    UnauthorizedConfigChange = 13,
+    DuplicateMember = 14,
+    InvalidThreshold = 15,
+    TimelockNotElapsed = 16,
+    MissingPermission = 17,
+    UnsupportedAccountVersion = 18,
+    StaleVoteAccount = 19, // Vote account left over from an earlier proposal at the same id
+    AccountOutOfOrder = 20, // Account passed in a slot meant for another kind of account; the log names the slot
+    Paused = 21, // The config authority paused voting, proposing and execution
+    CpiTooLarge = 22, // Stored instruction passes more accounts or data than an executed CPI may
+    InsufficientFundsForRent = 23, // Payer can't cover the rent-exempt minimum of an account it must create
+    TreasuryNotEmpty = 24, // The treasury still holds lamports that closing the multisig would strand
+}
+
+impl From<MultisigError> for ProgramError {
+    fn from(error: MultisigError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
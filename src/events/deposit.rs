@@ -0,0 +1,70 @@
+use pinocchio::{
+    log::sol_log_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Emitted when lamports are deposited into a multisig's treasury, so indexers can
+/// follow who funded it without scanning every transfer to the treasury PDA.
+pub struct Deposit {
+    pub multisig: Pubkey,
+    pub depositor: Pubkey,
+    pub lamports: u64,
+}
+
+impl Deposit {
+    pub const DISCRIMINATOR: u8 = 3;
+    pub const LEN: usize = 1 + 32 + 32 + 8; // discriminator, multisig, depositor and lamports
+
+    pub fn serialize(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0] = Self::DISCRIMINATOR;
+        data[1..33].copy_from_slice(&self.multisig);
+        data[33..65].copy_from_slice(&self.depositor);
+        data[65..73].copy_from_slice(&self.lamports.to_le_bytes());
+        data
+    }
+
+    pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN || data[0] != Self::DISCRIMINATOR {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(Self {
+            multisig: data[1..33].try_into().unwrap(),
+            depositor: data[33..65].try_into().unwrap(),
+            lamports: u64::from_le_bytes(data[65..73].try_into().unwrap()),
+        })
+    }
+
+    /// Logs the event as a `Program data:` entry (base64 of `serialize`)
+    pub fn emit(&self) {
+        sol_log_data(&[&self.serialize()]);
+    }
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_deposit_event {
+    use super::*;
+
+    #[test]
+    fn test_deposit_round_trip() {
+        let event = Deposit {
+            multisig: [2u8; 32],
+            depositor: [5u8; 32],
+            lamports: 1_000_000,
+        };
+
+        let logged = event.serialize();
+        let decoded = Deposit::deserialize(&logged).unwrap();
+
+        assert_eq!(decoded.multisig, [2u8; 32]);
+        assert_eq!(decoded.depositor, [5u8; 32]);
+        assert_eq!(decoded.lamports, 1_000_000);
+
+        // Never mistaken for another event
+        assert!(crate::events::VoteCast::deserialize(&logged).is_err());
+    }
+}
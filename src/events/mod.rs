@@ -0,0 +1,9 @@
+pub mod vote_cast;
+pub mod proposal_created;
+pub mod proposal_snapshot;
+pub mod deposit;
+
+pub use vote_cast::*;
+pub use proposal_created::*;
+pub use proposal_snapshot::*;
+pub use deposit::*;
@@ -0,0 +1,92 @@
+use pinocchio::{
+    log::sol_log_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::state::{ProposalState, PROPOSAL_LINK_LEN};
+
+/// Emitted when a proposal is created, carrying its `link` so indexers can fetch the
+/// off-chain title and description alongside the proposal.
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub proposal_type: u8,
+    pub expiry: u64,
+    pub link: [u8; PROPOSAL_LINK_LEN],
+}
+
+impl ProposalCreated {
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 8 + 32 + 1 + 8 + PROPOSAL_LINK_LEN; // discriminator, proposal_id, proposer, proposal_type, expiry and link
+
+    pub fn new(proposal: &ProposalState) -> Self {
+        Self {
+            proposal_id: proposal.proposal_id,
+            proposer: proposal.proposer,
+            proposal_type: proposal.proposal_type,
+            expiry: proposal.expiry,
+            link: proposal.link,
+        }
+    }
+
+    pub fn serialize(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0] = Self::DISCRIMINATOR;
+        data[1..9].copy_from_slice(&self.proposal_id.to_le_bytes());
+        data[9..41].copy_from_slice(&self.proposer);
+        data[41] = self.proposal_type;
+        data[42..50].copy_from_slice(&self.expiry.to_le_bytes());
+        data[50..].copy_from_slice(&self.link);
+        data
+    }
+
+    pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN || data[0] != Self::DISCRIMINATOR {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(Self {
+            proposal_id: u64::from_le_bytes(data[1..9].try_into().unwrap()),
+            proposer: data[9..41].try_into().unwrap(),
+            proposal_type: data[41],
+            expiry: u64::from_le_bytes(data[42..50].try_into().unwrap()),
+            link: data[50..].try_into().unwrap(),
+        })
+    }
+
+    /// Logs the event as a `Program data:` entry (base64 of `serialize`)
+    pub fn emit(&self) {
+        sol_log_data(&[&self.serialize()]);
+    }
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_proposal_created_event {
+    use super::*;
+
+    #[test]
+    fn test_proposal_created_round_trip() {
+        let event = ProposalCreated {
+            proposal_id: 12345,
+            proposer: [7u8; 32],
+            proposal_type: 1,
+            expiry: 99,
+            link: [3u8; PROPOSAL_LINK_LEN],
+        };
+
+        let logged = event.serialize();
+        let decoded = ProposalCreated::deserialize(&logged).unwrap();
+
+        assert_eq!(decoded.proposal_id, 12345);
+        assert_eq!(decoded.proposer, [7u8; 32]);
+        assert_eq!(decoded.proposal_type, 1);
+        assert_eq!(decoded.expiry, 99);
+        assert_eq!(decoded.link, [3u8; PROPOSAL_LINK_LEN]);
+
+        // Never mistaken for another event
+        assert!(crate::events::VoteCast::deserialize(&logged).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use pinocchio::{
+    log::sol_log_data,
+    program_error::ProgramError,
+};
+
+use crate::state::ProposalState;
+
+/// Compact, versioned view of a proposal's status and tallies, emitted after votes and
+/// executions so clients can follow a proposal from its logs without fetching the account.
+/// Built with `ProposalState::serialize_for_log`.
+pub struct ProposalSnapshot {
+    pub proposal_id: u64,
+    pub status: u8, // ProposalStatus
+    pub for_votes: u64,
+    pub against_votes: u64,
+    pub abstain_votes: u64,
+    pub expiry: u64,
+    pub executed: u8,
+}
+
+impl ProposalSnapshot {
+    pub const DISCRIMINATOR: u8 = 2;
+    /// Layout version, bumped whenever fields are added so older snapshots stay decodable
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 8 + 1 + 8 + 8 + 8 + 8 + 1; // discriminator, version, proposal_id, status, tallies, expiry and executed
+
+    pub fn new(proposal: &ProposalState) -> Self {
+        Self {
+            proposal_id: proposal.proposal_id,
+            status: proposal.result,
+            for_votes: proposal.for_votes,
+            against_votes: proposal.against_votes,
+            abstain_votes: proposal.abstain_votes,
+            expiry: proposal.expiry,
+            executed: proposal.executed,
+        }
+    }
+
+    pub fn serialize(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0] = Self::DISCRIMINATOR;
+        data[1] = Self::VERSION;
+        data[2..10].copy_from_slice(&self.proposal_id.to_le_bytes());
+        data[10] = self.status;
+        data[11..19].copy_from_slice(&self.for_votes.to_le_bytes());
+        data[19..27].copy_from_slice(&self.against_votes.to_le_bytes());
+        data[27..35].copy_from_slice(&self.abstain_votes.to_le_bytes());
+        data[35..43].copy_from_slice(&self.expiry.to_le_bytes());
+        data[43] = self.executed;
+        data
+    }
+
+    pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN || data[0] != Self::DISCRIMINATOR || data[1] != Self::VERSION {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(Self {
+            proposal_id: u64::from_le_bytes(data[2..10].try_into().unwrap()),
+            status: data[10],
+            for_votes: u64::from_le_bytes(data[11..19].try_into().unwrap()),
+            against_votes: u64::from_le_bytes(data[19..27].try_into().unwrap()),
+            abstain_votes: u64::from_le_bytes(data[27..35].try_into().unwrap()),
+            expiry: u64::from_le_bytes(data[35..43].try_into().unwrap()),
+            executed: data[43],
+        })
+    }
+
+    /// Logs the snapshot as a `Program data:` entry (base64 of `serialize`)
+    pub fn emit(&self) {
+        sol_log_data(&[&self.serialize()]);
+    }
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_proposal_snapshot_event {
+    use super::*;
+    use crate::state::ProposalStatus;
+    use bytemuck::Zeroable;
+
+    #[test]
+    fn test_proposal_snapshot_round_trip() {
+        let mut proposal = ProposalState::zeroed();
+        proposal.proposal_id = 12345;
+        proposal.set_status(ProposalStatus::Executed);
+        proposal.for_votes = 3;
+        proposal.against_votes = 1;
+        proposal.abstain_votes = 2;
+        proposal.expiry = 99;
+        proposal.executed = 1;
+
+        let logged = proposal.serialize_for_log();
+        let decoded = ProposalSnapshot::deserialize(&logged).unwrap();
+
+        assert_eq!(decoded.proposal_id, 12345);
+        assert_eq!(decoded.status, ProposalStatus::Executed as u8);
+        assert_eq!((decoded.for_votes, decoded.against_votes, decoded.abstain_votes), (3, 1, 2));
+        assert_eq!(decoded.expiry, 99);
+        assert_eq!(decoded.executed, 1);
+
+        // A snapshot of another layout version is never misread
+        let mut other_version = logged;
+        other_version[1] = ProposalSnapshot::VERSION + 1;
+        assert!(ProposalSnapshot::deserialize(&other_version).is_err());
+        assert!(crate::events::VoteCast::deserialize(&logged).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use pinocchio::{
+    log::sol_log_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Emitted every time a vote is accepted, so indexers can rebuild voting history
+/// from the `Program data:` log lines instead of parsing prose logs.
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub vote_choice: u8,
+    pub for_votes: u64,
+    pub against_votes: u64,
+    pub abstain_votes: u64,
+    pub status: u8, // ProposalStatus after the vote
+}
+
+impl VoteCast {
+    pub const DISCRIMINATOR: u8 = 0;
+    pub const LEN: usize = 1 + 8 + 32 + 1 + 8 + 8 + 8 + 1; // discriminator, proposal_id, voter, vote_choice, tallies and status
+
+    pub fn serialize(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        data[0] = Self::DISCRIMINATOR;
+        data[1..9].copy_from_slice(&self.proposal_id.to_le_bytes());
+        data[9..41].copy_from_slice(&self.voter);
+        data[41] = self.vote_choice;
+        data[42..50].copy_from_slice(&self.for_votes.to_le_bytes());
+        data[50..58].copy_from_slice(&self.against_votes.to_le_bytes());
+        data[58..66].copy_from_slice(&self.abstain_votes.to_le_bytes());
+        data[66] = self.status;
+        data
+    }
+
+    pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN || data[0] != Self::DISCRIMINATOR {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(Self {
+            proposal_id: u64::from_le_bytes(data[1..9].try_into().unwrap()),
+            voter: data[9..41].try_into().unwrap(),
+            vote_choice: data[41],
+            for_votes: u64::from_le_bytes(data[42..50].try_into().unwrap()),
+            against_votes: u64::from_le_bytes(data[50..58].try_into().unwrap()),
+            abstain_votes: u64::from_le_bytes(data[58..66].try_into().unwrap()),
+            status: data[66],
+        })
+    }
+
+    /// Logs the event as a `Program data:` entry (base64 of `serialize`)
+    pub fn emit(&self) {
+        sol_log_data(&[&self.serialize()]);
+    }
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_vote_cast_event {
+    use super::*;
+
+    #[test]
+    fn test_vote_cast_round_trip() {
+        let event = VoteCast {
+            proposal_id: 12345,
+            voter: [7u8; 32],
+            vote_choice: 2,
+            for_votes: 1,
+            against_votes: 4,
+            abstain_votes: 9,
+            status: 2,
+        };
+
+        let logged = event.serialize();
+        let decoded = VoteCast::deserialize(&logged).unwrap();
+
+        assert_eq!(decoded.proposal_id, 12345);
+        assert_eq!(decoded.voter, [7u8; 32]);
+        assert_eq!(decoded.vote_choice, 2);
+        assert_eq!(decoded.for_votes, 1);
+        assert_eq!(decoded.against_votes, 4);
+        assert_eq!(decoded.abstain_votes, 9);
+        assert_eq!(decoded.status, 2);
+    }
+
+    #[test]
+    fn test_vote_cast_rejects_truncated_data() {
+        let logged = VoteCast {
+            proposal_id: 1,
+            voter: [1u8; 32],
+            vote_choice: 1,
+            for_votes: 1,
+            against_votes: 0,
+            abstain_votes: 0,
+            status: 1,
+        }.serialize();
+
+        assert!(VoteCast::deserialize(&logged[..VoteCast::LEN - 1]).is_err());
+    }
+}
@@ -0,0 +1,481 @@
+//! Client-side builders for this program's instructions, so the wire format each handler
+//! parses lives in one place. Instruction data builders return the full data, discriminator
+//! included; `Instruction` constructors also derive the PDAs the handler expects.
+//!
+//! Proposal types and vote choices are passed as their `u8` wire values, see `ProposalType`
+//! and `VoteChoice`.
+
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::instructions::{MultisigInstructions, VOTE_FLAG_DRY_RUN};
+
+fn program_id() -> Pubkey {
+    Pubkey::new_from_array(crate::ID)
+}
+
+/// `[b"multisig_config", multisig]`
+pub fn multisig_config_address(multisig: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"multisig_config", multisig.as_ref()], &program_id())
+}
+
+/// `[b"proposal", multisig, proposal_id]`
+pub fn proposal_address(multisig: &Pubkey, proposal_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"proposal", multisig.as_ref(), &proposal_id.to_le_bytes()], &program_id())
+}
+
+/// `[b"vote_state", multisig, proposal_id]`
+pub fn vote_state_address(multisig: &Pubkey, proposal_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vote_state", multisig.as_ref(), &proposal_id.to_le_bytes()], &program_id())
+}
+
+/// `[b"receipt", proposal_state, member]`
+pub fn receipt_address(proposal_state: &Pubkey, member: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"receipt", proposal_state.as_ref(), member.as_ref()], &program_id())
+}
+
+/// [Vote][proposal_id: u64][vote_choice: u8][proposal_bump: u8]
+pub fn vote_ix_data(proposal_id: u64, choice: u8, bump: u8) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::Vote as u8];
+    data.extend_from_slice(&proposal_id.to_le_bytes());
+    data.push(choice);
+    data.push(bump);
+    data
+}
+
+/// A vote changing the voter's earlier choice: `vote_ix_data` with the change flag set
+pub fn change_vote_ix_data(proposal_id: u64, choice: u8, bump: u8) -> Vec<u8> {
+    let mut data = vote_ix_data(proposal_id, choice, bump);
+    data.push(1);
+    data
+}
+
+/// A vote previewed without being recorded: `vote_ix_data` with the dry run flag set
+pub fn dry_run_vote_ix_data(proposal_id: u64, choice: u8, bump: u8) -> Vec<u8> {
+    let mut data = vote_ix_data(proposal_id, choice, bump);
+    data.push(VOTE_FLAG_DRY_RUN);
+    data
+}
+
+/// A vote cast by a delegate for the member at `delegator_index` in `Multisig.members`
+pub fn delegated_vote_ix_data(proposal_id: u64, choice: u8, bump: u8, change_vote: bool, delegator_index: u8) -> Vec<u8> {
+    let mut data = vote_ix_data(proposal_id, choice, bump);
+    data.push(change_vote as u8);
+    data.push(delegator_index);
+    data
+}
+
+/// [BatchVote][num_votes: u8] followed by an [entry_len: u16][vote payload] entry per vote,
+/// each built by `vote_ix_data` or one of its variants
+pub fn batch_vote_ix_data(votes: &[Vec<u8>]) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::BatchVote as u8, votes.len() as u8];
+    for vote in votes {
+        // The entry drops the Vote discriminator
+        let payload = &vote[1..];
+        data.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+        data.extend_from_slice(payload);
+    }
+    data
+}
+
+/// [CreateProposal][proposal_id: u64][duration: u64][proposal_bump: u8][proposal_type: u8][payload]
+pub fn create_proposal_ix_data(proposal_id: u64, duration: u64, bump: u8, proposal_type: u8, payload: &[u8]) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::CreateProposal as u8];
+    data.extend_from_slice(&proposal_id.to_le_bytes());
+    data.extend_from_slice(&duration.to_le_bytes());
+    data.push(bump);
+    data.push(proposal_type);
+    data.extend_from_slice(payload);
+    data
+}
+
+/// ArbitraryCpi payload storing `instruction` for execution:
+/// [target_program: Pubkey][num_accounts: u8][(pubkey, is_signer, is_writable) per account][data_len: u16][data]
+pub fn stored_instruction_payload(instruction: &Instruction) -> Vec<u8> {
+    let mut payload = instruction.program_id.to_bytes().to_vec();
+    payload.push(instruction.accounts.len() as u8);
+    for meta in &instruction.accounts {
+        payload.extend_from_slice(meta.pubkey.as_ref());
+        payload.push(meta.is_signer as u8);
+        payload.push(meta.is_writable as u8);
+    }
+    payload.extend_from_slice(&(instruction.data.len() as u16).to_le_bytes());
+    payload.extend_from_slice(&instruction.data);
+    payload
+}
+
+/// [ExecuteProposal][proposal_id: u64][proposal_bump: u8][multisig_seed: u64]
+pub fn execute_proposal_ix_data(proposal_id: u64, bump: u8, multisig_seed: u64) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::ExecuteProposal as u8];
+    data.extend_from_slice(&proposal_id.to_le_bytes());
+    data.push(bump);
+    data.extend_from_slice(&multisig_seed.to_le_bytes());
+    data
+}
+
+/// `[discriminator][proposal_id: u64][proposal_bump: u8]`, the data of every instruction
+/// addressing a proposal by id alone
+fn proposal_ix_data(discriminator: MultisigInstructions, proposal_id: u64, bump: u8) -> Vec<u8> {
+    let mut data = vec![discriminator as u8];
+    data.extend_from_slice(&proposal_id.to_le_bytes());
+    data.push(bump);
+    data
+}
+
+/// [CancelProposal][proposal_id: u64][proposal_bump: u8]
+pub fn cancel_proposal_ix_data(proposal_id: u64, bump: u8) -> Vec<u8> {
+    proposal_ix_data(MultisigInstructions::CancelProposal, proposal_id, bump)
+}
+
+/// [CloseProposal][proposal_id: u64][proposal_bump: u8]
+pub fn close_proposal_ix_data(proposal_id: u64, bump: u8) -> Vec<u8> {
+    proposal_ix_data(MultisigInstructions::CloseProposal, proposal_id, bump)
+}
+
+/// [Veto][proposal_id: u64][proposal_bump: u8]
+pub fn veto_ix_data(proposal_id: u64, bump: u8) -> Vec<u8> {
+    proposal_ix_data(MultisigInstructions::Veto, proposal_id, bump)
+}
+
+/// [GetProposalResult][proposal_id: u64][proposal_bump: u8]
+pub fn get_proposal_result_ix_data(proposal_id: u64, bump: u8) -> Vec<u8> {
+    proposal_ix_data(MultisigInstructions::GetProposalResult, proposal_id, bump)
+}
+
+/// [FinalizeProposal][proposal_id: u64][proposal_bump: u8]
+pub fn finalize_proposal_ix_data(proposal_id: u64, bump: u8) -> Vec<u8> {
+    proposal_ix_data(MultisigInstructions::FinalizeProposal, proposal_id, bump)
+}
+
+/// [ExtendProposalExpiry][proposal_id: u64][proposal_bump: u8][extension: u64]
+pub fn extend_proposal_expiry_ix_data(proposal_id: u64, bump: u8, extension: u64) -> Vec<u8> {
+    let mut data = proposal_ix_data(MultisigInstructions::ExtendProposalExpiry, proposal_id, bump);
+    data.extend_from_slice(&extension.to_le_bytes());
+    data
+}
+
+/// [ChangeThreshold][new_threshold: u64] followed by [proposal_type: u8] when only that
+/// type's threshold changes
+pub fn change_threshold_ix_data(new_threshold: u64, proposal_type: Option<u8>) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::ChangeThreshold as u8];
+    data.extend_from_slice(&new_threshold.to_le_bytes());
+    data.extend(proposal_type);
+    data
+}
+
+/// [EmergencyPause][paused: u8]
+pub fn emergency_pause_ix_data(paused: bool) -> Vec<u8> {
+    vec![MultisigInstructions::EmergencyPause as u8, paused as u8]
+}
+
+/// [RotateConfigAuthority][new_authority: Pubkey]
+pub fn rotate_config_authority_ix_data(new_authority: &Pubkey) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::RotateConfigAuthority as u8];
+    data.extend_from_slice(new_authority.as_ref());
+    data
+}
+
+/// [SetVetoAuthority][veto_authority: Pubkey], the all-zeros pubkey disabling veto
+pub fn set_veto_authority_ix_data(veto_authority: &Pubkey) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::SetVetoAuthority as u8];
+    data.extend_from_slice(veto_authority.as_ref());
+    data
+}
+
+/// [DelegateVote][delegate: Pubkey], the all-zeros pubkey revoking the delegation
+pub fn delegate_vote_ix_data(delegate: &Pubkey) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::DelegateVote as u8];
+    data.extend_from_slice(delegate.as_ref());
+    data
+}
+
+/// [UpdateExpiryPolicy][max_proposal_duration: u64]
+pub fn update_expiry_policy_ix_data(max_proposal_duration: u64) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::UpdateExpiryPolicy as u8];
+    data.extend_from_slice(&max_proposal_duration.to_le_bytes());
+    data
+}
+
+/// [DepositToTreasury][lamports: u64]
+pub fn deposit_to_treasury_ix_data(lamports: u64) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::DepositToTreasury as u8];
+    data.extend_from_slice(&lamports.to_le_bytes());
+    data
+}
+
+/// [SetMembersRoot][members_root: 32 bytes][root_member_count: u64]
+pub fn set_members_root_ix_data(members_root: &[u8; 32], root_member_count: u64) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::SetMembersRoot as u8];
+    data.extend_from_slice(members_root);
+    data.extend_from_slice(&root_member_count.to_le_bytes());
+    data
+}
+
+/// [ReplaceMember][old_member: Pubkey][new_member: Pubkey], for a stored ArbitraryCpi
+/// instruction signed by the multisig
+pub fn replace_member_ix_data(old_member: &Pubkey, new_member: &Pubkey) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::ReplaceMember as u8];
+    data.extend_from_slice(old_member.as_ref());
+    data.extend_from_slice(new_member.as_ref());
+    data
+}
+
+/// [ChangeConfig][min_threshold: u64][quorum: u64][execution_delay: u64], the payload a
+/// ConfigChange proposal stores
+pub fn change_config_ix_data(min_threshold: u64, quorum: u64, execution_delay: u64) -> Vec<u8> {
+    let mut data = vec![MultisigInstructions::ChangeConfig as u8];
+    data.extend_from_slice(&min_threshold.to_le_bytes());
+    data.extend_from_slice(&quorum.to_le_bytes());
+    data.extend_from_slice(&execution_delay.to_le_bytes());
+    data
+}
+
+/// [WithdrawExcessRent]
+pub fn withdraw_excess_rent_ix_data() -> Vec<u8> {
+    vec![MultisigInstructions::WithdrawExcessRent as u8]
+}
+
+/// [RevokeDelegation][retract_votes: u8]
+pub fn revoke_delegation_ix_data(retract_votes: bool) -> Vec<u8> {
+    vec![MultisigInstructions::RevokeDelegation as u8, retract_votes as u8]
+}
+
+/// [SweepExpiredProposals]
+pub fn sweep_expired_proposals_ix_data() -> Vec<u8> {
+    vec![MultisigInstructions::SweepExpiredProposals as u8]
+}
+
+/// `voter` casting `choice` on proposal `proposal_id` of `multisig` for the first time
+pub fn vote(voter: &Pubkey, multisig: &Pubkey, proposal_id: u64, choice: u8) -> Instruction {
+    let (_, proposal_bump) = proposal_address(multisig, proposal_id);
+    vote_with_data(voter, voter, multisig, proposal_id, vote_ix_data(proposal_id, choice, proposal_bump))
+}
+
+/// `voter` changing their earlier vote on proposal `proposal_id` of `multisig` to `choice`
+pub fn change_vote(voter: &Pubkey, multisig: &Pubkey, proposal_id: u64, choice: u8) -> Instruction {
+    let (_, proposal_bump) = proposal_address(multisig, proposal_id);
+    vote_with_data(voter, voter, multisig, proposal_id, change_vote_ix_data(proposal_id, choice, proposal_bump))
+}
+
+/// Accounts of a vote by `voter`, recorded in the receipt of `member`
+fn vote_with_data(voter: &Pubkey, member: &Pubkey, multisig: &Pubkey, proposal_id: u64, data: Vec<u8>) -> Instruction {
+    let (proposal_state, _) = proposal_address(multisig, proposal_id);
+
+    Instruction::new_with_bytes(
+        program_id(),
+        &data,
+        vec![
+            AccountMeta::new(*voter, true),
+            AccountMeta::new(*multisig, false),
+            AccountMeta::new(proposal_state, false),
+            AccountMeta::new(vote_state_address(multisig, proposal_id).0, false),
+            AccountMeta::new_readonly(multisig_config_address(multisig).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(receipt_address(&proposal_state, member).0, false),
+        ],
+    )
+}
+
+/// `delegate` casting the vote of `delegator`, the member at `delegator_index`
+pub fn delegated_vote(delegate: &Pubkey, delegator: &Pubkey, delegator_index: u8, multisig: &Pubkey, proposal_id: u64, choice: u8, change_vote: bool) -> Instruction {
+    let (_, proposal_bump) = proposal_address(multisig, proposal_id);
+    let data = delegated_vote_ix_data(proposal_id, choice, proposal_bump, change_vote, delegator_index);
+    vote_with_data(delegate, delegator, multisig, proposal_id, data)
+}
+
+/// `proposer` proposing an action of `proposal_type` with `payload` on `multisig`, open
+/// for `duration` seconds
+pub fn create_proposal(proposer: &Pubkey, multisig: &Pubkey, proposal_id: u64, duration: u64, proposal_type: u8, payload: &[u8]) -> Instruction {
+    let (proposal_state, proposal_bump) = proposal_address(multisig, proposal_id);
+
+    Instruction::new_with_bytes(
+        program_id(),
+        &create_proposal_ix_data(proposal_id, duration, proposal_bump, proposal_type, payload),
+        vec![
+            AccountMeta::new(*proposer, true),
+            AccountMeta::new(*multisig, false),
+            AccountMeta::new_readonly(multisig_config_address(multisig).0, false),
+            AccountMeta::new(proposal_state, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// `executor` executing proposal `proposal_id` of the multisig created with `multisig_seed`.
+/// `action_accounts` follow the fixed accounts, see `process_execute_proposal_instruction`.
+pub fn execute_proposal(executor: &Pubkey, multisig: &Pubkey, proposal_id: u64, multisig_seed: u64, action_accounts: &[AccountMeta]) -> Instruction {
+    let (proposal_state, proposal_bump) = proposal_address(multisig, proposal_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(*executor, true),
+        AccountMeta::new(*multisig, false),
+        AccountMeta::new(proposal_state, false),
+        AccountMeta::new(multisig_config_address(multisig).0, false),
+    ];
+    accounts.extend_from_slice(action_accounts);
+
+    Instruction::new_with_bytes(
+        program_id(),
+        &execute_proposal_ix_data(proposal_id, proposal_bump, multisig_seed),
+        accounts,
+    )
+}
+
+/// `config_authority` pausing or unpausing `multisig`
+pub fn emergency_pause(config_authority: &Pubkey, multisig: &Pubkey, paused: bool) -> Instruction {
+    Instruction::new_with_bytes(
+        program_id(),
+        &emergency_pause_ix_data(paused),
+        vec![
+            AccountMeta::new_readonly(*multisig, false),
+            AccountMeta::new(multisig_config_address(multisig).0, false),
+            AccountMeta::new_readonly(*config_authority, true),
+        ],
+    )
+}
+
+/// `config_authority` changing the threshold of `multisig`, or of one proposal type only
+pub fn change_threshold(config_authority: &Pubkey, multisig: &Pubkey, new_threshold: u64, proposal_type: Option<u8>) -> Instruction {
+    Instruction::new_with_bytes(
+        program_id(),
+        &change_threshold_ix_data(new_threshold, proposal_type),
+        vec![
+            AccountMeta::new_readonly(*multisig, false),
+            AccountMeta::new(multisig_config_address(multisig).0, false),
+            AccountMeta::new_readonly(*config_authority, true),
+        ],
+    )
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_instruction {
+    use super::*;
+    use crate::state::{ProposalType, VoteChoice};
+
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+
+    /// How the handlers read the leading proposal id
+    fn read_proposal_id(data: &[u8]) -> u64 {
+        u64::from_le_bytes(data[0..8].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_vote_data_matches_handler_layout() {
+        let data = vote_ix_data(12345, VoteChoice::Against as u8, 254);
+
+        assert!(matches!(MultisigInstructions::try_from(&data[0]), Ok(MultisigInstructions::Vote)));
+        // The handler sees the data without its discriminator
+        let payload = &data[1..];
+        assert_eq!(payload.len(), 10);
+        assert_eq!(read_proposal_id(payload), 12345);
+        assert_eq!((payload[8], payload[9]), (VoteChoice::Against as u8, 254));
+
+        assert_eq!(&change_vote_ix_data(12345, 1, 254)[10..], &[1]);
+        assert_eq!(&dry_run_vote_ix_data(12345, 1, 254)[10..], &[VOTE_FLAG_DRY_RUN]);
+        assert_eq!(&delegated_vote_ix_data(12345, 1, 254, false, 3)[10..], &[0, 3]);
+    }
+
+    #[test]
+    fn test_batch_vote_data_matches_handler_layout() {
+        let data = batch_vote_ix_data(&[vote_ix_data(1, 1, 250), delegated_vote_ix_data(2, 3, 251, false, 4)]);
+
+        assert!(matches!(MultisigInstructions::try_from(&data[0]), Ok(MultisigInstructions::BatchVote)));
+        assert_eq!(data[1], 2);
+
+        let first = &data[2..];
+        assert_eq!(u16::from_le_bytes([first[0], first[1]]), 10);
+        assert_eq!(read_proposal_id(&first[2..]), 1);
+        assert_eq!((first[10], first[11]), (1, 250));
+
+        let second = &first[12..];
+        assert_eq!(u16::from_le_bytes([second[0], second[1]]), 12);
+        assert_eq!(read_proposal_id(&second[2..]), 2);
+        assert_eq!(&second[10..], &[3, 251, 0, 4]);
+    }
+
+    #[test]
+    fn test_create_proposal_data_matches_handler_layout() {
+        let recipient = Pubkey::new_unique();
+        let mut payload = recipient.to_bytes().to_vec();
+        payload.extend_from_slice(&500u64.to_le_bytes());
+
+        let data = create_proposal_ix_data(7, 3_600, 253, ProposalType::Transfer as u8, &payload);
+
+        assert!(matches!(MultisigInstructions::try_from(&data[0]), Ok(MultisigInstructions::CreateProposal)));
+        let payload_start = 1 + 8 + 8 + 1 + 1;
+        assert_eq!(read_proposal_id(&data[1..]), 7);
+        assert_eq!(u64::from_le_bytes(data[9..17].try_into().unwrap()), 3_600);
+        assert_eq!((data[17], data[18]), (253, ProposalType::Transfer as u8));
+        assert_eq!(data.len() - payload_start, ProposalType::Transfer.payload_len());
+        assert_eq!(&data[payload_start..], payload.as_slice());
+    }
+
+    #[test]
+    fn test_stored_instruction_payload_matches_handler_layout() {
+        let target = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(target, &[9, 8, 7], vec![AccountMeta::new(account, true)]);
+
+        let payload = stored_instruction_payload(&instruction);
+
+        assert_eq!(&payload[..32], target.as_ref());
+        assert_eq!(payload[32], 1);
+        assert_eq!(&payload[33..65], account.as_ref());
+        assert_eq!((payload[65], payload[66]), (1, 1));
+        assert_eq!(u16::from_le_bytes(payload[67..69].try_into().unwrap()), 3);
+        assert_eq!(&payload[69..], &[9, 8, 7]);
+    }
+
+    #[test]
+    fn test_fixed_size_data_lengths() {
+        let expected = [
+            (execute_proposal_ix_data(1, 2, 3), MultisigInstructions::ExecuteProposal as u8, 17),
+            (cancel_proposal_ix_data(1, 2), MultisigInstructions::CancelProposal as u8, 9),
+            (close_proposal_ix_data(1, 2), MultisigInstructions::CloseProposal as u8, 9),
+            (veto_ix_data(1, 2), MultisigInstructions::Veto as u8, 9),
+            (get_proposal_result_ix_data(1, 2), MultisigInstructions::GetProposalResult as u8, 9),
+            (finalize_proposal_ix_data(1, 2), MultisigInstructions::FinalizeProposal as u8, 9),
+            (extend_proposal_expiry_ix_data(1, 2, 3), MultisigInstructions::ExtendProposalExpiry as u8, 17),
+            (change_threshold_ix_data(2, None), MultisigInstructions::ChangeThreshold as u8, 8),
+            (change_threshold_ix_data(2, Some(ProposalType::Transfer as u8)), MultisigInstructions::ChangeThreshold as u8, 9),
+            (emergency_pause_ix_data(true), MultisigInstructions::EmergencyPause as u8, 1),
+            (revoke_delegation_ix_data(true), MultisigInstructions::RevokeDelegation as u8, 1),
+            (update_expiry_policy_ix_data(3_600), MultisigInstructions::UpdateExpiryPolicy as u8, 8),
+            (change_config_ix_data(2, 3, 60), MultisigInstructions::ChangeConfig as u8, 24),
+            (rotate_config_authority_ix_data(&Pubkey::new_unique()), MultisigInstructions::RotateConfigAuthority as u8, 32),
+            (delegate_vote_ix_data(&Pubkey::new_unique()), MultisigInstructions::DelegateVote as u8, 32),
+            (set_veto_authority_ix_data(&Pubkey::new_unique()), MultisigInstructions::SetVetoAuthority as u8, 32),
+            (sweep_expired_proposals_ix_data(), MultisigInstructions::SweepExpiredProposals as u8, 0),
+            (withdraw_excess_rent_ix_data(), MultisigInstructions::WithdrawExcessRent as u8, 0),
+        ];
+
+        for (data, discriminator, payload_len) in expected {
+            assert_eq!(data[0], discriminator);
+            assert_eq!(data.len() - 1, payload_len, "discriminator {}", discriminator);
+        }
+    }
+
+    #[test]
+    fn test_vote_accounts_follow_canonical_order() {
+        let voter = Pubkey::new_unique();
+        let instruction = vote(&voter, &MULTISIG, 1, VoteChoice::For as u8);
+
+        let (proposal_state, _) = proposal_address(&MULTISIG, 1);
+        let keys: Vec<Pubkey> = instruction.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(keys, vec![
+            voter,
+            MULTISIG,
+            proposal_state,
+            vote_state_address(&MULTISIG, 1).0,
+            multisig_config_address(&MULTISIG).0,
+            system_program::ID,
+            receipt_address(&proposal_state, &voter).0,
+        ]);
+        assert!(instruction.accounts[0].is_signer);
+    }
+}
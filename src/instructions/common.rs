@@ -0,0 +1,171 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use pinocchio_system::instructions::{Allocate, Assign, CreateAccount, Transfer};
+
+use crate::{
+    error::MultisigError,
+    state::{Multisig, MAX_MEMBERS},
+};
+
+/// Fails with `MissingRequiredSignature` unless `account` signed the transaction
+pub fn require_signer(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer() {
+        log!("Error: Account {} must be a signer", account.key());
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+/// Fails with `InvalidAccountData` unless `account` was passed writable
+pub fn require_writable(account: &AccountInfo) -> ProgramResult {
+    if !account.is_writable() {
+        log!("Error: Account {} must be writable", account.key());
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+/// Fails with `AccountOutOfOrder` unless `matches`, logging the slot `account` was passed
+/// in and the account expected there. Checking each slot by role turns a misordered account
+/// list into an error naming the first misplaced slot, rather than a failed PDA check later.
+pub fn require_slot(account: &AccountInfo, slot: usize, expected: &str, matches: bool) -> ProgramResult {
+    if !matches {
+        log!("Error: Account {} in slot {} is not the {} account", account.key(), slot, expected);
+        return Err(MultisigError::AccountOutOfOrder.into());
+    }
+    Ok(())
+}
+
+/// Number of distinct members of `multisig` among the accounts in `signers` that signed.
+/// A member passed more than once counts once.
+pub fn count_member_signers(multisig: &Multisig, signers: &[AccountInfo]) -> usize {
+    let mut seen = [Pubkey::default(); MAX_MEMBERS];
+    let mut member_signatures = 0;
+    for signer in signers.iter().filter(|account| account.is_signer()) {
+        if multisig.is_member(signer.key()) && !seen[..member_signatures].contains(signer.key()) {
+            seen[member_signatures] = *signer.key();
+            member_signatures += 1;
+        }
+    }
+    member_signatures
+}
+
+/// Creates the program-owned PDA `account` with `space` bytes, `payer` funding its rent.
+/// `CreateAccount` fails on an address that already holds lamports, which anyone can send
+/// to a PDA before it is created, so such an account is topped up to rent exemption and
+/// then allocated and assigned instead.
+///
+/// Fails with `InsufficientFundsForRent` before any CPI when `payer` can't cover what the
+/// account is short of its rent-exempt minimum, rather than with the system program's error.
+pub fn create_pda_account(payer: &AccountInfo, account: &AccountInfo, space: usize, seeds: &[Seed]) -> ProgramResult {
+    let minimum_balance = Rent::get()?.minimum_balance(space);
+    let rent_due = minimum_balance.saturating_sub(account.lamports());
+
+    if payer.lamports() < rent_due {
+        log!("Error: {} holds {} lamports but {} are needed for rent", payer.key(), payer.lamports(), rent_due);
+        return Err(MultisigError::InsufficientFundsForRent.into());
+    }
+
+    if account.lamports() == 0 {
+        return CreateAccount {
+            from: payer,
+            to: account,
+            lamports: minimum_balance,
+            space: space as u64,
+            owner: &crate::ID,
+        }.invoke_signed(&[Signer::from(seeds)]);
+    }
+
+    log!("Account {} is pre-funded, allocating it in place", account.key());
+
+    if rent_due > 0 {
+        Transfer {
+            from: payer,
+            to: account,
+            lamports: rent_due,
+        }.invoke()?;
+    }
+
+    Allocate {
+        account,
+        space: space as u64,
+    }.invoke_signed(&[Signer::from(seeds)])?;
+
+    Assign {
+        account,
+        owner: &crate::ID,
+    }.invoke_signed(&[Signer::from(seeds)])
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_common {
+    use super::*;
+
+    /// Header the runtime serializes in front of every account, as `AccountInfo` reads it
+    #[repr(C)]
+    struct RawAccount {
+        borrow_state: u8,
+        is_signer: u8,
+        is_writable: u8,
+        executable: u8,
+        resize_delta: i32,
+        key: [u8; 32],
+        owner: [u8; 32],
+        lamports: u64,
+        data_len: u64,
+    }
+
+    fn with_account(is_signer: bool, is_writable: bool, f: impl FnOnce(&AccountInfo)) {
+        let mut raw = RawAccount {
+            borrow_state: u8::MAX,
+            is_signer: is_signer as u8,
+            is_writable: is_writable as u8,
+            executable: 0,
+            resize_delta: 0,
+            key: [0x01; 32],
+            owner: [0; 32],
+            lamports: 0,
+            data_len: 0,
+        };
+        let account: AccountInfo = unsafe { core::mem::transmute(&mut raw as *mut RawAccount) };
+        f(&account);
+    }
+
+    #[test]
+    fn test_require_signer() {
+        with_account(true, false, |account| assert!(require_signer(account).is_ok()));
+        with_account(false, true, |account| {
+            assert_eq!(require_signer(account), Err(ProgramError::MissingRequiredSignature));
+        });
+    }
+
+    #[test]
+    fn test_require_writable() {
+        with_account(false, true, |account| assert!(require_writable(account).is_ok()));
+        with_account(true, false, |account| {
+            assert_eq!(require_writable(account), Err(ProgramError::InvalidAccountData));
+        });
+    }
+
+    #[test]
+    fn test_require_slot() {
+        with_account(false, false, |account| {
+            assert!(require_slot(account, 1, "multisig", true).is_ok());
+            assert_eq!(
+                require_slot(account, 1, "multisig", false),
+                Err(ProgramError::Custom(MultisigError::AccountOutOfOrder as u32))
+            );
+        });
+    }
+}
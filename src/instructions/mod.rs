@@ -1,13 +1,100 @@
-pub mod init_multisig;
-pub use init_multisig::*;
+pub mod common;
+
 
 pub mod process_vote;
 pub use process_vote::*;
 
+pub mod process_create_multisig;
+pub use process_create_multisig::*;
+
+pub mod process_add_member;
+pub use process_add_member::*;
+
+pub mod process_remove_member;
+pub use process_remove_member::*;
+
+pub mod process_execute_proposal;
+pub use process_execute_proposal::*;
+
+pub mod process_cancel_proposal;
+pub use process_cancel_proposal::*;
+
+pub mod process_close_proposal;
+pub use process_close_proposal::*;
+
+pub mod process_create_proposal;
+pub use process_create_proposal::*;
+
+pub mod process_extend_proposal_expiry;
+pub use process_extend_proposal_expiry::*;
+
+pub mod process_delegate_vote;
+pub use process_delegate_vote::*;
+
+pub mod process_change_threshold;
+pub use process_change_threshold::*;
+
+pub mod process_veto;
+pub use process_veto::*;
+
+pub mod process_initialize_config;
+pub use process_initialize_config::*;
+
+pub mod process_get_proposal_result;
+pub use process_get_proposal_result::*;
+
+pub mod process_batch_vote;
+pub use process_batch_vote::*;
+
+pub mod process_update_member_weight;
+pub use process_update_member_weight::*;
+
+pub mod process_transfer_from_multisig;
+pub use process_transfer_from_multisig::*;
+
+pub mod process_sweep_expired_proposals;
+pub use process_sweep_expired_proposals::*;
+
+pub mod process_rotate_config_authority;
+pub use process_rotate_config_authority::*;
+
+pub mod process_close_multisig;
+pub use process_close_multisig::*;
+
+pub mod process_emergency_pause;
+pub use process_emergency_pause::*;
+
+pub mod process_set_veto_authority;
+pub use process_set_veto_authority::*;
+
+pub mod process_revoke_delegation;
+pub use process_revoke_delegation::*;
+
+pub mod process_finalize_proposal;
+pub use process_finalize_proposal::*;
+
+pub mod process_update_expiry_policy;
+pub use process_update_expiry_policy::*;
+
+pub mod process_deposit_to_treasury;
+pub use process_deposit_to_treasury::*;
+
+pub mod process_set_members_root;
+pub use process_set_members_root::*;
+
+pub mod process_replace_member;
+pub use process_replace_member::*;
+
+pub mod process_change_config;
+pub use process_change_config::*;
+
+pub mod process_withdraw_excess_rent;
+pub use process_withdraw_excess_rent::*;
+
 use pinocchio::program_error::ProgramError;
 
 pub enum MultisigInstructions {
-    InitMultisig = 0, // Johnny + Raunit 
+    // 0 was the legacy InitMultisig, superseded by CreateMultisig and no longer accepted
     //update expiry
     //update threshold
     //update members
@@ -16,6 +103,32 @@ pub enum MultisigInstructions {
     Vote = 3, // Shrinath + Mohammed + shradesh
     // will close if expiry achieved & votes < threshold || execute if votes >= threshold
     CloseProposal = 4, // Nanasi + Mishal + Apaar + Ghazal 
+    CreateMultisig = 5,
+    AddMember = 6,
+    RemoveMember = 7,
+    ExecuteProposal = 8,
+    CancelProposal = 9,
+    ExtendProposalExpiry = 10,
+    DelegateVote = 11,
+    ChangeThreshold = 12,
+    Veto = 13,
+    InitializeConfig = 14,
+    GetProposalResult = 15,
+    BatchVote = 16,
+    UpdateMemberWeight = 17,
+    SweepExpiredProposals = 18,
+    RotateConfigAuthority = 19,
+    CloseMultisig = 20,
+    EmergencyPause = 21,
+    SetVetoAuthority = 22,
+    RevokeDelegation = 23,
+    FinalizeProposal = 24,
+    UpdateExpiryPolicy = 25,
+    DepositToTreasury = 26,
+    SetMembersRoot = 27,
+    ReplaceMember = 28,
+    ChangeConfig = 29,
+    WithdrawExcessRent = 30,
 
     //Santoshi CHAD own version
 }
@@ -25,11 +138,36 @@ impl TryFrom<&u8> for MultisigInstructions {
 
     fn try_from(value: &u8) -> Result<Self, Self::Error> {
         match *value {
-            0 => Ok(MultisigInstructions::InitMultisig),
             1 => Ok(MultisigInstructions::UpdateMultisig),
             2 => Ok(MultisigInstructions::CreateProposal),
             3 => Ok(MultisigInstructions::Vote),
             4 => Ok(MultisigInstructions::CloseProposal),
+            5 => Ok(MultisigInstructions::CreateMultisig),
+            6 => Ok(MultisigInstructions::AddMember),
+            7 => Ok(MultisigInstructions::RemoveMember),
+            8 => Ok(MultisigInstructions::ExecuteProposal),
+            9 => Ok(MultisigInstructions::CancelProposal),
+            10 => Ok(MultisigInstructions::ExtendProposalExpiry),
+            11 => Ok(MultisigInstructions::DelegateVote),
+            12 => Ok(MultisigInstructions::ChangeThreshold),
+            13 => Ok(MultisigInstructions::Veto),
+            14 => Ok(MultisigInstructions::InitializeConfig),
+            15 => Ok(MultisigInstructions::GetProposalResult),
+            16 => Ok(MultisigInstructions::BatchVote),
+            17 => Ok(MultisigInstructions::UpdateMemberWeight),
+            18 => Ok(MultisigInstructions::SweepExpiredProposals),
+            19 => Ok(MultisigInstructions::RotateConfigAuthority),
+            20 => Ok(MultisigInstructions::CloseMultisig),
+            21 => Ok(MultisigInstructions::EmergencyPause),
+            22 => Ok(MultisigInstructions::SetVetoAuthority),
+            23 => Ok(MultisigInstructions::RevokeDelegation),
+            24 => Ok(MultisigInstructions::FinalizeProposal),
+            25 => Ok(MultisigInstructions::UpdateExpiryPolicy),
+            26 => Ok(MultisigInstructions::DepositToTreasury),
+            27 => Ok(MultisigInstructions::SetMembersRoot),
+            28 => Ok(MultisigInstructions::ReplaceMember),
+            29 => Ok(MultisigInstructions::ChangeConfig),
+            30 => Ok(MultisigInstructions::WithdrawExcessRent),
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
@@ -0,0 +1,213 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::common::{require_signer, require_writable},
+    state::{is_empty_slot, Multisig, MultisigConfig, MAX_MEMBERS},
+};
+
+/// Adds a new member to the multisig.
+///
+/// The multisig PDA itself must sign, which only happens when the program invokes
+/// this instruction on behalf of an approved proposal. The threshold is an absolute
+/// count, so adding a member never pushes it above `num_members` and it is left as is.
+///
+/// Accounts: [multisig, multisig_config]
+///
+/// Instruction data (after the discriminator): [new_member: Pubkey]
+pub fn process_add_member_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 32 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(multisig)?;
+    require_writable(multisig)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let new_member: Pubkey = data[0..32].try_into().unwrap();
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+    let multisig_config_data = MultisigConfig::from_account_info(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let num_members = multisig_data.num_members as usize;
+
+    if num_members >= MAX_MEMBERS {
+        log!("Error: Multisig already has the maximum of {} members", MAX_MEMBERS);
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    if is_empty_slot(&new_member) {
+        log!("Error: The default pubkey can't be a member");
+        return Err(ProgramError::InvalidArgument);
+    };
+
+    if multisig_data.is_member(&new_member) {
+        log!("Error: Member already exists");
+        return Err(ProgramError::InvalidArgument);
+    };
+
+    // Keeps the member list sorted so lookups can binary search it
+    multisig_data.insert_member(new_member, 1);
+
+    if multisig_config_data.min_threshold > multisig_data.num_members as u64 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    log!("Member added, multisig now has {} members", multisig_data.num_members);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_add_member_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const MEMBER: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+
+    fn multisig_account(members: &[Pubkey]) -> Account {
+        let mut data = vec![0u8; Multisig::LEN];
+        data[offset_of!(Multisig, version)] = Multisig::VERSION;
+        data[offset_of!(Multisig, num_members)] = members.len() as u8;
+        let members_offset = offset_of!(Multisig, members);
+        for (i, member) in members.iter().enumerate() {
+            data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+        }
+        Account::new_data(1 * LAMPORTS_PER_SOL, &data, &ID).unwrap()
+    }
+
+    fn config_account(min_threshold: u64, bump: u8) -> Account {
+        let mut data = vec![0u8; MultisigConfig::LEN];
+        data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+        data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&min_threshold.to_le_bytes());
+        data[offset_of!(MultisigConfig, bump)] = bump;
+        Account::new_data(1 * LAMPORTS_PER_SOL, &data, &ID).unwrap()
+    }
+
+    fn add_member(members: &[Pubkey], new_member: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>) {
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let mut data = vec![6u8]; // Instruction discriminator for add member
+        data.extend_from_slice(new_member.as_ref());
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(MULTISIG, true),                     // multisig (signer via CPI)
+                AccountMeta::new_readonly(multisig_config_pda, false), // multisig_config
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MULTISIG, multisig_account(members)),
+            (multisig_config_pda, config_account(1, config_bump)),
+        ];
+
+        (instruction, tx_accounts)
+    }
+
+    #[test]
+    fn test_add_member() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [MEMBER, Pubkey::new_unique()];
+        let new_member = Pubkey::new_unique();
+        let (instruction, tx_accounts) = add_member(&members, new_member);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let multisig = &result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        let multisig: Multisig = bytemuck::pod_read_unaligned(&multisig.data);
+        assert_eq!(multisig.num_members, 3);
+
+        // Members end up sorted, with the new member among them
+        let mut expected = [MEMBER.to_bytes(), members[1].to_bytes(), new_member.to_bytes()];
+        expected.sort();
+        assert_eq!(&multisig.members[..3], &expected);
+        assert_eq!(multisig.members_sorted, 1);
+        assert!(multisig.is_member(&new_member.to_bytes()));
+    }
+
+    #[test]
+    fn test_add_member_rejects_duplicate() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let existing = Pubkey::new_unique();
+        let members = [MEMBER, existing];
+        let (instruction, tx_accounts) = add_member(&members, existing);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidArgument)],
+        );
+    }
+
+    #[test]
+    fn test_add_member_capacity_boundary() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // One slot left: the last member fits
+        let members: Vec<Pubkey> = (0..MAX_MEMBERS - 1).map(|_| Pubkey::new_unique()).collect();
+        let (instruction, tx_accounts) = add_member(&members, Pubkey::new_unique());
+        mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        // Full: nothing more fits
+        let members: Vec<Pubkey> = (0..MAX_MEMBERS).map(|_| Pubkey::new_unique()).collect();
+        let (instruction, tx_accounts) = add_member(&members, Pubkey::new_unique());
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::instructions::process_vote_instruction;
+
+/// Most votes a single batch may cast, keeping the instruction within the compute budget
+pub const MAX_BATCH_VOTES: usize = 8;
+
+/// Size of the length prefix in front of every batch entry
+const BATCH_ENTRY_PREFIX_LEN: usize = 2;
+
+/// Casts the signer's vote on several proposals at once.
+///
+/// Each entry is a full `Vote` payload and goes through `process_vote_instruction`
+/// unchanged, so every vote gets the same validation and options as a single vote: flags,
+/// a delegator byte or a membership proof. Any failing entry fails the whole instruction,
+/// and the runtime reverts the votes already applied.
+///
+/// Accounts: [voter, multisig, multisig_config, system_program, ..(proposal_state, vote_state, vote_receipt) per entry]
+///
+/// Instruction data (after the discriminator): [num_votes: u8] followed by num_votes entries of
+/// [entry_len: u16][vote payload: entry_len bytes], the payload laid out as `Vote`'s data
+pub fn process_batch_vote_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    let Some((&num_votes, mut entries)) = data.split_first() else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let num_votes = num_votes as usize;
+
+    if num_votes == 0 || num_votes > MAX_BATCH_VOTES {
+        log!("Error: A batch must hold between 1 and {} votes", MAX_BATCH_VOTES);
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [voter, multisig, multisig_config, system_program, proposal_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if proposal_accounts.len() < num_votes * 3 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    for entry_accounts in proposal_accounts.chunks_exact(3).take(num_votes) {
+        if entries.len() < BATCH_ENTRY_PREFIX_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let entry_len = u16::from_le_bytes(entries[..BATCH_ENTRY_PREFIX_LEN].try_into().unwrap()) as usize;
+
+        let Some(entry) = entries.get(BATCH_ENTRY_PREFIX_LEN..BATCH_ENTRY_PREFIX_LEN + entry_len) else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        entries = &entries[BATCH_ENTRY_PREFIX_LEN + entry_len..];
+
+        let vote_accounts = [
+            voter.clone(),
+            multisig.clone(),
+            entry_accounts[0].clone(),
+            entry_accounts[1].clone(),
+            multisig_config.clone(),
+            system_program.clone(),
+            entry_accounts[2].clone(),
+        ];
+
+        process_vote_instruction(&vote_accounts, entry)?;
+    }
+
+    // Every byte has to belong to an entry
+    if !entries.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    log!("Batch of {} votes processed", num_votes);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_batch_vote_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::{
+        error::MultisigError,
+        state::{Multisig, MultisigConfig, ProposalState, ProposalStatus},
+    };
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const VOTER: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const DELEGATOR: Pubkey = Pubkey::new_from_array([0x03; 32]);
+
+    /// One batch entry per (proposal_id, vote_choice), each against a fresh Active proposal
+    fn batch_vote(votes: &[(u64, u8)]) -> (Instruction, Vec<(Pubkey, Account)>, Vec<Pubkey>) {
+        batch_vote_for(votes, VOTER, &[])
+    }
+
+    /// `batch_vote` with `vote_suffix` appended to every entry, the votes recorded for `member`.
+    /// DELEGATOR has delegated its vote to VOTER.
+    fn batch_vote_for(votes: &[(u64, u8)], member: Pubkey, vote_suffix: &[u8]) -> (Instruction, Vec<(Pubkey, Account)>, Vec<Pubkey>) {
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let members = [VOTER, DELEGATOR];
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = members.len() as u8;
+            let members_offset = offset_of!(Multisig, members);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data[offset_of!(Multisig, delegations) + 32..][..32].copy_from_slice(VOTER.as_ref());
+            data
+        };
+
+        // A threshold of 2 keeps every proposal Active after a single vote
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&2u64.to_le_bytes());
+            data[offset_of!(MultisigConfig, bump)] = config_bump;
+            data
+        };
+
+        let mut data = vec![16u8]; // Instruction discriminator for batch vote
+        data.push(votes.len() as u8);
+
+        let mut account_metas = vec![
+            AccountMeta::new(VOTER, true),
+            AccountMeta::new(MULTISIG, false),
+            AccountMeta::new(multisig_config_pda, false),
+            AccountMeta::new_readonly(system_program_id, false),
+        ];
+        let mut tx_accounts = vec![
+            (VOTER, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+            (system_program_id, system_account),
+        ];
+        let mut proposal_pdas = Vec::new();
+
+        for &(proposal_id, vote_choice) in votes {
+            let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+                &[b"proposal", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
+                &ID,
+            );
+            let (vote_state_pda, _) = Pubkey::find_program_address(
+                &[b"vote_state", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
+                &ID,
+            );
+            let (vote_receipt_pda, _) = Pubkey::find_program_address(
+                &[b"receipt", proposal_state_pda.as_ref(), member.as_ref()],
+                &ID,
+            );
+
+            let proposal_data = {
+                let mut data = vec![0u8; ProposalState::BASE_LEN];
+                data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&proposal_id.to_le_bytes());
+                data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&u64::MAX.to_le_bytes());
+                data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
+                data[offset_of!(ProposalState, bump)] = proposal_bump;
+                data[offset_of!(ProposalState, active_member_count)] = members.len() as u8;
+                let active_members_offset = offset_of!(ProposalState, active_members);
+                for (i, member) in members.iter().enumerate() {
+                    data[active_members_offset + i * 32..active_members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+                }
+                data
+            };
+
+            data.extend_from_slice(&(10 + vote_suffix.len() as u16).to_le_bytes());
+            data.extend_from_slice(&proposal_id.to_le_bytes());
+            data.push(vote_choice);
+            data.push(proposal_bump);
+            data.extend_from_slice(vote_suffix);
+
+            account_metas.push(AccountMeta::new(proposal_state_pda, false));
+            account_metas.push(AccountMeta::new(vote_state_pda, false));
+            account_metas.push(AccountMeta::new(vote_receipt_pda, false));
+            tx_accounts.push((proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &proposal_data, &ID).unwrap()));
+            tx_accounts.push((vote_state_pda, Account::new(0, 0, &system_program_id)));
+            tx_accounts.push((vote_receipt_pda, Account::new(0, 0, &system_program_id)));
+            proposal_pdas.push(proposal_state_pda);
+        }
+
+        (Instruction::new_with_bytes(ID, &data, account_metas), tx_accounts, proposal_pdas)
+    }
+
+    fn tallies(accounts: &[(Pubkey, Account)], proposal_state_pda: &Pubkey) -> (u64, u64, u64) {
+        let proposal_account = &accounts.iter().find(|(key, _)| key == proposal_state_pda).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        (proposal.for_votes, proposal.against_votes, proposal.abstain_votes)
+    }
+
+    #[test]
+    fn test_batch_votes_on_three_proposals() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, proposal_pdas) = batch_vote(&[(1, 1), (2, 2), (3, 3)]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        assert_eq!(tallies(&result.resulting_accounts, &proposal_pdas[0]), (1, 0, 0));
+        assert_eq!(tallies(&result.resulting_accounts, &proposal_pdas[1]), (0, 1, 0));
+        assert_eq!(tallies(&result.resulting_accounts, &proposal_pdas[2]), (0, 0, 1));
+    }
+
+    #[test]
+    fn test_batch_votes_count_toward_participation() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = batch_vote(&[(1, 1), (2, 2), (3, 3)]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let multisig_account = &result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        let multisig: Multisig = bytemuck::pod_read_unaligned(&multisig_account.data);
+        assert_eq!(multisig.participation_count[..2], [3, 0]);
+    }
+
+    #[test]
+    fn test_batch_casts_delegated_votes() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // No flags, then the delegator's index in `Multisig.members`
+        let (instruction, tx_accounts, proposal_pdas) = batch_vote_for(&[(1, 1), (2, 2)], DELEGATOR, &[0, 1]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        assert_eq!(tallies(&result.resulting_accounts, &proposal_pdas[0]), (1, 0, 0));
+        assert_eq!(tallies(&result.resulting_accounts, &proposal_pdas[1]), (0, 1, 0));
+
+        let multisig_account = &result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        let multisig: Multisig = bytemuck::pod_read_unaligned(&multisig_account.data);
+        assert_eq!(multisig.participation_count[..2], [0, 2]);
+    }
+
+    #[test]
+    fn test_batch_rejects_trailing_bytes() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (mut instruction, tx_accounts, _) = batch_vote(&[(1, 1)]);
+        instruction.data.push(0);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_batch_fails_as_a_whole_when_one_vote_is_invalid() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // The second entry carries an invalid vote choice
+        let (instruction, tx_accounts, _) = batch_vote(&[(1, 1), (2, 0), (3, 1)]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::InvalidVoteChoice as u32))],
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::common::{require_signer, require_writable},
+    state::{ProposalState, ProposalStatus},
+};
+
+/// Withdraws an Active proposal before it expires.
+///
+/// Only the proposer recorded on the proposal may cancel it. Cancelled is a terminal
+/// state, so the vote instruction rejects any further votes on it.
+///
+/// Accounts: [proposer, multisig, proposal_state]
+///
+/// Instruction data (after the discriminator): [proposal_id: u64][proposal_bump: u8]
+pub fn process_cancel_proposal_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 9 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [proposer, multisig, proposal_state, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(proposer)?;
+    require_writable(proposal_state)?;
+
+    for accounts in [multisig, proposal_state] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let proposal_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let proposal_bump = data[8];
+
+    let proposal_data = ProposalState::from_account_info(proposal_state)?;
+
+    let proposal_seed = [
+        b"proposal".as_slice(),
+        multisig.key().as_slice(),
+        &proposal_id.to_le_bytes(),
+        &[proposal_bump],
+    ];
+    let proposal_pda = pubkey::checked_create_program_address(&proposal_seed, &crate::ID)?;
+
+    if &proposal_pda != proposal_state.key() || proposal_data.proposal_id != proposal_id {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if proposer.key() != &proposal_data.proposer {
+        log!("Error: Only the proposer can cancel this proposal");
+        return Err(ProgramError::IncorrectAuthority);
+    }
+
+    match proposal_data.status()? {
+        ProposalStatus::Active => {},
+        _ => return Err(ProgramError::InvalidAccountData), // Proposal already reached a terminal state
+    };
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    if proposal_data.is_expired(current_time) {
+        log!("Proposal has expired");
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    proposal_data.set_status(ProposalStatus::Cancelled);
+
+    log!("Proposal {} cancelled by proposer", proposal_id);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_cancel_proposal_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const PROPOSER: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const PROPOSAL_ID: u64 = 1;
+
+    fn cancel_proposal(signer: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+
+        let proposal_data = {
+            let mut data = vec![0u8; ProposalState::BASE_LEN];
+            data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&PROPOSAL_ID.to_le_bytes());
+            data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&u64::MAX.to_le_bytes());
+            data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
+            data[offset_of!(ProposalState, bump)] = proposal_bump;
+            data[offset_of!(ProposalState, proposer)..][..32].copy_from_slice(PROPOSER.as_ref());
+            data
+        };
+
+        let mut data = vec![9u8]; // Instruction discriminator for cancel proposal
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(proposal_bump);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(signer, true),        // proposer (signer)
+                AccountMeta::new_readonly(MULTISIG, false),     // multisig
+                AccountMeta::new(proposal_state_pda, false),    // proposal_state
+            ],
+        );
+
+        let tx_accounts = vec![
+            (signer, Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default())),
+            (MULTISIG, Account::new(1 * LAMPORTS_PER_SOL, 0, &ID)),
+            (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &proposal_data, &ID).unwrap()),
+        ];
+
+        (instruction, tx_accounts, proposal_state_pda)
+    }
+
+    #[test]
+    fn test_cancel_proposal_by_proposer() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, proposal_state_pda) = cancel_proposal(PROPOSER);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Cancelled as u8);
+    }
+
+    #[test]
+    fn test_cancel_proposal_rejects_non_proposer() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = cancel_proposal(Pubkey::new_unique());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::IncorrectAuthority)],
+        );
+    }
+}
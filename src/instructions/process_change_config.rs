@@ -0,0 +1,235 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::common::{require_signer, require_writable},
+    state::{Multisig, MultisigConfig, MAX_EXECUTION_DELAY},
+};
+
+/// Applies an executed `ProposalType::ConfigChange`: sets the general threshold, the
+/// quorum and the execution delay together. This is how a config `governed_by_proposals`
+/// changes its parameters.
+///
+/// The multisig PDA itself must sign, which only happens when the program invokes this
+/// instruction on behalf of an approved proposal. As with `ChangeThreshold`, proposals
+/// already created keep the threshold, quorum and delay they snapshotted.
+///
+/// The threshold must lie between 1 and the eligible members, the quorum can't exceed
+/// their combined weight and the delay can't exceed `MAX_EXECUTION_DELAY`, so no change
+/// can leave the config unable to pass the proposal that would fix it.
+///
+/// Accounts: [multisig, multisig_config]
+///
+/// Instruction data (after the discriminator): [min_threshold: u64][quorum: u64][execution_delay: u64]
+pub fn process_change_config_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 24 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(multisig)?;
+    require_writable(multisig_config)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let min_threshold = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let quorum = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let execution_delay = u64::from_le_bytes(data[16..24].try_into().unwrap());
+
+    let multisig_data = Multisig::from_account_info(multisig)?;
+    let multisig_config_data = MultisigConfig::from_account_info_mut(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let (eligible_members, eligible_weight) = multisig_data.eligible_voters();
+
+    if min_threshold == 0 || min_threshold > eligible_members {
+        log!("Error: Threshold must be between 1 and the number of members");
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    if quorum > eligible_weight {
+        log!("Error: Quorum can't exceed the members' combined weight of {}", eligible_weight);
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    if execution_delay > MAX_EXECUTION_DELAY {
+        log!("Error: Execution delay can't exceed {} seconds", MAX_EXECUTION_DELAY);
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    multisig_config_data.min_threshold = min_threshold;
+    multisig_config_data.quorum = quorum;
+    multisig_config_data.execution_delay = execution_delay;
+
+    log!(
+        "Config changed: threshold {}, quorum {}, execution delay {}",
+        min_threshold,
+        quorum,
+        execution_delay
+    );
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_change_config_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+
+    fn change_config(multisig_signs: bool, min_threshold: u64, quorum: u64, execution_delay: u64) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = 3;
+            let members_offset = offset_of!(Multisig, members);
+            for i in 0..3 {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(Pubkey::new_unique().as_ref());
+            }
+            data
+        };
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&1u64.to_le_bytes());
+            data[offset_of!(MultisigConfig, governed_by_proposals)] = 1;
+            data[offset_of!(MultisigConfig, bump)] = config_bump;
+            data
+        };
+
+        let mut data = vec![29u8]; // Instruction discriminator for change config
+        data.extend_from_slice(&min_threshold.to_le_bytes());
+        data.extend_from_slice(&quorum.to_le_bytes());
+        data.extend_from_slice(&execution_delay.to_le_bytes());
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(MULTISIG, multisig_signs), // multisig (signer via CPI)
+                AccountMeta::new(multisig_config_pda, false),        // multisig_config
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+        ];
+
+        (instruction, tx_accounts, multisig_config_pda)
+    }
+
+    #[test]
+    fn test_change_config_sets_threshold_quorum_and_delay() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, multisig_config_pda) = change_config(true, 3, 2, 600);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let config_account = &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let config: MultisigConfig = bytemuck::pod_read_unaligned(&config_account.data);
+        assert_eq!((config.min_threshold, config.quorum, config.execution_delay), (3, 2, 600));
+    }
+
+    #[test]
+    fn test_change_config_requires_multisig_signature() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = change_config(false, 2, 2, 600);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::MissingRequiredSignature)],
+        );
+    }
+
+    #[test]
+    fn test_change_config_rejects_threshold_above_member_count() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = change_config(true, 4, 2, 600);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_change_config_rejects_quorum_above_member_weight() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // Three members of weight 1 can cast at most 3 votes
+        let (instruction, tx_accounts, _) = change_config(true, 2, 4, 600);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_change_config_rejects_execution_delay_above_limit() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = change_config(true, 2, 2, MAX_EXECUTION_DELAY + 1);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
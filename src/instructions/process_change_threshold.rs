@@ -0,0 +1,306 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::common::{count_member_signers, require_writable},
+    state::{Multisig, MultisigConfig, ProposalType, MAX_MEMBERS},
+};
+
+/// Updates `MultisigConfig.min_threshold`, or with a proposal type given, the threshold
+/// of that type alone in `MultisigConfig.type_thresholds`. A type threshold of 0 clears
+/// it, so the type falls back to the general threshold.
+///
+/// Authorized either by the multisig PDA signing (when invoked from an approved proposal)
+/// or by the config's `config_authority` signing this instruction directly, passed among
+/// the trailing accounts. Configs without a config authority fall back to a strict
+/// majority of the current members signing. A config `governed_by_proposals` only takes
+/// the multisig PDA's signature.
+///
+/// Proposals snapshot their required votes at creation, so the new threshold only
+/// applies to proposals created after the change; in-flight proposals keep the old one.
+///
+/// Accounts: [multisig, multisig_config, ..member signers]
+///
+/// Instruction data (after the discriminator): [new_threshold: u64] followed optionally by
+/// [proposal_type: u8]
+pub fn process_change_threshold_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if !matches!(data.len(), 8 | 9) {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig_config)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let new_threshold = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let proposal_type = data.get(8).map(ProposalType::try_from).transpose()?;
+
+    let multisig_data = Multisig::from_account_info(multisig)?;
+    let multisig_config_data = MultisigConfig::from_account_info_mut(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let num_members = multisig_data.num_members as usize;
+
+    if num_members > MAX_MEMBERS {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !multisig.is_signer() {
+        multisig_config_data.require_direct_changes_allowed()?;
+    }
+
+    if !multisig.is_signer() && multisig_config_data.config_authority != Pubkey::default() {
+        multisig_config_data.check_config_authority(signers)?;
+    } else if !multisig.is_signer() {
+        let member_signatures = count_member_signers(multisig_data, signers);
+
+        if member_signatures * 2 <= num_members {
+            log!("Error: {} of {} members signed, a majority is required", member_signatures, num_members);
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+    }
+
+    let (eligible_members, _) = multisig_data.eligible_voters();
+
+    // A threshold of 1 is the hard floor; only a type threshold can be 0, which clears it
+    let cleared = proposal_type.is_some() && new_threshold == 0;
+    if !cleared && (new_threshold == 0 || new_threshold > eligible_members) {
+        log!("Error: Threshold must be between 1 and the number of members");
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    match proposal_type {
+        Some(proposal_type) => {
+            multisig_config_data.type_thresholds[proposal_type as usize] = new_threshold;
+            log!("Threshold of proposal type {} changed to {}", proposal_type as u8, new_threshold);
+        },
+        None => {
+            multisig_config_data.min_threshold = new_threshold;
+            log!("Threshold changed to {}", new_threshold);
+        },
+    }
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_change_threshold_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::error::MultisigError;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+
+    fn change_threshold(members: &[Pubkey], signers: &[Pubkey], config_authority: Pubkey, new_threshold: u64) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = members.len() as u8;
+            let members_offset = offset_of!(Multisig, members);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data
+        };
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&1u64.to_le_bytes());
+            data[offset_of!(MultisigConfig, config_authority)..][..32].copy_from_slice(config_authority.as_ref());
+            data[offset_of!(MultisigConfig, bump)] = config_bump;
+            data
+        };
+
+        let mut data = vec![12u8]; // Instruction discriminator for change threshold
+        data.extend_from_slice(&new_threshold.to_le_bytes());
+
+        let mut account_metas = vec![
+            AccountMeta::new_readonly(MULTISIG, false),   // multisig
+            AccountMeta::new(multisig_config_pda, false), // multisig_config
+        ];
+        let mut tx_accounts = vec![
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+        ];
+        for signer in signers {
+            account_metas.push(AccountMeta::new_readonly(*signer, true));
+            tx_accounts.push((*signer, Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default())));
+        }
+
+        (Instruction::new_with_bytes(ID, &data, account_metas), tx_accounts, multisig_config_pda)
+    }
+
+    #[test]
+    fn test_majority_of_members_changes_threshold() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, multisig_config_pda) = change_threshold(&members, &members[..2], Pubkey::default(), 3);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let config = &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let threshold_offset = offset_of!(MultisigConfig, min_threshold);
+        assert_eq!(u64::from_le_bytes(config.data[threshold_offset..threshold_offset + 8].try_into().unwrap()), 3);
+    }
+
+    #[test]
+    fn test_minority_of_members_cannot_change_threshold() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = change_threshold(&members, &members[..2], Pubkey::default(), 3);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::MissingRequiredSignature)],
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_threshold_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        for new_threshold in [0, 4] {
+            let (instruction, tx_accounts, _) = change_threshold(&members, &members, Pubkey::default(), new_threshold);
+
+            mollusk.process_and_validate_instruction(
+                &instruction,
+                &tx_accounts,
+                &[Check::err(ProgramError::InvalidInstructionData)],
+            );
+        }
+    }
+
+    #[test]
+    fn test_members_root_bounds_threshold_by_root_member_count() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // The root holds 5 voters, more than `members`, so 5 is reachable and 6 isn't
+        for (new_threshold, check) in [(5, Check::success()), (6, Check::err(ProgramError::InvalidInstructionData))] {
+            let (instruction, mut tx_accounts, _) = change_threshold(&members, &members, Pubkey::default(), new_threshold);
+            let multisig = &mut tx_accounts[0].1;
+            multisig.data[offset_of!(Multisig, members_root)..][..32].copy_from_slice(&[7u8; 32]);
+            multisig.data[offset_of!(Multisig, root_member_count)..][..8].copy_from_slice(&5u64.to_le_bytes());
+
+            mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[check]);
+        }
+    }
+
+    #[test]
+    fn test_config_authority_changes_threshold() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let config_authority = Pubkey::new_unique();
+        let (instruction, tx_accounts, multisig_config_pda) = change_threshold(&members, &[config_authority], config_authority, 2);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let config = &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let threshold_offset = offset_of!(MultisigConfig, min_threshold);
+        assert_eq!(u64::from_le_bytes(config.data[threshold_offset..threshold_offset + 8].try_into().unwrap()), 2);
+    }
+
+    #[test]
+    fn test_config_governed_by_proposals_rejects_config_authority() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let config_authority = Pubkey::new_unique();
+        let (instruction, mut tx_accounts, multisig_config_pda) = change_threshold(&members, &[config_authority], config_authority, 2);
+        let config = &mut tx_accounts.iter_mut().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        config.data[offset_of!(MultisigConfig, governed_by_proposals)] = 1;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::UnauthorizedConfigChange as u32))],
+        );
+    }
+
+    #[test]
+    fn test_members_cannot_bypass_config_authority() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // Every member signs, but only the config authority may change the config directly
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = change_threshold(&members, &members, Pubkey::new_unique(), 2);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::UnauthorizedConfigChange as u32))],
+        );
+    }
+
+    #[test]
+    fn test_type_threshold_leaves_general_threshold() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (mut instruction, tx_accounts, multisig_config_pda) = change_threshold(&members, &members, Pubkey::default(), 3);
+        instruction.data.push(ProposalType::Transfer as u8);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let config: MultisigConfig = bytemuck::pod_read_unaligned(
+            &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1.data,
+        );
+        assert_eq!(config.type_thresholds[ProposalType::Transfer as usize], 3);
+        assert_eq!(config.min_threshold, 1);
+    }
+}
@@ -0,0 +1,312 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::{close_account, common::{count_member_signers, require_writable}},
+    state::{Multisig, MultisigConfig, ProposalState, ProposalStatus, ProposalType, MAX_MEMBERS},
+};
+
+/// Winds a multisig down: closes the `Multisig` and `MultisigConfig` accounts and every
+/// proposal account still open, sending their lamports to `rent_recipient`. The treasury
+/// has to be emptied beforehand: once the multisig is gone nothing can sign for it, so a
+/// treasury still holding lamports fails with `TreasuryNotEmpty`.
+///
+/// Every open proposal (`Multisig.open_proposals` of them) must be passed, and none of them
+/// may still be live, so closing never orphans a vote in progress. Vote states and receipts
+/// aren't closed here; close proposals with `CloseProposal` first to reclaim those.
+///
+/// Authorized either by at least two thirds of the current members signing, passed after
+/// the proposals, or by a Succeeded `CloseMultisig` proposal among the passed proposals
+/// whose recipient is `rent_recipient` and whose timelock has elapsed.
+///
+/// Accounts: [multisig, multisig_config, rent_recipient, treasury, ..proposal_states, ..member signers]
+///
+/// Instruction data (after the discriminator): empty
+pub fn process_close_multisig_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if !data.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, rent_recipient, treasury, remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig)?;
+    require_writable(multisig_config)?;
+    require_writable(rent_recipient)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let multisig_data = Multisig::from_account_info(multisig)?;
+    let multisig_config_data = &MultisigConfig::load(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let num_members = multisig_data.num_members as usize;
+
+    if num_members > MAX_MEMBERS {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if treasury.key() != &multisig_data.treasury {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if treasury.lamports() > 0 {
+        log!("Error: The treasury still holds {} lamports", treasury.lamports());
+        return Err(MultisigError::TreasuryNotEmpty.into());
+    }
+
+    let open_proposals = multisig_data.open_proposals as usize;
+
+    if remaining.len() < open_proposals {
+        log!("Error: All {} open proposals must be passed", open_proposals);
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let (proposal_states, signers) = remaining.split_at(open_proposals);
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let mut approved_by_proposal = false;
+
+    for (i, proposal_state) in proposal_states.iter().enumerate() {
+        if proposal_state.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        require_writable(proposal_state)?;
+
+        // Each open proposal has to be accounted for once
+        if proposal_states[..i].iter().any(|previous| previous.key() == proposal_state.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let proposal_data = ProposalState::from_account_info(proposal_state)?;
+
+        let proposal_seed = [
+            b"proposal".as_slice(),
+            multisig.key().as_slice(),
+            &proposal_data.proposal_id.to_le_bytes(),
+            &[proposal_data.bump],
+        ];
+        let proposal_pda = pubkey::checked_create_program_address(&proposal_seed, &crate::ID)?;
+
+        if &proposal_pda != proposal_state.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let status = proposal_data.status()?;
+
+        if !status.is_terminal() {
+            log!("Error: Proposal {} is still active", proposal_data.proposal_id);
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if status == ProposalStatus::Succeeded
+            && proposal_data.proposal_type == ProposalType::CloseMultisig as u8
+            && proposal_data.payload[..32] == *rent_recipient.key()
+            && current_time >= proposal_data.executable_at()
+        {
+            approved_by_proposal = true;
+        }
+    }
+
+    if !approved_by_proposal {
+        let member_signatures = count_member_signers(multisig_data, signers);
+
+        if num_members == 0 || member_signatures * 3 < num_members * 2 {
+            log!("Error: {} of {} members signed, two thirds are required", member_signatures, num_members);
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+    }
+
+    for proposal_state in proposal_states {
+        close_account(proposal_state, rent_recipient)?;
+    }
+    close_account(multisig_config, rent_recipient)?;
+    close_account(multisig, rent_recipient)?;
+
+    log!("Multisig closed along with {} proposals", open_proposals);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_close_multisig_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const RECIPIENT: Pubkey = Pubkey::new_from_array([0x03; 32]);
+    const TREASURY: Pubkey = Pubkey::new_from_array([0x04; 32]);
+    const PROPOSAL_ID: u64 = 1;
+
+    /// A multisig of three members with one open proposal in `status`, closed with the
+    /// first two members signing
+    fn close_multisig(status: ProposalStatus) -> (Instruction, Vec<(Pubkey, Account)>, [Pubkey; 3]) {
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = members.len() as u8;
+            let members_offset = offset_of!(Multisig, members);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data[offset_of!(Multisig, open_proposals)..][..4].copy_from_slice(&1u32.to_le_bytes());
+            data[offset_of!(Multisig, treasury)..][..32].copy_from_slice(TREASURY.as_ref());
+            data
+        };
+
+        let mut config = MultisigConfig::zeroed();
+        config.version = MultisigConfig::VERSION;
+        config.bump = config_bump;
+
+        let mut proposal = ProposalState::zeroed();
+        proposal.proposal_id = PROPOSAL_ID;
+        proposal.bump = proposal_bump;
+        proposal.set_status(status);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &[20u8], // Instruction discriminator for close multisig
+            vec![
+                AccountMeta::new(MULTISIG, false),             // multisig
+                AccountMeta::new(multisig_config_pda, false),  // multisig_config
+                AccountMeta::new(RECIPIENT, false),            // rent_recipient
+                AccountMeta::new_readonly(TREASURY, false),    // treasury
+                AccountMeta::new(proposal_state_pda, false),   // proposal_state
+                AccountMeta::new_readonly(members[0], true),   // member (signer)
+                AccountMeta::new_readonly(members[1], true),   // member (signer)
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (RECIPIENT, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (TREASURY, Account::new(0, 0, &system_program_id)),
+            (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&proposal), &ID).unwrap()),
+            (members[0], Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (members[1], Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+        ];
+
+        (instruction, tx_accounts, [MULTISIG, multisig_config_pda, proposal_state_pda])
+    }
+
+    #[test]
+    fn test_close_multisig_without_active_proposals() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let (instruction, tx_accounts, closed) = close_multisig(ProposalStatus::Failed);
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[
+                Check::success(),
+                Check::account(&RECIPIENT).lamports(4 * LAMPORTS_PER_SOL).build(),
+            ],
+        );
+
+        for key in closed {
+            let account = &result.resulting_accounts.iter().find(|(pubkey, _)| pubkey == &key).unwrap().1;
+            assert_eq!(account.lamports, 0);
+            assert_eq!(account.owner, system_program_id);
+            assert!(account.data.iter().all(|byte| *byte == 0));
+        }
+    }
+
+    #[test]
+    fn test_close_multisig_with_funded_treasury_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, mut tx_accounts, _) = close_multisig(ProposalStatus::Failed);
+        let treasury = &mut tx_accounts.iter_mut().find(|(key, _)| key == &TREASURY).unwrap().1;
+        treasury.lamports = 1;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::TreasuryNotEmpty as u32))],
+        );
+    }
+
+    #[test]
+    fn test_close_multisig_with_active_proposal_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = close_multisig(ProposalStatus::Active);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    #[test]
+    fn test_close_multisig_without_supermajority_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (mut instruction, tx_accounts, _) = close_multisig(ProposalStatus::Failed);
+        instruction.accounts.pop();
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::MissingRequiredSignature)],
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::common::{require_signer, require_writable},
+    state::{Multisig, ProposalState, ProposalStatus, ProposalType, VoteReceipt, VoteState},
+};
+
+/// Moves all lamports of `account` to `rent_recipient`, zeroes its data and hands it back
+/// to the system program.
+pub(crate) fn close_account(account: &AccountInfo, rent_recipient: &AccountInfo) -> ProgramResult {
+    let reclaimed = account.lamports();
+
+    let mut recipient_lamports = rent_recipient.try_borrow_mut_lamports()?;
+    *recipient_lamports = recipient_lamports.checked_add(reclaimed).ok_or(MultisigError::ArithmeticOverflow)?;
+    drop(recipient_lamports);
+    *account.try_borrow_mut_lamports()? = 0;
+
+    account.try_borrow_mut_data()?.fill(0);
+    unsafe { account.assign(&pinocchio_system::ID) };
+
+    Ok(())
+}
+
+/// Closes a finalized proposal and, if passed, its vote state and vote receipts, sending
+/// the rent to `rent_recipient`.
+///
+/// Only proposals that are past their expiry and Failed, Cancelled or Executed can be closed.
+/// A Succeeded proposal still has its action pending, so it stays open until executed; polls
+/// and CloseMultisig proposals are never executed and can be closed once Succeeded. The
+/// caller must be a member of the multisig.
+///
+/// Accounts: [member, rent_recipient, multisig, proposal_state, optional vote_state, ..vote_receipts]
+///
+/// Instruction data (after the discriminator): [proposal_id: u64][proposal_bump: u8]
+pub fn process_close_proposal_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 9 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [member, rent_recipient, multisig, proposal_state, remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(member)?;
+    require_writable(rent_recipient)?;
+    require_writable(multisig)?;
+    require_writable(proposal_state)?;
+
+    for accounts in [multisig, proposal_state] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let proposal_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let proposal_bump = data[8];
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+    let proposal_data = ProposalState::from_account_info(proposal_state)?;
+
+    if !multisig_data.is_member(member.key()) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let proposal_seed = [
+        b"proposal".as_slice(),
+        multisig.key().as_slice(),
+        &proposal_id.to_le_bytes(),
+        &[proposal_bump],
+    ];
+    let proposal_pda = pubkey::checked_create_program_address(&proposal_seed, &crate::ID)?;
+
+    if &proposal_pda != proposal_state.key() || proposal_data.proposal_id != proposal_id {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    match proposal_data.status()? {
+        ProposalStatus::Failed | ProposalStatus::Cancelled | ProposalStatus::Executed => {},
+        // Nothing will ever execute these, so they'd stay open forever otherwise
+        ProposalStatus::Succeeded if proposal_data.is_poll() || !ProposalType::try_from(&proposal_data.proposal_type)?.is_executable() => {},
+        ProposalStatus::Succeeded => {
+            log!("Error: Proposal {} has not been executed yet", proposal_id);
+            return Err(ProgramError::InvalidAccountData);
+        }
+        _ => return Err(ProgramError::InvalidAccountData), // Proposal is not finalized
+    };
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    if !proposal_data.is_expired(current_time) {
+        log!("Proposal has not expired yet");
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    // The vote state is optional: it only exists once somebody voted
+    let (vote_state, vote_receipts) = match remaining.split_first() {
+        Some((vote_state, vote_receipts)) => (Some(vote_state), vote_receipts),
+        None => (None, remaining),
+    };
+
+    if let Some(vote_state) = vote_state {
+        if vote_state.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vote_state_seed = [
+            b"vote_state".as_slice(),
+            multisig.key().as_slice(),
+            &proposal_id.to_le_bytes(),
+            &[VoteState::from_account_info(vote_state)?.bump],
+        ];
+        let vote_state_pda = pubkey::checked_create_program_address(&vote_state_seed, &crate::ID)?;
+
+        if &vote_state_pda != vote_state.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        close_account(vote_state, rent_recipient)?;
+        log!("VoteState closed");
+    }
+
+    for vote_receipt in vote_receipts {
+        if vote_receipt.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        require_writable(vote_receipt)?;
+
+        let receipt_data = VoteReceipt::from_account_info(vote_receipt)?;
+
+        let receipt_seed = [
+            b"receipt".as_slice(),
+            proposal_state.key().as_slice(),
+            receipt_data.voter.as_slice(),
+            &[receipt_data.bump],
+        ];
+        let receipt_pda = pubkey::checked_create_program_address(&receipt_seed, &crate::ID)?;
+
+        if &receipt_pda != vote_receipt.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        close_account(vote_receipt, rent_recipient)?;
+    }
+
+    close_account(proposal_state, rent_recipient)?;
+
+    // Proposals created before the counter existed were never counted
+    multisig_data.open_proposals = multisig_data.open_proposals.saturating_sub(1);
+
+    log!("Proposal {} closed", proposal_id);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_close_proposal_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const MEMBER: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const RECIPIENT: Pubkey = Pubkey::new_from_array([0x03; 32]);
+    const PROPOSAL_ID: u64 = 1;
+    const EXPIRY: u64 = 1_000;
+
+    fn close_proposal(status: ProposalStatus) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey, Pubkey, Pubkey) {
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (vote_state_pda, vote_state_bump) = Pubkey::find_program_address(
+            &[b"vote_state", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (vote_receipt_pda, receipt_bump) = Pubkey::find_program_address(
+            &[b"receipt", proposal_state_pda.as_ref(), MEMBER.as_ref()],
+            &ID,
+        );
+
+        let mut receipt = VoteReceipt::zeroed();
+        receipt.voter = MEMBER.to_bytes();
+        receipt.choice = 1;
+        receipt.bump = receipt_bump;
+
+        let mut vote_state = VoteState::zeroed();
+        vote_state.has_permission = 1;
+        vote_state.bump = vote_state_bump;
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = 1;
+            data[offset_of!(Multisig, members)..][..32].copy_from_slice(MEMBER.as_ref());
+            data
+        };
+
+        let proposal_data = {
+            let mut data = vec![0u8; ProposalState::BASE_LEN];
+            data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&PROPOSAL_ID.to_le_bytes());
+            data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&EXPIRY.to_le_bytes());
+            data[offset_of!(ProposalState, result)] = status as u8;
+            data[offset_of!(ProposalState, bump)] = proposal_bump;
+            data
+        };
+
+        let mut data = vec![4u8]; // Instruction discriminator for close proposal
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(proposal_bump);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(MEMBER, true),        // member (signer)
+                AccountMeta::new(RECIPIENT, false),             // rent_recipient
+                AccountMeta::new(MULTISIG, false),              // multisig
+                AccountMeta::new(proposal_state_pda, false),    // proposal_state
+                AccountMeta::new(vote_state_pda, false),        // vote_state
+                AccountMeta::new(vote_receipt_pda, false),      // vote_receipt
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MEMBER, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (RECIPIENT, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &proposal_data, &ID).unwrap()),
+            (vote_state_pda, Account::new_data(LAMPORTS_PER_SOL / 2, bytemuck::bytes_of(&vote_state), &ID).unwrap()),
+            (vote_receipt_pda, Account::new_data(LAMPORTS_PER_SOL / 4, bytemuck::bytes_of(&receipt), &ID).unwrap()),
+        ];
+
+        (instruction, tx_accounts, proposal_state_pda, vote_state_pda, vote_receipt_pda)
+    }
+
+    #[test]
+    fn test_close_finalized_proposal_reclaims_rent() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = EXPIRY as i64 + 1;
+
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let (instruction, tx_accounts, proposal_state_pda, vote_state_pda, vote_receipt_pda) = close_proposal(ProposalStatus::Executed);
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[
+                Check::success(),
+                Check::account(&RECIPIENT).lamports(1 * LAMPORTS_PER_SOL + LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 2 + LAMPORTS_PER_SOL / 4).build(),
+            ],
+        );
+
+        for pda in [proposal_state_pda, vote_state_pda, vote_receipt_pda] {
+            let account = &result.resulting_accounts.iter().find(|(key, _)| key == &pda).unwrap().1;
+            assert_eq!(account.lamports, 0);
+            assert_eq!(account.owner, system_program_id);
+            assert!(account.data.iter().all(|byte| *byte == 0));
+        }
+    }
+
+    #[test]
+    fn test_close_rejects_active_proposal() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = EXPIRY as i64 + 1;
+
+        let (instruction, tx_accounts, _, _, _) = close_proposal(ProposalStatus::Active);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    #[test]
+    fn test_close_rejects_unexecuted_succeeded_proposal() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = EXPIRY as i64 + 1;
+
+        let (instruction, mut tx_accounts, proposal_state_pda, _, _) = close_proposal(ProposalStatus::Succeeded);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+
+        // Succeeded polls and CloseMultisig proposals have nothing left to execute, so they can be closed
+        let mut poll_accounts = tx_accounts.clone();
+        let proposal = &mut poll_accounts.iter_mut().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        proposal.data[offset_of!(ProposalState, num_options)] = 2;
+        mollusk.process_and_validate_instruction(&instruction, &poll_accounts, &[Check::success()]);
+
+        let proposal = &mut tx_accounts.iter_mut().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        proposal.data[offset_of!(ProposalState, proposal_type)] = ProposalType::CloseMultisig as u8;
+        mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+    }
+}
@@ -0,0 +1,387 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    error::MultisigError,
+    instructions::common::require_signer,
+    state::{Multisig, MultisigConfig, MAX_MEMBERS, ROLE_ALL},
+};
+
+/// Instruction data (after the discriminator):
+/// [seed: u64][multisig_bump: u8][config_bump: u8][threshold: u8][num_members: u8][members: 32 * num_members]
+/// followed optionally by [weights: u64 * num_members]; members default to a weight of 1.
+/// Both bumps must be the canonical ones.
+pub fn process_create_multisig_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() < 12 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [creator, multisig, multisig_config, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(creator)?;
+
+    let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let multisig_bump = data[8];
+    let config_bump = data[9];
+    let threshold = data[10];
+    let num_members = data[11];
+
+    if num_members as usize > MAX_MEMBERS {
+        log!("Error: Too many members, max is {}", MAX_MEMBERS);
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    if threshold == 0 || threshold > num_members {
+        log!("Error: Threshold must be between 1 and the number of members");
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let members_end = 12 + num_members as usize * 32;
+    let has_weights = data.len() == members_end + num_members as usize * 8;
+
+    if data.len() != members_end && !has_weights {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let weight_at = |i: usize| -> u64 {
+        if has_weights {
+            let offset = members_end + i * 8;
+            u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+        } else {
+            1
+        }
+    };
+
+    if (0..num_members as usize).any(|i| weight_at(i) == 0) {
+        log!("Error: Member weights must be non-zero");
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    // Both PDAs only at their canonical bumps, so a creator and seed name exactly one
+    // multisig and the multisig exactly one config
+    let seed_bytes = seed.to_le_bytes();
+    let (multisig_pda, canonical_multisig_bump) = pubkey::find_program_address(
+        &[b"multisig".as_slice(), creator.key().as_slice(), seed_bytes.as_slice()],
+        &crate::ID,
+    );
+
+    if &multisig_pda != multisig.key() || multisig_bump != canonical_multisig_bump {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let (config_pda, canonical_config_bump) = pubkey::find_program_address(
+        &[b"multisig_config".as_slice(), multisig.key().as_slice()],
+        &crate::ID,
+    );
+
+    if &config_pda != multisig_config.key() || config_bump != canonical_config_bump {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if multisig.owner() == &crate::ID || multisig_config.owner() == &crate::ID {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let rent = Rent::get()?;
+
+    log!("Creating Multisig Account");
+
+    let multisig_bump_bytes = [multisig_bump];
+    let multisig_signer_seeds = [
+        Seed::from(b"multisig"),
+        Seed::from(creator.key()),
+        Seed::from(&seed_bytes),
+        Seed::from(&multisig_bump_bytes),
+    ];
+
+    CreateAccount {
+        from: creator,
+        to: multisig,
+        lamports: rent.minimum_balance(Multisig::LEN),
+        space: Multisig::LEN as u64,
+        owner: &crate::ID,
+    }.invoke_signed(&[Signer::from(&multisig_signer_seeds)])?;
+
+    let multisig_data = Multisig::initialize(multisig)?;
+    multisig_data.creator = *creator.key();
+    multisig_data.num_members = num_members;
+    for i in 0..num_members as usize {
+        let offset = 12 + i * 32;
+        multisig_data.members[i] = data[offset..offset + 32].try_into().unwrap();
+        multisig_data.weights[i] = weight_at(i);
+        multisig_data.roles[i] = ROLE_ALL;
+    }
+    // Sorted members let every lookup binary search the list
+    multisig_data.sort_members();
+
+    // Sorted, a repeated member sits next to its copy
+    let members = &multisig_data.members[..num_members as usize];
+    if members.contains(&Pubkey::default()) {
+        log!("Error: The default pubkey can't be a member");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if members.windows(2).any(|pair| pair[0] == pair[1]) {
+        log!("Error: Members must be unique");
+        return Err(MultisigError::DuplicateMember.into());
+    }
+
+    multisig_data.bump = multisig_bump;
+
+    // The treasury is a system-owned PDA that holds the multisig's funds
+    let (treasury, treasury_bump) = pubkey::find_program_address(
+        &[b"treasury".as_slice(), multisig.key().as_slice()],
+        &crate::ID,
+    );
+    multisig_data.treasury = treasury;
+    multisig_data.treasury_bump = treasury_bump;
+
+    log!("Creating MultisigConfig Account");
+
+    let config_bump_bytes = [config_bump];
+    let config_signer_seeds = [
+        Seed::from(b"multisig_config"),
+        Seed::from(multisig.key()),
+        Seed::from(&config_bump_bytes),
+    ];
+
+    CreateAccount {
+        from: creator,
+        to: multisig_config,
+        lamports: rent.minimum_balance(MultisigConfig::LEN),
+        space: MultisigConfig::LEN as u64,
+        owner: &crate::ID,
+    }.invoke_signed(&[Signer::from(&config_signer_seeds)])?;
+
+    let multisig_config_data = MultisigConfig::initialize(multisig_config)?;
+    multisig_config_data.min_threshold = threshold as u64;
+    multisig_config_data.config_authority = *creator.key();
+    multisig_config_data.bump = config_bump;
+
+    log!("Multisig created with {} members, threshold {}", num_members, threshold);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_create_multisig_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const CREATOR: Pubkey = Pubkey::new_from_array([0x01; 32]);
+
+    fn create_multisig_ix(seed: u64, threshold: u8, members: &[Pubkey]) -> (Instruction, Pubkey, Pubkey) {
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+
+        let (multisig_pda, multisig_bump) = Pubkey::find_program_address(
+            &[b"multisig", CREATOR.as_ref(), &seed.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", multisig_pda.as_ref()],
+            &ID,
+        );
+
+        let mut data = vec![5u8]; // Instruction discriminator for create multisig
+        data.extend_from_slice(&seed.to_le_bytes());
+        data.push(multisig_bump);
+        data.push(config_bump);
+        data.push(threshold);
+        data.push(members.len() as u8);
+        for member in members {
+            data.extend_from_slice(member.as_ref());
+        }
+
+        let ix_accounts = vec![
+            AccountMeta::new(CREATOR, true),                      // creator (signer)
+            AccountMeta::new(multisig_pda, false),                // multisig
+            AccountMeta::new(multisig_config_pda, false),         // multisig_config
+            AccountMeta::new_readonly(system_program_id, false),  // system_program
+        ];
+
+        (Instruction::new_with_bytes(ID, &data, ix_accounts), multisig_pda, multisig_config_pda)
+    }
+
+    fn tx_accounts(multisig_pda: Pubkey, multisig_config_pda: Pubkey) -> Vec<(Pubkey, Account)> {
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+
+        vec![
+            (CREATOR, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (multisig_pda, Account::new(0, 0, &system_program_id)),
+            (multisig_config_pda, Account::new(0, 0, &system_program_id)),
+            (system_program_id, system_account),
+        ]
+    }
+
+    #[test]
+    fn test_create_multisig_with_three_members() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [CREATOR, Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, multisig_pda, multisig_config_pda) = create_multisig_ix(42, 2, &members);
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts(multisig_pda, multisig_config_pda),
+            &[
+                Check::success(),
+                Check::account(&multisig_pda).owner(&ID).space(Multisig::LEN).build(),
+                Check::account(&multisig_config_pda).owner(&ID).space(MultisigConfig::LEN).build(),
+            ],
+        );
+
+        let multisig_account = &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_pda).unwrap().1;
+        let data = &multisig_account.data;
+
+        let creator_offset = offset_of!(Multisig, creator);
+        assert_eq!(&data[creator_offset..creator_offset + 32], CREATOR.as_ref());
+        assert_eq!(data[offset_of!(Multisig, num_members)], 3);
+
+        // Members are stored sorted
+        let mut sorted_members = members;
+        sorted_members.sort();
+        let members_offset = offset_of!(Multisig, members);
+        for (i, member) in sorted_members.iter().enumerate() {
+            let start = members_offset + i * 32;
+            assert_eq!(&data[start..start + 32], member.as_ref());
+        }
+        assert_eq!(data[offset_of!(Multisig, members_sorted)], 1);
+
+        let config_account = &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let threshold_offset = offset_of!(MultisigConfig, min_threshold);
+        let min_threshold = u64::from_le_bytes(config_account.data[threshold_offset..threshold_offset + 8].try_into().unwrap());
+        assert_eq!(min_threshold, 2);
+
+        let authority_offset = offset_of!(MultisigConfig, config_authority);
+        assert_eq!(&config_account.data[authority_offset..authority_offset + 32], CREATOR.as_ref());
+    }
+
+    #[test]
+    fn test_create_multisig_rejects_zero_threshold() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [CREATOR, Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, multisig_pda, multisig_config_pda) = create_multisig_ix(42, 0, &members);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts(multisig_pda, multisig_config_pda),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_create_multisig_rejects_threshold_above_members() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [CREATOR, Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, multisig_pda, multisig_config_pda) = create_multisig_ix(42, 4, &members);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts(multisig_pda, multisig_config_pda),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_create_multisig_rejects_non_canonical_config_bump() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [CREATOR, Pubkey::new_unique()];
+        let (mut instruction, multisig_pda, multisig_config_pda) = create_multisig_ix(42, 1, &members);
+
+        // A valid program address for the config seeds at a lower bump
+        let (alias_pda, alias_bump) = (0..instruction.data[10])
+            .rev()
+            .find_map(|bump| {
+                Pubkey::create_program_address(&[b"multisig_config", multisig_pda.as_ref(), &[bump]], &ID)
+                    .ok()
+                    .map(|pda| (pda, bump))
+            })
+            .unwrap();
+
+        instruction.data[10] = alias_bump;
+        instruction.accounts[2].pubkey = alias_pda;
+        let mut accounts = tx_accounts(multisig_pda, multisig_config_pda);
+        accounts[2].0 = alias_pda;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[Check::err(ProgramError::InvalidSeeds)],
+        );
+    }
+
+    #[test]
+    fn test_create_multisig_rejects_duplicate_members() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let other = Pubkey::new_unique();
+        let members = [other, CREATOR, other];
+        let (instruction, multisig_pda, multisig_config_pda) = create_multisig_ix(42, 2, &members);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts(multisig_pda, multisig_config_pda),
+            &[Check::err(ProgramError::Custom(MultisigError::DuplicateMember as u32))],
+        );
+    }
+
+    #[test]
+    fn test_create_multisig_rejects_default_pubkey_member() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [CREATOR, Pubkey::default(), Pubkey::new_unique()];
+        let (instruction, multisig_pda, multisig_config_pda) = create_multisig_ix(42, 2, &members);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts(multisig_pda, multisig_config_pda),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_create_multisig_rejects_too_many_members() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..MAX_MEMBERS + 1).map(|_| Pubkey::new_unique()).collect();
+        let (instruction, multisig_pda, multisig_config_pda) = create_multisig_ix(42, 2, &members);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts(multisig_pda, multisig_config_pda),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
@@ -0,0 +1,812 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    error::MultisigError,
+    events::ProposalCreated,
+    instructions::common::{require_signer, require_writable},
+    state::{
+        Multisig, MultisigConfig, ProposalAccountMeta, ProposalState, ProposalStatus, ProposalType,
+        MAX_CPI_ACCOUNTS, MAX_CPI_DATA_LEN, MAX_MEMBERS, MAX_PROPOSAL_ACCOUNTS, MAX_PROPOSAL_DATA_LEN,
+        MAX_PROPOSAL_OPTIONS, PROPOSAL_LINK_LEN, ROLE_CAN_PROPOSE,
+    },
+};
+
+/// Splits an ArbitraryCpi payload into its target program, account metas, instruction data
+/// and whatever follows the instruction data
+fn parse_stored_instruction(payload: &[u8]) -> Result<(Pubkey, &[u8], &[u8], &[u8]), ProgramError> {
+    if payload.len() < 33 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let target_program: Pubkey = payload[0..32].try_into().unwrap();
+    let num_accounts = payload[32] as usize;
+
+    if num_accounts > MAX_PROPOSAL_ACCOUNTS {
+        log!("Error: A stored instruction can reference at most {} accounts", MAX_PROPOSAL_ACCOUNTS);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let metas_end = 33 + num_accounts * ProposalAccountMeta::LEN;
+    let data_start = metas_end + 2;
+
+    if payload.len() < data_start {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let data_len = u16::from_le_bytes(payload[metas_end..data_start].try_into().unwrap()) as usize;
+
+    if data_len > MAX_PROPOSAL_DATA_LEN {
+        log!("Error: Stored instruction data is capped at {} bytes", MAX_PROPOSAL_DATA_LEN);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if payload.len() < data_start + data_len {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Execute refuses a CPI above these limits, so such a proposal could never run
+    if num_accounts > MAX_CPI_ACCOUNTS || data_len > MAX_CPI_DATA_LEN {
+        log!("Error: A CPI can pass at most {} accounts and {} bytes of data", MAX_CPI_ACCOUNTS, MAX_CPI_DATA_LEN);
+        return Err(MultisigError::CpiTooLarge.into());
+    }
+
+    let (stored_data, rest) = payload[data_start..].split_at(data_len);
+    Ok((target_program, &payload[33..metas_end], stored_data, rest))
+}
+
+/// Creates an Active proposal that expires `duration` seconds from now. The duration can't
+/// exceed the config's `proposal_duration_limit`.
+///
+/// The current multisig members and their weights are copied into `active_members` and
+/// `active_weights`, and the required votes, quorum and execution delay are fixed from the
+/// current config, so votes on this proposal are counted against the membership, weights,
+/// threshold and quorum at creation time. A multisig using a members root has the root snapshotted
+/// too, and votes on the proposal are checked against it instead.
+///
+/// Accounts: [proposer, multisig, multisig_config, proposal_state, system_program]
+///
+/// Instruction data (after the discriminator): [proposal_id: u64][duration: u64][proposal_bump: u8]
+/// followed optionally by [proposal_type: u8][payload: `ProposalType::payload_len` bytes];
+/// without them the proposal is an ArbitraryCpi proposal with no stored instruction.
+///
+/// An ArbitraryCpi payload holds the stored instruction instead:
+/// [target_program: Pubkey][num_accounts: u8][num_accounts * (pubkey: Pubkey, is_signer: u8, is_writable: u8)]
+/// [data_len: u16][data: data_len bytes]. The account is sized `ProposalState::BASE_LEN + data_len`,
+/// so proposals only pay rent for the instruction data they store.
+///
+/// Either payload may be followed by a [link: 64 bytes] pointing at the proposal's off-chain
+/// metadata, stored in `ProposalState.link` and emitted in the `ProposalCreated` event.
+pub fn process_create_proposal_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() < 17 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let (proposal_type, payload) = match data.get(17) {
+        Some(proposal_type) => (ProposalType::try_from(proposal_type)?, &data[18..]),
+        None => (ProposalType::ArbitraryCpi, &data[17..]),
+    };
+
+    let (typed_payload, target_program, stored_metas, stored_data, link) = match proposal_type {
+        ProposalType::ArbitraryCpi if !payload.is_empty() => {
+            let (target_program, stored_metas, stored_data, link) = parse_stored_instruction(payload)?;
+            (&[][..], target_program, stored_metas, stored_data, link)
+        },
+        _ => {
+            let payload_len = proposal_type.payload_len();
+            if payload.len() != payload_len && payload.len() != payload_len + PROPOSAL_LINK_LEN {
+                log!("Error: Payload must be {} bytes for this proposal type", payload_len);
+                return Err(ProgramError::InvalidInstructionData);
+            };
+            let (typed_payload, link) = payload.split_at(payload_len);
+            (typed_payload, Pubkey::default(), &[][..], &[][..], link)
+        },
+    };
+
+    if !link.is_empty() && link.len() != PROPOSAL_LINK_LEN {
+        log!("Error: The link must be {} bytes", PROPOSAL_LINK_LEN);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // A Poll offers at least two options and no more than the proposal can tally
+    if proposal_type == ProposalType::Poll && !(2..=MAX_PROPOSAL_OPTIONS as u8).contains(&payload[0]) {
+        log!("Error: A poll needs between 2 and {} options", MAX_PROPOSAL_OPTIONS);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let [proposer, multisig, multisig_config, proposal_state, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(proposer)?;
+
+    // Counts the new proposal among the multisig's open ones
+    require_writable(multisig)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let proposal_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let duration = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let proposal_bump = data[16];
+
+    if duration == 0 {
+        log!("Error: Proposal duration must be non-zero");
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+    let multisig_config_data = &MultisigConfig::load(multisig_config)?;
+    let num_members = multisig_data.num_members as usize;
+
+    if num_members > MAX_MEMBERS {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !multisig_data.is_member(proposer.key()) {
+        log!("Error: Proposer is not a member of the multisig");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !multisig_data.has_role(proposer.key(), ROLE_CAN_PROPOSE) {
+        log!("Error: Proposer is not allowed to create proposals");
+        return Err(MultisigError::MissingPermission.into());
+    }
+
+    // A member listed twice would have two snapshot slots and could vote twice
+    if multisig_data.has_duplicate_members() {
+        log!("Error: Multisig lists a member more than once");
+        return Err(MultisigError::DuplicateMember.into());
+    }
+
+    // Only the canonical bump, so an id names exactly one proposal; a second proposal at
+    // another bump would share the id's vote state
+    let proposal_id_bytes = proposal_id.to_le_bytes();
+    let (proposal_pda, canonical_bump) = pubkey::find_program_address(
+        &[b"proposal".as_slice(), multisig.key().as_slice(), proposal_id_bytes.as_slice()],
+        &crate::ID,
+    );
+
+    if &proposal_pda != proposal_state.key() || proposal_bump != canonical_bump {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Checked once the config is known to be this multisig's own, so another config can't
+    // lift the pause
+    if let Err(error) = multisig_config_data.require_not_paused() {
+        log!("Error: Proposing is paused");
+        return Err(error);
+    }
+
+    // The id must not be in use: the PDA has to be an empty system account, so a new
+    // proposal can never overwrite or alias an existing one
+    if proposal_state.owner() != &pinocchio_system::ID || proposal_state.data_len() != 0 {
+        log!("Error: Proposal {} already exists", proposal_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    if duration > multisig_config_data.proposal_duration_limit() {
+        log!("Error: Proposals can't run longer than {} seconds", multisig_config_data.proposal_duration_limit());
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let expiry = current_time.checked_add(duration).ok_or(ProgramError::InvalidInstructionData)?;
+
+    multisig_data.open_proposals = multisig_data
+        .open_proposals
+        .checked_add(1)
+        .ok_or(MultisigError::ArithmeticOverflow)?;
+    multisig_data.proposal_nonce = multisig_data
+        .proposal_nonce
+        .checked_add(1)
+        .ok_or(MultisigError::ArithmeticOverflow)?;
+    multisig_data.total_proposals = multisig_data
+        .total_proposals
+        .checked_add(1)
+        .ok_or(MultisigError::ArithmeticOverflow)?;
+
+    log!("Creating ProposalState Account");
+
+    let proposal_bump_bytes = [proposal_bump];
+    let proposal_signer_seeds = [
+        Seed::from(b"proposal"),
+        Seed::from(multisig.key()),
+        Seed::from(&proposal_id_bytes),
+        Seed::from(&proposal_bump_bytes),
+    ];
+
+    CreateAccount {
+        from: proposer,
+        to: proposal_state,
+        lamports: Rent::get()?.minimum_balance(ProposalState::BASE_LEN + stored_data.len()),
+        space: (ProposalState::BASE_LEN + stored_data.len()) as u64,
+        owner: &crate::ID,
+    }.invoke_signed(&[Signer::from(&proposal_signer_seeds)])?;
+
+    let proposal_data = ProposalState::from_account_info(proposal_state)?;
+    proposal_data.proposal_id = proposal_id;
+    proposal_data.nonce = multisig_data.proposal_nonce;
+    proposal_data.expiry = expiry;
+    proposal_data.set_status(ProposalStatus::Active);
+    proposal_data.bump = proposal_bump;
+    proposal_data.created_time = current_time;
+    proposal_data.start_time = current_time;
+    proposal_data.proposer = *proposer.key();
+    // Percentage thresholds are taken of whoever votes: the members root, or `members`
+    let (eligible_members, _) = multisig_data.eligible_voters();
+    proposal_data.required_votes = multisig_config_data.required_votes_for(proposal_type as u8, eligible_members)?;
+    proposal_data.quorum = multisig_config_data.quorum;
+    proposal_data.execution_delay = multisig_config_data.execution_delay;
+    proposal_data.proposal_type = proposal_type as u8;
+    proposal_data.payload[..typed_payload.len()].copy_from_slice(typed_payload);
+    proposal_data.link[..link.len()].copy_from_slice(link);
+    if proposal_type == ProposalType::Poll {
+        proposal_data.num_options = typed_payload[0];
+    }
+
+    proposal_data.target_program = target_program;
+    proposal_data.num_accounts = (stored_metas.len() / ProposalAccountMeta::LEN) as u8;
+    for (stored, meta) in proposal_data.accounts.iter_mut().zip(stored_metas.chunks_exact(ProposalAccountMeta::LEN)) {
+        *stored = ProposalAccountMeta {
+            pubkey: meta[0..32].try_into().unwrap(),
+            is_signer: meta[32],
+            is_writable: meta[33],
+        };
+    }
+    proposal_data.instruction_data_len = stored_data.len() as u16;
+
+    // Snapshot of the membership; votes are only ever looked up within the first
+    // `active_member_count` slots and unused slots stay zeroed
+    proposal_data.active_member_count = num_members as u8;
+    proposal_data.active_members[..num_members].copy_from_slice(&multisig_data.members[..num_members]);
+    proposal_data.active_members[num_members..].fill(Pubkey::default());
+    for (snapshot, weight) in proposal_data.active_weights[..num_members].iter_mut().zip(&multisig_data.weights[..num_members]) {
+        *snapshot = (*weight).max(1);
+    }
+    proposal_data.active_weights[num_members..].fill(0);
+    proposal_data.members_root = multisig_data.members_root;
+    proposal_data.root_member_count = multisig_data.root_member_count;
+
+    // The stored instruction data lives right after the fixed part of the account
+    let (_, instruction_data) = ProposalState::from_account_info_with_data(proposal_state)?;
+    instruction_data.copy_from_slice(stored_data);
+
+    ProposalCreated::new(proposal_data).emit();
+
+    log!("Proposal {} created, expires at {}", proposal_id, expiry);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_create_proposal_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::state::{VoteReceipt, VoteState};
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const PROPOSER: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const PROPOSAL_ID: u64 = 1;
+
+    fn create_proposal(members: &[Pubkey], duration: u64) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        create_proposal_with_payload(members, duration, &[])
+    }
+
+    /// `typed_payload` is appended after the bump: [proposal_type][payload], or empty
+    fn create_proposal_with_payload(members: &[Pubkey], duration: u64, typed_payload: &[u8]) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&1u64.to_le_bytes());
+            data[offset_of!(MultisigConfig, bump)] = config_bump;
+            data
+        };
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = members.len() as u8;
+            let members_offset = offset_of!(Multisig, members);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data
+        };
+
+        let mut data = vec![2u8]; // Instruction discriminator for create proposal
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.extend_from_slice(&duration.to_le_bytes());
+        data.push(proposal_bump);
+        data.extend_from_slice(typed_payload);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(PROPOSER, true),                     // proposer (signer)
+                AccountMeta::new(MULTISIG, false),                    // multisig
+                AccountMeta::new_readonly(multisig_config_pda, false), // multisig_config
+                AccountMeta::new(proposal_state_pda, false),          // proposal_state
+                AccountMeta::new_readonly(system_program_id, false),  // system_program
+            ],
+        );
+
+        let tx_accounts = vec![
+            (PROPOSER, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+            (proposal_state_pda, Account::new(0, 0, &system_program_id)),
+            (system_program_id, system_account),
+        ];
+
+        (instruction, tx_accounts, proposal_state_pda)
+    }
+
+    #[test]
+    fn test_create_proposal_then_vote() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let members = [PROPOSER, Pubkey::new_unique()];
+        let (instruction, tx_accounts, proposal_state_pda) = create_proposal(&members, 3_600);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let expiry = u64::from_le_bytes(proposal_account.data[offset_of!(ProposalState, expiry)..][..8].try_into().unwrap());
+        assert_eq!(expiry, 4_600);
+        let proposal_data: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!(proposal_data.created_time, 1_000);
+        assert_eq!(proposal_data.age(1_600), 600);
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Active as u8);
+        let active_members_offset = offset_of!(ProposalState, active_members);
+        assert_eq!(&proposal_account.data[active_members_offset..active_members_offset + 32], PROPOSER.as_ref());
+        assert_eq!(&proposal_account.data[active_members_offset + 32..active_members_offset + 64], members[1].as_ref());
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, active_member_count)], 2);
+        let required_votes = u64::from_le_bytes(proposal_account.data[offset_of!(ProposalState, required_votes)..][..8].try_into().unwrap());
+        assert_eq!(required_votes, 1);
+
+        // Vote For on the new proposal with a threshold of 1
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let proposal_bump = proposal_account.data[offset_of!(ProposalState, bump)];
+        let (vote_state_pda, _) = Pubkey::find_program_address(
+            &[b"vote_state", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, _) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+        let (vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[b"receipt", proposal_state_pda.as_ref(), members[1].as_ref()],
+            &ID,
+        );
+
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(1); // Vote choice: For
+        data.push(proposal_bump);
+
+        let vote = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(members[1], true),
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new(vote_state_pda, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(vote_receipt_pda, false),
+            ],
+        );
+
+        let vote_accounts = vec![
+            (members[1], Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().clone(),
+            result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().clone(),
+            (vote_state_pda, Account::new(0, 0, &system_program_id)),
+            result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().clone(),
+            (system_program_id, system_account),
+            (vote_receipt_pda, Account::new(0, 0, &system_program_id)),
+        ];
+
+        let result = mollusk.process_and_validate_instruction(&vote, &vote_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+
+        let vote_state_account = &result.resulting_accounts.iter().find(|(key, _)| key == &vote_state_pda).unwrap().1;
+        assert_eq!(vote_state_account.data.len(), VoteState::LEN);
+
+        let vote_receipt_account = &result.resulting_accounts.iter().find(|(key, _)| key == &vote_receipt_pda).unwrap().1;
+        let receipt: VoteReceipt = bytemuck::pod_read_unaligned(&vote_receipt_account.data);
+        assert_eq!(receipt.voter, members[1].to_bytes());
+        assert_eq!(receipt.choice, 1);
+    }
+
+    #[test]
+    fn test_recreated_proposal_rejects_stale_vote_state() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        // Proposal 1 was created before as the multisig's 4th proposal, then closed
+        // without its vote state
+        let members = [PROPOSER, Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, proposal_state_pda) = create_proposal(&members, 3_600);
+        let multisig_account = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        multisig_account.data[offset_of!(Multisig, proposal_nonce)..][..4].copy_from_slice(&4u32.to_le_bytes());
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let proposal_data: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!(proposal_data.nonce, 5);
+
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let (vote_state_pda, vote_state_bump) = Pubkey::find_program_address(
+            &[b"vote_state", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, _) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+        let (vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[b"receipt", proposal_state_pda.as_ref(), members[1].as_ref()],
+            &ID,
+        );
+
+        let mut stale_vote_state = VoteState::zeroed();
+        stale_vote_state.has_permission = 1;
+        stale_vote_state.proposal_nonce = 4;
+        stale_vote_state.vote_count = 1;
+        stale_vote_state.bump = vote_state_bump;
+
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(1); // Vote choice: For
+        data.push(proposal_data.bump);
+
+        let vote = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(members[1], true),
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new(vote_state_pda, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(vote_receipt_pda, false),
+            ],
+        );
+
+        let vote_accounts = vec![
+            (members[1], Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().clone(),
+            result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().clone(),
+            (vote_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&stale_vote_state), &ID).unwrap()),
+            result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().clone(),
+            (system_program_id, system_account),
+            (vote_receipt_pda, Account::new(0, 0, &system_program_id)),
+        ];
+
+        mollusk.process_and_validate_instruction(
+            &vote,
+            &vote_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::StaleVoteAccount as u32))],
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_rejects_zero_duration() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = create_proposal(&[PROPOSER], 0);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_rejects_used_id() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, proposal_state_pda) = create_proposal(&[PROPOSER], 3_600);
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+        let proposal_before = result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1.data.clone();
+
+        // Same id again: the existing proposal is left untouched
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &result.resulting_accounts,
+            &[
+                Check::err(ProgramError::AccountAlreadyInitialized),
+                Check::account(&proposal_state_pda).data(&proposal_before).build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_rejects_id_held_by_foreign_account() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, mut tx_accounts, proposal_state_pda) = create_proposal(&[PROPOSER], 3_600);
+        let proposal = &mut tx_accounts.iter_mut().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        proposal.owner = Pubkey::new_unique();
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::AccountAlreadyInitialized)],
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_rejects_non_member() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = create_proposal(&[Pubkey::new_unique()], 3_600);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_rejects_vote_only_member() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, mut tx_accounts, _) = create_proposal(&[PROPOSER, Pubkey::new_unique()], 3_600);
+        tx_accounts[1].1.data[offset_of!(Multisig, roles)] = ROLE_CAN_VOTE;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::MissingPermission as u32))],
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_rejects_duplicate_members() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let duplicate = Pubkey::new_unique();
+        let (instruction, tx_accounts, _) = create_proposal(&[PROPOSER, duplicate, duplicate], 3_600);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::DuplicateMember as u32))],
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_sizes_account_to_stored_instruction_data() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let target_program = Pubkey::new_unique();
+        let stored_account = Pubkey::new_unique();
+
+        for data_len in [0usize, 128] {
+            let stored_data: Vec<u8> = (0..data_len).map(|i| i as u8).collect();
+
+            let mut payload = vec![ProposalType::ArbitraryCpi as u8];
+            payload.extend_from_slice(target_program.as_ref());
+            payload.push(1); // num_accounts
+            payload.extend_from_slice(stored_account.as_ref());
+            payload.extend_from_slice(&[0, 1]); // is_signer, is_writable
+            payload.extend_from_slice(&(data_len as u16).to_le_bytes());
+            payload.extend_from_slice(&stored_data);
+
+            let (instruction, tx_accounts, proposal_state_pda) = create_proposal_with_payload(&[PROPOSER], 3_600, &payload);
+
+            let result = mollusk.process_and_validate_instruction(
+                &instruction,
+                &tx_accounts,
+                &[
+                    Check::success(),
+                    Check::account(&proposal_state_pda).space(ProposalState::BASE_LEN + data_len).build(),
+                ],
+            );
+
+            let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+            let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+            assert_eq!(proposal.instruction_data_len as usize, data_len);
+            assert_eq!(proposal.target_program, target_program.to_bytes());
+            assert_eq!(proposal.num_accounts, 1);
+            assert_eq!(proposal.accounts[0].pubkey, stored_account.to_bytes());
+            assert_eq!(proposal.accounts[0].is_writable, 1);
+            assert_eq!(&proposal_account.data[ProposalState::BASE_LEN..], stored_data.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_create_proposal_rejects_non_canonical_bump() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (mut instruction, mut tx_accounts, proposal_state_pda) = create_proposal(&[PROPOSER], 3_600);
+
+        // A valid program address for the same id at a lower bump
+        let (alias_pda, alias_bump) = (0..instruction.data[17])
+            .rev()
+            .find_map(|bump| {
+                Pubkey::create_program_address(&[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes(), &[bump]], &ID)
+                    .ok()
+                    .map(|pda| (pda, bump))
+            })
+            .unwrap();
+
+        instruction.data[17] = alias_bump;
+        instruction.accounts[3].pubkey = alias_pda;
+        tx_accounts.iter_mut().find(|(key, _)| key == &proposal_state_pda).unwrap().0 = alias_pda;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidSeeds)],
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_rejects_oversized_instruction_data() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let data_len = MAX_PROPOSAL_DATA_LEN + 1;
+        let mut payload = vec![ProposalType::ArbitraryCpi as u8];
+        payload.extend_from_slice(Pubkey::new_unique().as_ref());
+        payload.push(0); // num_accounts
+        payload.extend_from_slice(&(data_len as u16).to_le_bytes());
+        payload.extend_from_slice(&vec![0u8; data_len]);
+
+        let (instruction, tx_accounts, _) = create_proposal_with_payload(&[PROPOSER], 3_600, &payload);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_rejects_instruction_above_cpi_limits() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // Storable, but more than an executed CPI may pass
+        let data_len = MAX_CPI_DATA_LEN + 1;
+        let mut payload = vec![ProposalType::ArbitraryCpi as u8];
+        payload.extend_from_slice(Pubkey::new_unique().as_ref());
+        payload.push(0); // num_accounts
+        payload.extend_from_slice(&(data_len as u16).to_le_bytes());
+        payload.extend_from_slice(&vec![0u8; data_len]);
+
+        let (instruction, tx_accounts, _) = create_proposal_with_payload(&[PROPOSER], 3_600, &payload);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::CpiTooLarge as u32))],
+        );
+
+        let num_accounts = MAX_CPI_ACCOUNTS + 1;
+        let mut payload = vec![ProposalType::ArbitraryCpi as u8];
+        payload.extend_from_slice(Pubkey::new_unique().as_ref());
+        payload.push(num_accounts as u8);
+        for _ in 0..num_accounts {
+            payload.extend_from_slice(Pubkey::new_unique().as_ref());
+            payload.extend_from_slice(&[0, 0]); // is_signer, is_writable
+        }
+        payload.extend_from_slice(&0u16.to_le_bytes());
+
+        let (instruction, tx_accounts, _) = create_proposal_with_payload(&[PROPOSER], 3_600, &payload);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::CpiTooLarge as u32))],
+        );
+    }
+
+    #[test]
+    fn test_link_is_stored_and_included_in_creation_event() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let link = [0x6c; PROPOSAL_LINK_LEN];
+        let mut payload = vec![ProposalType::Transfer as u8];
+        payload.extend_from_slice(Pubkey::new_unique().as_ref());
+        payload.extend_from_slice(&500u64.to_le_bytes());
+        payload.extend_from_slice(&link);
+
+        let (instruction, tx_accounts, proposal_state_pda) = create_proposal_with_payload(&[PROPOSER], 3_600, &payload);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!(proposal.link, link);
+        assert_eq!(&proposal.payload[..32], &payload[1..33]);
+
+        // The logged event is built from the stored proposal
+        let event = ProposalCreated::deserialize(&ProposalCreated::new(&proposal).serialize()).unwrap();
+        assert_eq!(event.link, link);
+        assert_eq!(event.proposer, PROPOSER.to_bytes());
+    }
+
+    #[test]
+    fn test_create_proposal_rejects_truncated_link() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let mut payload = vec![ProposalType::Transfer as u8];
+        payload.extend_from_slice(Pubkey::new_unique().as_ref());
+        payload.extend_from_slice(&500u64.to_le_bytes());
+        payload.extend_from_slice(&[1u8; PROPOSAL_LINK_LEN - 1]);
+
+        let (instruction, tx_accounts, _) = create_proposal_with_payload(&[PROPOSER], 3_600, &payload);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::common::{require_signer, require_writable},
+    state::{Multisig, ROLE_CAN_VOTE},
+};
+
+/// Delegates the signer's vote to another member, or revokes the delegation when the
+/// delegate is the default pubkey. Votes the delegate already cast stand; see
+/// `process_revoke_delegation_instruction` to retract them.
+///
+/// While a delegation is active only the delegate can cast the member's vote, by passing
+/// the member's index to the vote instruction. Only members holding `ROLE_CAN_VOTE` have a
+/// vote to delegate.
+///
+/// Accounts: [member, multisig]
+///
+/// Instruction data (after the discriminator): [delegate: Pubkey]
+pub fn process_delegate_vote_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 32 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [member, multisig, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(member)?;
+    require_writable(multisig)?;
+
+    if multisig.owner() != &crate::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let delegate: Pubkey = data[0..32].try_into().unwrap();
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+    let member_index = multisig_data
+        .member_index(member.key())
+        .ok_or(MultisigError::NotAMember)?;
+
+    if delegate == Pubkey::default() {
+        multisig_data.delegations[member_index] = Pubkey::default();
+        log!("Delegation revoked");
+        return Ok(());
+    }
+
+    if !multisig_data.has_role(member.key(), ROLE_CAN_VOTE) {
+        log!("Error: Member is not allowed to vote");
+        return Err(MultisigError::MissingPermission.into());
+    }
+
+    if &delegate == member.key() {
+        log!("Error: Cannot delegate to yourself");
+        return Err(ProgramError::InvalidArgument);
+    };
+
+    if !multisig_data.is_member(&delegate) {
+        return Err(MultisigError::NotAMember.into());
+    }
+
+    multisig_data.delegations[member_index] = delegate;
+
+    log!("Vote delegated to {}", &delegate);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_delegate_vote_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const MEMBER: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+
+    fn delegate_vote(members: &[Pubkey], delegate: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>) {
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = members.len() as u8;
+            let members_offset = offset_of!(Multisig, members);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data
+        };
+
+        let mut data = vec![11u8]; // Instruction discriminator for delegate vote
+        data.extend_from_slice(delegate.as_ref());
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(MEMBER, true),    // member (signer)
+                AccountMeta::new(MULTISIG, false),          // multisig
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MEMBER, Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default())),
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+        ];
+
+        (instruction, tx_accounts)
+    }
+
+    #[test]
+    fn test_delegate_and_revoke() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let delegate = Pubkey::new_unique();
+        let (instruction, tx_accounts) = delegate_vote(&[Pubkey::new_unique(), MEMBER, delegate], delegate);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let delegation_offset = offset_of!(Multisig, delegations) + 32; // MEMBER is at index 1
+        let multisig = &result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        assert_eq!(&multisig.data[delegation_offset..delegation_offset + 32], delegate.as_ref());
+
+        // Delegating to the default pubkey revokes
+        let (mut revoke, _) = delegate_vote(&[], Pubkey::default());
+        revoke.accounts = instruction.accounts.clone();
+        let result = mollusk.process_and_validate_instruction(&revoke, &result.resulting_accounts, &[Check::success()]);
+
+        let multisig = &result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        assert_eq!(&multisig.data[delegation_offset..delegation_offset + 32], &[0u8; 32]);
+    }
+
+    #[test]
+    fn test_propose_only_member_cannot_delegate() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, mut tx_accounts) = delegate_vote(&[MEMBER, Pubkey::new_unique()], Pubkey::new_unique());
+        tx_accounts[1].1.data[offset_of!(Multisig, roles)] = crate::state::ROLE_CAN_PROPOSE;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::MissingPermission as u32))],
+        );
+    }
+
+    #[test]
+    fn test_delegate_to_non_member_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts) = delegate_vote(&[MEMBER, Pubkey::new_unique()], Pubkey::new_unique());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::NotAMember as u32))],
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    events::Deposit,
+    instructions::common::{require_signer, require_writable},
+    state::Multisig,
+};
+
+/// Moves `lamports` from the depositor into the multisig's treasury PDA, the account
+/// `ProposalType::Transfer` proposals pay from, and emits a `Deposit` event. Anyone can
+/// deposit. A plain system transfer to the treasury works as well, but leaves no event.
+///
+/// Accounts: [depositor, multisig, treasury, system_program]
+///
+/// Instruction data (after the discriminator): [lamports: u64]
+pub fn process_deposit_to_treasury_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [depositor, multisig, treasury, system_program, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(depositor)?;
+    require_writable(depositor)?;
+    require_writable(treasury)?;
+
+    if multisig.owner() != &crate::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if system_program.key() != &pinocchio_system::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let lamports = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    if lamports == 0 {
+        log!("Error: Deposit amount must be greater than zero");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let multisig_data = Multisig::from_account_info(multisig)?;
+
+    if treasury.key() != &multisig_data.treasury {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Transfer {
+        from: depositor,
+        to: treasury,
+        lamports,
+    }.invoke()?;
+
+    Deposit {
+        multisig: *multisig.key(),
+        depositor: *depositor.key(),
+        lamports,
+    }.emit();
+
+    log!("Deposited {} lamports into the treasury", lamports);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_deposit_to_treasury_instruction {
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const DEPOSITOR: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+
+    /// Deposit of `lamports` from DEPOSITOR into `treasury`, with MULTISIG's stored
+    /// treasury being the real treasury PDA
+    fn deposit(lamports: u64, treasury: Option<Pubkey>) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let (treasury_pda, treasury_bump) = Pubkey::find_program_address(
+            &[b"treasury", MULTISIG.as_ref()],
+            &ID,
+        );
+        let treasury = treasury.unwrap_or(treasury_pda);
+
+        let mut multisig = Multisig::zeroed();
+        multisig.version = Multisig::VERSION;
+        multisig.treasury = treasury_pda.to_bytes();
+        multisig.treasury_bump = treasury_bump;
+
+        let mut data = vec![26u8]; // Instruction discriminator for deposit to treasury
+        data.extend_from_slice(&lamports.to_le_bytes());
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(DEPOSITOR, true),
+                AccountMeta::new_readonly(MULTISIG, false),
+                AccountMeta::new(treasury, false),
+                AccountMeta::new_readonly(system_program_id, false),
+            ],
+        );
+
+        let accounts = vec![
+            (DEPOSITOR, Account::new(2 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (MULTISIG, Account::new_data(LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (treasury, Account::new(LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (system_program_id, system_account),
+        ];
+
+        (instruction, accounts, treasury)
+    }
+
+    #[test]
+    fn test_deposit_funds_treasury() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, accounts, treasury) = deposit(LAMPORTS_PER_SOL / 2, None);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[
+                Check::success(),
+                Check::account(&treasury).lamports(LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 2).build(),
+                Check::account(&DEPOSITOR).lamports(2 * LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 2).build(),
+            ],
+        );
+
+        // The logged event is built from the instruction's accounts and amount
+        let event = Deposit::deserialize(&Deposit {
+            multisig: instruction.accounts[1].pubkey.to_bytes(),
+            depositor: instruction.accounts[0].pubkey.to_bytes(),
+            lamports: u64::from_le_bytes(instruction.data[1..9].try_into().unwrap()),
+        }.serialize()).unwrap();
+        assert_eq!(event.multisig, MULTISIG.to_bytes());
+        assert_eq!(event.depositor, DEPOSITOR.to_bytes());
+        assert_eq!(event.lamports, LAMPORTS_PER_SOL / 2);
+    }
+
+    #[test]
+    fn test_deposit_to_other_account_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, accounts, _) = deposit(LAMPORTS_PER_SOL, Some(Pubkey::new_unique()));
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    #[test]
+    fn test_zero_deposit_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, accounts, _) = deposit(0, None);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey,
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::common::require_writable,
+    state::MultisigConfig,
+};
+
+/// Break-glass switch: sets or clears `MultisigConfig.paused`. While paused, voting,
+/// creating proposals and executing them all fail with `Paused`; unpausing restores
+/// normal operation, with proposals picking up where they left off.
+///
+/// Only the config's `config_authority`, signing and passed among the trailing accounts,
+/// can pause or unpause. No proposal could lift a pause, as none can execute meanwhile.
+///
+/// Accounts: [multisig, multisig_config, ..signers]
+///
+/// Instruction data (after the discriminator): [paused: u8], 1 pauses and 0 unpauses
+pub fn process_emergency_pause_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    let [paused] = data else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    if *paused > 1 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig_config)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let multisig_config_data = MultisigConfig::from_account_info_mut(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    multisig_config_data.check_config_authority(signers)?;
+
+    multisig_config_data.paused = *paused;
+
+    match *paused {
+        1 => log!("Multisig paused"),
+        _ => log!("Multisig unpaused"),
+    };
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_emergency_pause_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::{
+        error::MultisigError,
+        instructions::MultisigInstructions,
+        state::{Multisig, ProposalState, ProposalStatus, VoteReceipt, VoteState},
+    };
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const CREATOR: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const AUTHORITY: Pubkey = Pubkey::new_from_array([0x04; 32]);
+    const PROPOSAL_ID: u64 = 1;
+    const MULTISIG_SEED: u64 = 7;
+
+    struct Setup {
+        accounts: Vec<(Pubkey, Account)>,
+        multisig_pda: Pubkey,
+        multisig_config_pda: Pubkey,
+        vote: Instruction,
+        execute: Instruction,
+    }
+
+    /// A two member multisig with a threshold of 2 and one Active proposal with one of its
+    /// two votes cast, whose stored instruction is this program's no-op UpdateMultisig
+    fn setup() -> Setup {
+        let member = Pubkey::new_unique();
+
+        let (multisig_pda, multisig_bump) = Pubkey::find_program_address(
+            &[b"multisig", CREATOR.as_ref(), &MULTISIG_SEED.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", multisig_pda.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", multisig_pda.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (vote_state_pda, _) = Pubkey::find_program_address(
+            &[b"vote_state", multisig_pda.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let receipt_pda = |voter: &Pubkey| Pubkey::find_program_address(
+            &[b"receipt", proposal_state_pda.as_ref(), voter.as_ref()],
+            &ID,
+        ).0;
+
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+
+        let mut multisig = Multisig::zeroed();
+        multisig.version = Multisig::VERSION;
+        multisig.creator = CREATOR.to_bytes();
+        multisig.num_members = 2;
+        multisig.members[0] = CREATOR.to_bytes();
+        multisig.members[1] = member.to_bytes();
+        multisig.bump = multisig_bump;
+
+        let mut config = MultisigConfig::zeroed();
+        config.version = MultisigConfig::VERSION;
+        config.min_threshold = 2;
+        config.config_authority = AUTHORITY.to_bytes();
+        config.bump = config_bump;
+
+        let mut proposal = ProposalState::zeroed();
+        proposal.proposal_id = PROPOSAL_ID;
+        proposal.expiry = 9999999999;
+        proposal.set_status(ProposalStatus::Active);
+        proposal.bump = proposal_bump;
+        proposal.active_member_count = 2;
+        proposal.active_members[0] = CREATOR.to_bytes();
+        proposal.active_members[1] = member.to_bytes();
+        proposal.target_program = ID.to_bytes();
+        proposal.instruction_data_len = 1;
+
+        let mut proposal_data = bytemuck::bytes_of(&proposal).to_vec();
+        proposal_data.push(MultisigInstructions::UpdateMultisig as u8);
+
+        let accounts = vec![
+            (CREATOR, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (member, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (AUTHORITY, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (multisig_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &proposal_data, &ID).unwrap()),
+            (vote_state_pda, Account::new(0, 0, &system_program_id)),
+            (receipt_pda(&CREATOR), Account::new(0, 0, &system_program_id)),
+            (receipt_pda(&member), Account::new(0, 0, &system_program_id)),
+            (system_program_id, system_account),
+            (ID, program::create_program_account_loader_v3(&ID)),
+        ];
+
+        let vote = |voter: Pubkey| {
+            let mut data = vec![3u8]; // Instruction discriminator for vote
+            data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+            data.push(1); // Vote choice: For
+            data.push(proposal_bump);
+
+            Instruction::new_with_bytes(
+                ID,
+                &data,
+                vec![
+                    AccountMeta::new(voter, true),
+                    AccountMeta::new(multisig_pda, false),
+                    AccountMeta::new(proposal_state_pda, false),
+                    AccountMeta::new(vote_state_pda, false),
+                    AccountMeta::new_readonly(multisig_config_pda, false),
+                    AccountMeta::new_readonly(system_program_id, false),
+                    AccountMeta::new(receipt_pda(&voter), false),
+                ],
+            )
+        };
+
+        let mut data = vec![8u8]; // Instruction discriminator for execute proposal
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(proposal_bump);
+        data.extend_from_slice(&MULTISIG_SEED.to_le_bytes());
+
+        let execute = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(CREATOR, true),                       // executor (signer)
+                AccountMeta::new(multisig_pda, false),                 // multisig
+                AccountMeta::new(proposal_state_pda, false),           // proposal_state
+                AccountMeta::new_readonly(multisig_config_pda, false), // multisig_config
+                AccountMeta::new_readonly(ID, false),                  // target program
+            ],
+        );
+
+        // The first vote is cast before the pause, so one more vote decides the proposal
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        let accounts = mollusk.process_and_validate_instruction(&vote(CREATOR), &accounts, &[Check::success()]).resulting_accounts;
+
+        Setup { accounts, multisig_pda, multisig_config_pda, vote: vote(member), execute }
+    }
+
+    fn set_paused(setup: &Setup, paused: u8) -> Instruction {
+        Instruction::new_with_bytes(
+            ID,
+            &[21u8, paused], // Instruction discriminator for emergency pause
+            vec![
+                AccountMeta::new_readonly(setup.multisig_pda, false),
+                AccountMeta::new(setup.multisig_config_pda, false),
+                AccountMeta::new_readonly(AUTHORITY, true),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_pause_halts_voting_and_execution_until_unpaused() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        let setup = setup();
+        let paused_error = || Check::err(ProgramError::Custom(MultisigError::Paused as u32));
+
+        let accounts = mollusk.process_and_validate_instruction(&set_paused(&setup, 1), &setup.accounts, &[Check::success()]).resulting_accounts;
+
+        mollusk.process_and_validate_instruction(&setup.vote, &accounts, &[paused_error()]);
+
+        // Unpausing lets the vote through, deciding the proposal
+        let accounts = mollusk.process_and_validate_instruction(&set_paused(&setup, 0), &accounts, &[Check::success()]).resulting_accounts;
+        let accounts = mollusk.process_and_validate_instruction(&setup.vote, &accounts, &[Check::success()]).resulting_accounts;
+
+        // Pausing again blocks executing the Succeeded proposal until the next unpause
+        let accounts = mollusk.process_and_validate_instruction(&set_paused(&setup, 1), &accounts, &[Check::success()]).resulting_accounts;
+
+        mollusk.process_and_validate_instruction(&setup.execute, &accounts, &[paused_error()]);
+
+        let accounts = mollusk.process_and_validate_instruction(&set_paused(&setup, 0), &accounts, &[Check::success()]).resulting_accounts;
+        let result = mollusk.process_and_validate_instruction(&setup.execute, &accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &setup.execute.accounts[2].pubkey).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert!(matches!(proposal.status(), Ok(ProposalStatus::Executed)));
+
+        let vote_state_account = &result.resulting_accounts.iter().find(|(key, _)| key == &setup.vote.accounts[3].pubkey).unwrap().1;
+        let vote_state: VoteState = bytemuck::pod_read_unaligned(&vote_state_account.data);
+        assert_eq!(vote_state.vote_count, 2);
+        assert_eq!(result.resulting_accounts.iter().find(|(key, _)| key == &setup.vote.accounts[6].pubkey).unwrap().1.data.len(), VoteReceipt::LEN);
+    }
+
+    #[test]
+    fn test_only_config_authority_can_pause() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        let setup = setup();
+
+        let mut instruction = set_paused(&setup, 1);
+        instruction.accounts[2] = AccountMeta::new_readonly(CREATOR, true);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &setup.accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::UnauthorizedConfigChange as u32))],
+        );
+
+        let config_account = &setup.accounts.iter().find(|(key, _)| key == &setup.multisig_config_pda).unwrap().1;
+        assert_eq!(config_account.data[offset_of!(MultisigConfig, paused)], 0);
+    }
+}
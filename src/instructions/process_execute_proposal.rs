@@ -0,0 +1,879 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::slice_invoke_signed,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    log::sol_log_data,
+    program_error::ProgramError,
+    pubkey::{self},
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::{common::{require_signer, require_writable}, process_transfer_from_multisig_instruction, MultisigInstructions},
+    state::{Multisig, MultisigConfig, ProposalState, ProposalStatus, ProposalType, MAX_CPI_ACCOUNTS, MAX_CPI_DATA_LEN, MAX_PROPOSAL_ACCOUNTS, MAX_PROPOSAL_PAYLOAD_LEN, ROLE_CAN_EXECUTE},
+};
+
+/// Executes a Succeeded proposal once its timelock, the `execution_delay` snapshotted at
+/// creation, has elapsed since it succeeded.
+///
+/// ArbitraryCpi proposals invoke their stored instruction, signed by both the multisig
+/// PDA and its treasury PDA, so stored instructions can act as the multisig (e.g.
+/// membership changes) or move funds held by the system-owned treasury. Typed proposals
+/// apply their built-in action: Transfer pays out of the treasury, while AddMember,
+/// RemoveMember, ChangeThreshold and ConfigChange invoke this program's own instruction
+/// with the multisig PDA as signer.
+///
+/// Only members holding `ROLE_CAN_EXECUTE` can execute.
+///
+/// The proposal is marked executed before any CPI, so a callee re-entering this
+/// instruction gets `AlreadyExecuted`, and its status moves to `Executed` once the action
+/// completes. After the action the multisig PDA must still hold its rent-exempt minimum.
+///
+/// Nothing executes while the config's emergency pause is on.
+///
+/// Accounts: [executor, multisig, proposal_state, multisig_config, ..action accounts] where
+/// the action accounts are
+/// - ArbitraryCpi: [target_program, ..stored instruction accounts], invoked with exactly the
+///   `instruction_data_len` bytes stored after the fixed part of the proposal. Instructions
+///   passing more than `MAX_CPI_ACCOUNTS` accounts or `MAX_CPI_DATA_LEN` bytes fail with
+///   `CpiTooLarge`. Creation rejects those already; this covers proposals stored before.
+/// - Transfer: [treasury, recipient, system_program], see `process_transfer_from_multisig_instruction`
+/// - AddMember, RemoveMember, ChangeThreshold, ConfigChange: [multisig_program], acting on `multisig_config`
+///
+/// Instruction data (after the discriminator): [proposal_id: u64][proposal_bump: u8][multisig_seed: u64]
+pub fn process_execute_proposal_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 17 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [executor, multisig, proposal_state, multisig_config, action_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(executor)?;
+    require_writable(multisig)?;
+    require_writable(proposal_state)?;
+
+    for accounts in [multisig, proposal_state, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let proposal_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let proposal_bump = data[8];
+    let multisig_seed = u64::from_le_bytes(data[9..17].try_into().unwrap());
+
+    let multisig_data = Multisig::from_account_info(multisig)?;
+    let multisig_config_data = MultisigConfig::load(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if let Err(error) = multisig_config_data.require_not_paused() {
+        log!("Error: Execution is paused");
+        return Err(error);
+    }
+
+    if !multisig_data.has_role(executor.key(), ROLE_CAN_EXECUTE) {
+        log!("Error: Executor is not allowed to execute proposals");
+        return Err(MultisigError::MissingPermission.into());
+    }
+
+    let (proposal_data, stored_instruction_data) = ProposalState::from_account_info_with_data(proposal_state)?;
+
+    let proposal_seed = [
+        b"proposal".as_slice(),
+        multisig.key().as_slice(),
+        &proposal_id.to_le_bytes(),
+        &[proposal_bump],
+    ];
+    let proposal_pda = pubkey::checked_create_program_address(&proposal_seed, &crate::ID)?;
+
+    if &proposal_pda != proposal_state.key() || proposal_data.proposal_id != proposal_id {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    match proposal_data.status()? {
+        ProposalStatus::Succeeded => {},
+        ProposalStatus::Executed => {
+            log!("Proposal has already been executed");
+            return Err(MultisigError::AlreadyExecuted.into());
+        },
+        _ => return Err(MultisigError::ThresholdNotMet.into()),
+    };
+
+    if proposal_data.executed != 0 {
+        log!("Proposal has already been executed");
+        return Err(MultisigError::AlreadyExecuted.into());
+    }
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    if current_time < proposal_data.executable_at() {
+        log!("Error: Proposal can't be executed before {}", proposal_data.executable_at());
+        return Err(MultisigError::TimelockNotElapsed.into());
+    }
+
+    let seed_bytes = multisig_seed.to_le_bytes();
+    let multisig_pda_seed = [
+        b"multisig".as_slice(),
+        multisig_data.creator.as_slice(),
+        seed_bytes.as_slice(),
+        &[multisig_data.bump],
+    ];
+    let multisig_pda = pubkey::checked_create_program_address(&multisig_pda_seed, &crate::ID)?;
+
+    if &multisig_pda != multisig.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Mark as executed before any CPI so the callee can't replay this proposal
+    proposal_data.executed = 1;
+
+    let multisig_signer_seeds = multisig_data.signer_seeds(&seed_bytes);
+
+    let treasury_bump = [multisig_data.treasury_bump];
+    let treasury_signer_seeds = [
+        Seed::from(b"treasury"),
+        Seed::from(multisig.key()),
+        Seed::from(&treasury_bump),
+    ];
+
+    let proposal_type = ProposalType::try_from(&proposal_data.proposal_type)?;
+    let payload = &proposal_data.payload[..proposal_type.payload_len()];
+
+    match proposal_type {
+        ProposalType::ArbitraryCpi => {
+            let [target_program, cpi_accounts @ ..] = action_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            let num_accounts = proposal_data.num_accounts as usize;
+
+            // Bounds the compute the CPI can burn, whatever the stored instruction holds
+            if num_accounts > MAX_CPI_ACCOUNTS || stored_instruction_data.len() > MAX_CPI_DATA_LEN {
+                log!("Error: A CPI can pass at most {} accounts and {} bytes of data", MAX_CPI_ACCOUNTS, MAX_CPI_DATA_LEN);
+                return Err(MultisigError::CpiTooLarge.into());
+            }
+
+            if cpi_accounts.len() < num_accounts {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+
+            if target_program.key() != &proposal_data.target_program {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+
+            for i in 0..num_accounts {
+                if cpi_accounts[i].key() != &proposal_data.accounts[i].pubkey {
+                    log!("Error: Account {} does not match the stored instruction", i);
+                    return Err(ProgramError::InvalidAccountData);
+                }
+            }
+
+            let account_metas: [AccountMeta; MAX_PROPOSAL_ACCOUNTS] = core::array::from_fn(|i| {
+                let meta = &proposal_data.accounts[i];
+                AccountMeta::new(&meta.pubkey, meta.is_writable != 0, meta.is_signer != 0)
+            });
+
+            let account_infos: [&AccountInfo; MAX_PROPOSAL_ACCOUNTS] = core::array::from_fn(|i| {
+                cpi_accounts.get(i).unwrap_or(executor)
+            });
+
+            let instruction = Instruction {
+                program_id: &proposal_data.target_program,
+                data: stored_instruction_data,
+                accounts: &account_metas[..num_accounts],
+            };
+
+            slice_invoke_signed(
+                &instruction,
+                &account_infos[..num_accounts],
+                &[Signer::from(&multisig_signer_seeds), Signer::from(&treasury_signer_seeds)],
+            )?;
+        },
+        ProposalType::Transfer => {
+            process_transfer_from_multisig_instruction(
+                multisig_data,
+                action_accounts,
+                payload,
+                Signer::from(&treasury_signer_seeds),
+            )?;
+        },
+        ProposalType::AddMember | ProposalType::RemoveMember | ProposalType::ChangeThreshold | ProposalType::ConfigChange => {
+            let [_multisig_program, ..] = action_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            let discriminator = match proposal_type {
+                ProposalType::AddMember => MultisigInstructions::AddMember,
+                ProposalType::RemoveMember => MultisigInstructions::RemoveMember,
+                ProposalType::ConfigChange => MultisigInstructions::ChangeConfig,
+                _ => MultisigInstructions::ChangeThreshold,
+            };
+
+            let mut instruction_data = [0u8; 1 + MAX_PROPOSAL_PAYLOAD_LEN];
+            instruction_data[0] = discriminator as u8;
+            instruction_data[1..=payload.len()].copy_from_slice(payload);
+
+            let account_metas = [
+                AccountMeta::new(multisig.key(), true, true),
+                AccountMeta::new(multisig_config.key(), multisig_config.is_writable(), false),
+            ];
+
+            let instruction = Instruction {
+                program_id: &crate::ID,
+                data: &instruction_data[..1 + payload.len()],
+                accounts: &account_metas,
+            };
+
+            slice_invoke_signed(
+                &instruction,
+                &[multisig, multisig_config],
+                &[Signer::from(&multisig_signer_seeds)],
+            )?;
+        },
+        ProposalType::CloseMultisig => {
+            log!("Error: CloseMultisig proposals authorize CloseMultisig and have no action to execute");
+            return Err(ProgramError::InvalidInstructionData);
+        },
+        ProposalType::Poll => {
+            log!("Error: Poll proposals only record a decision and have no action to execute");
+            return Err(ProgramError::InvalidInstructionData);
+        },
+    }
+
+    // The action must not have drained the multisig account below rent exemption
+    if multisig.lamports() < Rent::get()?.minimum_balance(multisig.data_len()) {
+        log!("Error: Execution would leave the multisig below its rent-exempt minimum");
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    proposal_data.set_status(ProposalStatus::Executed);
+    // Reloaded, as the action may have changed the multisig account
+    Multisig::from_account_info_mut(multisig)?.record_outcome(ProposalStatus::Executed)?;
+    sol_log_data(&[&proposal_data.serialize_for_log()]);
+
+    log!("Proposal {} executed", proposal_id);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_execute_proposal_instruction {
+    use core::mem::{offset_of, size_of};
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::state::ProposalAccountMeta;
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const CREATOR: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const RECIPIENT: Pubkey = Pubkey::new_from_array([0x03; 32]);
+
+    #[test]
+    fn test_execute_succeeded_proposal_transfers_lamports() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let multisig_seed = 7u64;
+        let proposal_id = 1u64;
+        let amount = LAMPORTS_PER_SOL / 2;
+
+        let (multisig_pda, multisig_bump) = Pubkey::find_program_address(
+            &[b"multisig", CREATOR.as_ref(), &multisig_seed.to_le_bytes()],
+            &ID,
+        );
+        let (treasury_pda, treasury_bump) = Pubkey::find_program_address(
+            &[b"treasury", multisig_pda.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", multisig_pda.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", multisig_pda.as_ref()],
+            &ID,
+        );
+
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+
+        let mut config = MultisigConfig::zeroed();
+        config.version = MultisigConfig::VERSION;
+        config.bump = config_bump;
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, creator)..][..32].copy_from_slice(CREATOR.as_ref());
+            data[offset_of!(Multisig, num_members)] = 1;
+            data[offset_of!(Multisig, members)..][..32].copy_from_slice(CREATOR.as_ref());
+            data[offset_of!(Multisig, bump)] = multisig_bump;
+            data[offset_of!(Multisig, treasury)..][..32].copy_from_slice(treasury_pda.as_ref());
+            data[offset_of!(Multisig, treasury_bump)] = treasury_bump;
+            data
+        };
+
+        // The stored instruction: system transfer from the treasury to the recipient
+        let mut transfer_data = vec![2, 0, 0, 0];
+        transfer_data.extend_from_slice(&amount.to_le_bytes());
+        let transfer = Instruction::new_with_bytes(
+            system_program_id,
+            &transfer_data,
+            vec![AccountMeta::new(treasury_pda, true), AccountMeta::new(RECIPIENT, false)],
+        );
+
+        let proposal_data = {
+            let mut data = vec![0u8; ProposalState::BASE_LEN + transfer.data.len()];
+            data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&proposal_id.to_le_bytes());
+            data[offset_of!(ProposalState, result)] = ProposalStatus::Succeeded as u8;
+            data[offset_of!(ProposalState, num_accounts)] = transfer.accounts.len() as u8;
+            data[offset_of!(ProposalState, instruction_data_len)..][..2].copy_from_slice(&(transfer.data.len() as u16).to_le_bytes());
+            data[offset_of!(ProposalState, target_program)..][..32].copy_from_slice(transfer.program_id.as_ref());
+            for (i, meta) in transfer.accounts.iter().enumerate() {
+                let offset = offset_of!(ProposalState, accounts) + i * size_of::<ProposalAccountMeta>();
+                data[offset + offset_of!(ProposalAccountMeta, pubkey)..][..32].copy_from_slice(meta.pubkey.as_ref());
+                data[offset + offset_of!(ProposalAccountMeta, is_signer)] = meta.is_signer as u8;
+                data[offset + offset_of!(ProposalAccountMeta, is_writable)] = meta.is_writable as u8;
+            }
+            data[ProposalState::BASE_LEN..].copy_from_slice(&transfer.data);
+            data
+        };
+
+        let mut data = vec![8u8]; // Instruction discriminator for execute proposal
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.push(proposal_bump);
+        data.extend_from_slice(&multisig_seed.to_le_bytes());
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(CREATOR, true),                      // executor (signer)
+                AccountMeta::new(multisig_pda, false),                // multisig
+                AccountMeta::new(proposal_state_pda, false),          // proposal_state
+                AccountMeta::new_readonly(multisig_config_pda, false), // multisig_config
+                AccountMeta::new_readonly(system_program_id, false),  // target program
+                AccountMeta::new(treasury_pda, false),                // transfer source
+                AccountMeta::new(RECIPIENT, false),                   // transfer destination
+            ],
+        );
+
+        let tx_accounts = vec![
+            (CREATOR, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (multisig_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &proposal_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (system_program_id, system_account),
+            (treasury_pda, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (RECIPIENT, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+        ];
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[
+                Check::success(),
+                Check::account(&treasury_pda).lamports(LAMPORTS_PER_SOL - amount).build(),
+                Check::account(&RECIPIENT).lamports(LAMPORTS_PER_SOL + amount).build(),
+            ],
+        );
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, executed)], 1);
+    }
+
+    #[test]
+    fn test_change_threshold_proposal_end_to_end() {
+        let (accounts, _, multisig_config_pda) = governance_proposal_end_to_end(ProposalType::ChangeThreshold, &2u64.to_le_bytes());
+
+        let config_account = &accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let config: MultisigConfig = bytemuck::pod_read_unaligned(&config_account.data);
+        assert_eq!(config.min_threshold, 2);
+    }
+
+    #[test]
+    fn test_config_change_proposal_end_to_end() {
+        let payload = [2u64.to_le_bytes(), 2u64.to_le_bytes(), 60u64.to_le_bytes()].concat();
+        let (accounts, _, multisig_config_pda) = governance_proposal_end_to_end(ProposalType::ConfigChange, &payload);
+
+        let config_account = &accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let config: MultisigConfig = bytemuck::pod_read_unaligned(&config_account.data);
+        assert_eq!((config.min_threshold, config.quorum, config.execution_delay), (2, 2, 60));
+    }
+
+    #[test]
+    fn test_add_member_proposal_end_to_end() {
+        let new_member = Pubkey::new_unique();
+        let (accounts, multisig_pda, _) = governance_proposal_end_to_end(ProposalType::AddMember, new_member.as_ref());
+
+        let multisig_account = &accounts.iter().find(|(key, _)| key == &multisig_pda).unwrap().1;
+        let multisig: Multisig = bytemuck::pod_read_unaligned(&multisig_account.data);
+        assert_eq!(multisig.num_members, 3);
+        assert!(multisig.members[..3].contains(&new_member.to_bytes()));
+    }
+
+    #[test]
+    fn test_statistics_count_created_succeeded_and_executed_proposals() {
+        let (accounts, multisig_pda, _) = governance_proposal_end_to_end(ProposalType::ChangeThreshold, &2u64.to_le_bytes());
+
+        let multisig_account = &accounts.iter().find(|(key, _)| key == &multisig_pda).unwrap().1;
+        let multisig: Multisig = bytemuck::pod_read_unaligned(&multisig_account.data);
+        assert_eq!(multisig.total_proposals, 1);
+        assert_eq!(multisig.succeeded_count, 1);
+        assert_eq!(multisig.failed_count, 0);
+        assert_eq!(multisig.executed_count, 1);
+    }
+
+    /// Proposes a built-in governance action with `payload` on a two member multisig with a
+    /// threshold of 1, votes it through and executes it, returning the resulting accounts
+    /// with the multisig and config addresses
+    fn governance_proposal_end_to_end(proposal_type: ProposalType, payload: &[u8]) -> (Vec<(Pubkey, Account)>, Pubkey, Pubkey) {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let multisig_seed = 7u64;
+        let proposal_id = 1u64;
+        let member = Pubkey::new_unique();
+
+        let (multisig_pda, multisig_bump) = Pubkey::find_program_address(
+            &[b"multisig", CREATOR.as_ref(), &multisig_seed.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", multisig_pda.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", multisig_pda.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (vote_state_pda, _) = Pubkey::find_program_address(
+            &[b"vote_state", multisig_pda.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[b"receipt", proposal_state_pda.as_ref(), member.as_ref()],
+            &ID,
+        );
+
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+
+        let mut multisig = Multisig::zeroed();
+
+        multisig.version = Multisig::VERSION;
+        multisig.creator = CREATOR.to_bytes();
+        multisig.num_members = 2;
+        multisig.members[0] = CREATOR.to_bytes();
+        multisig.members[1] = member.to_bytes();
+        multisig.bump = multisig_bump;
+
+        let mut config = MultisigConfig::zeroed();
+
+        config.version = MultisigConfig::VERSION;
+        config.min_threshold = 1;
+        config.bump = config_bump;
+
+        // Every instruction sees the same account set, carried over between steps
+        let mut accounts = vec![
+            (CREATOR, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (member, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (multisig_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (proposal_state_pda, Account::new(0, 0, &system_program_id)),
+            (vote_state_pda, Account::new(0, 0, &system_program_id)),
+            (vote_receipt_pda, Account::new(0, 0, &system_program_id)),
+            (system_program_id, system_account),
+            (ID, program::create_program_account_loader_v3(&ID)),
+        ];
+
+        // 1. Propose the action
+        let mut data = vec![2u8]; // Instruction discriminator for create proposal
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.extend_from_slice(&3_600u64.to_le_bytes());
+        data.push(proposal_bump);
+        data.push(proposal_type as u8);
+        data.extend_from_slice(payload);
+
+        let create = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(CREATOR, true),
+                AccountMeta::new(multisig_pda, false),
+                AccountMeta::new_readonly(multisig_config_pda, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+            ],
+        );
+        accounts = mollusk.process_and_validate_instruction(&create, &accounts, &[Check::success()]).resulting_accounts;
+
+        // 2. One For vote meets the current threshold of 1
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.push(1); // Vote choice: For
+        data.push(proposal_bump);
+
+        let vote = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(member, true),
+                AccountMeta::new(multisig_pda, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new(vote_state_pda, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(vote_receipt_pda, false),
+            ],
+        );
+        accounts = mollusk.process_and_validate_instruction(&vote, &accounts, &[Check::success()]).resulting_accounts;
+
+        // 3. Execute: the program invokes its own instruction for the action as the multisig
+        let mut data = vec![8u8]; // Instruction discriminator for execute proposal
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.push(proposal_bump);
+        data.extend_from_slice(&multisig_seed.to_le_bytes());
+
+        let execute = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(CREATOR, true),                  // executor (signer)
+                AccountMeta::new(multisig_pda, false),            // multisig
+                AccountMeta::new(proposal_state_pda, false),      // proposal_state
+                AccountMeta::new(multisig_config_pda, false),     // multisig_config
+                AccountMeta::new_readonly(ID, false),             // multisig program
+            ],
+        );
+        let result = mollusk.process_and_validate_instruction(&execute, &accounts, &[Check::success()]);
+
+        (result.resulting_accounts, multisig_pda, multisig_config_pda)
+    }
+
+    /// Creates an ArbitraryCpi proposal storing `stored_data` for a no-account instruction to
+    /// this program, votes it to Succeeded and returns the execute instruction with the
+    /// resulting accounts
+    fn succeeded_self_cpi_proposal(mollusk: &Mollusk, stored_data: &[u8], execution_delay: u64) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let multisig_seed = 7u64;
+        let proposal_id = 1u64;
+        let member = Pubkey::new_unique();
+
+        let (multisig_pda, multisig_bump) = Pubkey::find_program_address(
+            &[b"multisig", CREATOR.as_ref(), &multisig_seed.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", multisig_pda.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", multisig_pda.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (vote_state_pda, _) = Pubkey::find_program_address(
+            &[b"vote_state", multisig_pda.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[b"receipt", proposal_state_pda.as_ref(), member.as_ref()],
+            &ID,
+        );
+
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+
+        let mut multisig = Multisig::zeroed();
+
+        multisig.version = Multisig::VERSION;
+        multisig.creator = CREATOR.to_bytes();
+        multisig.num_members = 2;
+        multisig.members[0] = CREATOR.to_bytes();
+        multisig.members[1] = member.to_bytes();
+        multisig.bump = multisig_bump;
+
+        let mut config = MultisigConfig::zeroed();
+
+        config.version = MultisigConfig::VERSION;
+        config.min_threshold = 1;
+        config.execution_delay = execution_delay;
+        config.bump = config_bump;
+
+        let mut accounts = vec![
+            (CREATOR, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (member, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (multisig_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (proposal_state_pda, Account::new(0, 0, &system_program_id)),
+            (vote_state_pda, Account::new(0, 0, &system_program_id)),
+            (vote_receipt_pda, Account::new(0, 0, &system_program_id)),
+            (system_program_id, system_account),
+            (ID, program::create_program_account_loader_v3(&ID)),
+        ];
+
+        // 1. Propose invoking this program with the stored data and no accounts
+        let mut data = vec![2u8]; // Instruction discriminator for create proposal
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.extend_from_slice(&3_600u64.to_le_bytes());
+        data.push(proposal_bump);
+        data.push(ProposalType::ArbitraryCpi as u8);
+        data.extend_from_slice(ID.as_ref()); // target_program
+        data.push(0); // num_accounts
+        data.extend_from_slice(&(stored_data.len() as u16).to_le_bytes());
+        data.extend_from_slice(stored_data);
+
+        let create = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(CREATOR, true),
+                AccountMeta::new(multisig_pda, false),
+                AccountMeta::new_readonly(multisig_config_pda, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+            ],
+        );
+        accounts = mollusk.process_and_validate_instruction(&create, &accounts, &[Check::success()]).resulting_accounts;
+
+        // 2. One For vote meets the threshold of 1
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.push(1); // Vote choice: For
+        data.push(proposal_bump);
+
+        let vote = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(member, true),
+                AccountMeta::new(multisig_pda, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new(vote_state_pda, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(vote_receipt_pda, false),
+            ],
+        );
+        accounts = mollusk.process_and_validate_instruction(&vote, &accounts, &[Check::success()]).resulting_accounts;
+
+        // 3. Execute, invoking this program with the stored data
+        let mut data = vec![8u8]; // Instruction discriminator for execute proposal
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.push(proposal_bump);
+        data.extend_from_slice(&multisig_seed.to_le_bytes());
+
+        let execute = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(CREATOR, true),                  // executor (signer)
+                AccountMeta::new(multisig_pda, false),            // multisig
+                AccountMeta::new(proposal_state_pda, false),      // proposal_state
+                AccountMeta::new_readonly(multisig_config_pda, false), // multisig_config
+                AccountMeta::new_readonly(ID, false),             // target program
+            ],
+        );
+
+        (execute, accounts, proposal_state_pda)
+    }
+
+    #[test]
+    fn test_execute_passes_128_byte_stored_data_through() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        // UpdateMultisig is a no-op, so the invoke only succeeds if the data arrives intact
+        let mut stored_data = vec![MultisigInstructions::UpdateMultisig as u8];
+        stored_data.extend((1..128).map(|i| i as u8));
+
+        let (execute, accounts, proposal_state_pda) = succeeded_self_cpi_proposal(&mollusk, &stored_data, 0);
+
+        let result = mollusk.process_and_validate_instruction(
+            &execute,
+            &accounts,
+            &[
+                Check::success(),
+                Check::account(&proposal_state_pda).space(ProposalState::BASE_LEN + 128).build(),
+            ],
+        );
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, executed)], 1);
+        assert_eq!(&proposal_account.data[ProposalState::BASE_LEN..], stored_data.as_slice());
+    }
+
+    /// Rewrites the stored part of the proposal account in `accounts`
+    fn edit_proposal(accounts: &mut [(Pubkey, Account)], proposal_state_pda: &Pubkey, edit: impl FnOnce(&mut Vec<u8>)) {
+        let proposal_account = &mut accounts.iter_mut().find(|(key, _)| key == proposal_state_pda).unwrap().1;
+        edit(&mut proposal_account.data);
+    }
+
+    #[test]
+    fn test_execute_forwards_cpi_data_up_to_the_limit() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let mut stored_data = vec![MultisigInstructions::UpdateMultisig as u8];
+        stored_data.resize(MAX_CPI_DATA_LEN, 0);
+
+        let (execute, accounts, proposal_state_pda) = succeeded_self_cpi_proposal(&mollusk, &stored_data, 0);
+
+        mollusk.process_and_validate_instruction(&execute, &accounts, &[Check::success()]);
+
+        // One byte over the limit is never forwarded
+        let mut accounts = accounts;
+        edit_proposal(&mut accounts, &proposal_state_pda, |data| {
+            data.push(0);
+            let len_offset = offset_of!(ProposalState, instruction_data_len);
+            data[len_offset..len_offset + 2].copy_from_slice(&(MAX_CPI_DATA_LEN as u16 + 1).to_le_bytes());
+        });
+
+        mollusk.process_and_validate_instruction(
+            &execute,
+            &accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::CpiTooLarge as u32))],
+        );
+    }
+
+    #[test]
+    fn test_execute_forwards_cpi_accounts_up_to_the_limit() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let (mut execute, mut accounts, proposal_state_pda) =
+            succeeded_self_cpi_proposal(&mollusk, &[MultisigInstructions::UpdateMultisig as u8], 0);
+
+        // The zeroed stored metas name the system program, passed read-only in every slot
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        execute.accounts.extend((0..MAX_CPI_ACCOUNTS).map(|_| AccountMeta::new_readonly(system_program_id, false)));
+
+        edit_proposal(&mut accounts, &proposal_state_pda, |data| data[offset_of!(ProposalState, num_accounts)] = MAX_CPI_ACCOUNTS as u8);
+        mollusk.process_and_validate_instruction(&execute, &accounts, &[Check::success()]);
+
+        execute.accounts.push(AccountMeta::new_readonly(system_program_id, false));
+        edit_proposal(&mut accounts, &proposal_state_pda, |data| data[offset_of!(ProposalState, num_accounts)] = MAX_CPI_ACCOUNTS as u8 + 1);
+        mollusk.process_and_validate_instruction(
+            &execute,
+            &accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::CpiTooLarge as u32))],
+        );
+    }
+
+    #[test]
+    fn test_execute_passes_empty_stored_data_through() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let (execute, accounts, _) = succeeded_self_cpi_proposal(&mollusk, &[], 0);
+
+        // The invoked program sees exactly zero bytes, which it rejects as a missing discriminator
+        mollusk.process_and_validate_instruction(
+            &execute,
+            &accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_second_execute_is_rejected() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let stored_data = [MultisigInstructions::UpdateMultisig as u8];
+        let (execute, accounts, _) = succeeded_self_cpi_proposal(&mollusk, &stored_data, 0);
+
+        let result = mollusk.process_and_validate_instruction(&execute, &accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &execute.accounts[2].pubkey).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Executed as u8);
+
+        mollusk.process_and_validate_instruction(
+            &execute,
+            &result.resulting_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::AlreadyExecuted as u32))],
+        );
+    }
+
+    #[test]
+    fn test_vote_only_member_cannot_execute() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let stored_data = [MultisigInstructions::UpdateMultisig as u8];
+        let (execute, mut accounts, _) = succeeded_self_cpi_proposal(&mollusk, &stored_data, 0);
+
+        // CREATOR is the executor and the first member
+        let multisig_account = &mut accounts.iter_mut().find(|(key, _)| key == &execute.accounts[1].pubkey).unwrap().1;
+        multisig_account.data[offset_of!(Multisig, roles)] = crate::state::ROLE_CAN_VOTE;
+
+        mollusk.process_and_validate_instruction(
+            &execute,
+            &accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::MissingPermission as u32))],
+        );
+    }
+
+    #[test]
+    fn test_execute_before_timelock_is_rejected() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let stored_data = [MultisigInstructions::UpdateMultisig as u8];
+        let (execute, accounts, proposal_state_pda) = succeeded_self_cpi_proposal(&mollusk, &stored_data, 600);
+
+        let proposal_account = &accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!((proposal.succeeded_at, proposal.execution_delay), (1_000, 600));
+
+        // One second short of the delay
+        mollusk.sysvars.clock.unix_timestamp = 1_599;
+        mollusk.process_and_validate_instruction(
+            &execute,
+            &accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::TimelockNotElapsed as u32))],
+        );
+    }
+
+    #[test]
+    fn test_execute_after_timelock_succeeds() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let stored_data = [MultisigInstructions::UpdateMultisig as u8];
+        let (execute, accounts, proposal_state_pda) = succeeded_self_cpi_proposal(&mollusk, &stored_data, 600);
+
+        mollusk.sysvars.clock.unix_timestamp = 1_600;
+        let result = mollusk.process_and_validate_instruction(&execute, &accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, executed)], 1);
+    }
+}
@@ -0,0 +1,275 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::common::{require_signer, require_writable},
+    state::{ProposalState, ProposalStatus, MAX_PROPOSAL_DURATION},
+};
+
+/// Pushes the expiry of an Active, unexpired proposal later by `extension` seconds.
+///
+/// Only the proposer can extend, and the total lifetime of the proposal (measured from
+/// `created_time`) can't exceed `MAX_PROPOSAL_DURATION`.
+///
+/// Accounts: [proposer, multisig, proposal_state]
+///
+/// Instruction data (after the discriminator): [proposal_id: u64][proposal_bump: u8][extension: u64]
+pub fn process_extend_proposal_expiry_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 17 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [proposer, multisig, proposal_state, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(proposer)?;
+    require_writable(proposal_state)?;
+
+    for accounts in [multisig, proposal_state] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let proposal_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let proposal_bump = data[8];
+    let extension = u64::from_le_bytes(data[9..17].try_into().unwrap());
+
+    // The new expiry must be strictly later than the current one
+    if extension == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let proposal_data = ProposalState::from_account_info(proposal_state)?;
+
+    let proposal_seed = [
+        b"proposal".as_slice(),
+        multisig.key().as_slice(),
+        &proposal_id.to_le_bytes(),
+        &[proposal_bump],
+    ];
+    let proposal_pda = pubkey::checked_create_program_address(&proposal_seed, &crate::ID)?;
+
+    if &proposal_pda != proposal_state.key() || proposal_data.proposal_id != proposal_id {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if proposer.key() != &proposal_data.proposer {
+        log!("Error: Only the proposer can extend this proposal");
+        return Err(ProgramError::IncorrectAuthority);
+    }
+
+    match proposal_data.status()? {
+        ProposalStatus::Active => {},
+        _ => return Err(MultisigError::ProposalNotActive.into()),
+    };
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    if proposal_data.is_expired(current_time) {
+        log!("Proposal has expired");
+        return Err(MultisigError::ProposalExpired.into());
+    };
+
+    let new_expiry = proposal_data.expiry.checked_add(extension).ok_or(ProgramError::InvalidInstructionData)?;
+
+    if new_expiry.saturating_sub(proposal_data.created_time) > MAX_PROPOSAL_DURATION {
+        log!("Error: Proposals can't run longer than {} seconds", MAX_PROPOSAL_DURATION);
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    proposal_data.expiry = new_expiry;
+
+    log!("Proposal {} now expires at {}", proposal_id, new_expiry);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_extend_proposal_expiry_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::state::{Multisig, MultisigConfig, VoteState};
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const PROPOSER: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const PROPOSAL_ID: u64 = 1;
+    const CREATED_TIME: u64 = 1_000;
+    const EXPIRY: u64 = 2_000;
+
+    fn proposal_account(proposal_bump: u8) -> Account {
+        let mut data = vec![0u8; ProposalState::BASE_LEN];
+        data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&EXPIRY.to_le_bytes());
+        data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
+        data[offset_of!(ProposalState, bump)] = proposal_bump;
+        data[offset_of!(ProposalState, active_member_count)] = 1;
+        data[offset_of!(ProposalState, active_members)..][..32].copy_from_slice(PROPOSER.as_ref());
+        data[offset_of!(ProposalState, created_time)..][..8].copy_from_slice(&CREATED_TIME.to_le_bytes());
+        data[offset_of!(ProposalState, proposer)..][..32].copy_from_slice(PROPOSER.as_ref());
+        Account::new_data(1 * LAMPORTS_PER_SOL, &data, &ID).unwrap()
+    }
+
+    fn extend_instruction(proposal_state_pda: Pubkey, proposal_bump: u8, extension: u64) -> Instruction {
+        let mut data = vec![10u8]; // Instruction discriminator for extend proposal expiry
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(proposal_bump);
+        data.extend_from_slice(&extension.to_le_bytes());
+
+        Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(PROPOSER, true),      // proposer (signer)
+                AccountMeta::new_readonly(MULTISIG, false),     // multisig
+                AccountMeta::new(proposal_state_pda, false),    // proposal_state
+            ],
+        )
+    }
+
+    #[test]
+    fn test_extend_then_vote_after_original_expiry() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = EXPIRY as i64; // Last second the proposal is open
+
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (vote_state_pda, _) = Pubkey::find_program_address(
+            &[b"vote_state", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, _) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+        let (vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[b"receipt", proposal_state_pda.as_ref(), PROPOSER.as_ref()],
+            &ID,
+        );
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = 1;
+            data[offset_of!(Multisig, members)..][..32].copy_from_slice(PROPOSER.as_ref());
+            data
+        };
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&1u64.to_le_bytes());
+            data
+        };
+
+        let tx_accounts = vec![
+            (PROPOSER, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (proposal_state_pda, proposal_account(proposal_bump)),
+        ];
+
+        let result = mollusk.process_and_validate_instruction(
+            &extend_instruction(proposal_state_pda, proposal_bump, 600),
+            &tx_accounts,
+            &[Check::success()],
+        );
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let expiry = u64::from_le_bytes(proposal_account.data[offset_of!(ProposalState, expiry)..][..8].try_into().unwrap());
+        assert_eq!(expiry, EXPIRY + 600);
+
+        // Past the original expiry, but inside the extended window
+        mollusk.sysvars.clock.unix_timestamp = EXPIRY as i64 + 300;
+
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(1); // Vote choice: For
+        data.push(proposal_bump);
+
+        let vote = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(PROPOSER, true),
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new(vote_state_pda, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(vote_receipt_pda, false),
+            ],
+        );
+
+        let vote_accounts = vec![
+            result.resulting_accounts.iter().find(|(key, _)| key == &PROPOSER).unwrap().clone(),
+            result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().clone(),
+            result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().clone(),
+            (vote_state_pda, Account::new(0, 0, &system_program_id)),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+            (system_program_id, system_account),
+            (vote_receipt_pda, Account::new(0, 0, &system_program_id)),
+        ];
+
+        let result = mollusk.process_and_validate_instruction(&vote, &vote_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+
+        let vote_state_account = &result.resulting_accounts.iter().find(|(key, _)| key == &vote_state_pda).unwrap().1;
+        assert_eq!(vote_state_account.data.len(), VoteState::LEN);
+    }
+
+    #[test]
+    fn test_extend_past_max_duration_is_rejected() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = CREATED_TIME as i64;
+
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+
+        let tx_accounts = vec![
+            (PROPOSER, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (MULTISIG, Account::new(1 * LAMPORTS_PER_SOL, 0, &ID)),
+            (proposal_state_pda, proposal_account(proposal_bump)),
+        ];
+
+        mollusk.process_and_validate_instruction(
+            &extend_instruction(proposal_state_pda, proposal_bump, MAX_PROPOSAL_DURATION),
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
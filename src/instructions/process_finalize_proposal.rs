@@ -0,0 +1,243 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::{apply_projection, common::require_writable, project_outcome},
+    state::{Multisig, MultisigConfig, ProposalState},
+};
+
+/// Recomputes a proposal's outcome from its recorded votes and writes it, so a decided
+/// proposal can be finalized without casting another vote. Anyone can call it. An
+/// undecided proposal past its expiry is Cancelled (a Poll is decided by plurality).
+///
+/// Idempotent: a proposal that is already terminal is left as is and the call succeeds.
+/// A proposal that is still undecided and open fails with `ThresholdNotMet`.
+///
+/// The outcome is counted in the multisig's statistics.
+///
+/// Accounts: [multisig, multisig_config, proposal_state]
+///
+/// Instruction data (after the discriminator): [proposal_id: u64][proposal_bump: u8]
+pub fn process_finalize_proposal_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 9 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, proposal_state, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig)?;
+    require_writable(proposal_state)?;
+
+    for accounts in [multisig, multisig_config, proposal_state] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let proposal_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let proposal_bump = data[8];
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+    let proposal_data = ProposalState::from_account_info(proposal_state)?;
+    let multisig_config_data = &MultisigConfig::load(multisig_config)?;
+
+    let proposal_seed = [
+        b"proposal".as_slice(),
+        multisig.key().as_slice(),
+        &proposal_id.to_le_bytes(),
+        &[proposal_bump],
+    ];
+    let proposal_pda = pubkey::checked_create_program_address(&proposal_seed, &crate::ID)?;
+
+    if &proposal_pda != proposal_state.key() || proposal_data.proposal_id != proposal_id {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let status = proposal_data.status()?;
+
+    if status.is_terminal() {
+        log!("Proposal {} is already final with status {}", proposal_id, status as u8);
+        return Ok(());
+    }
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let required_votes = proposal_data.effective_required_votes(multisig_config_data)?;
+
+    if required_votes == 0 || required_votes > proposal_data.total_weight(multisig_data) {
+        log!("Error: Invalid threshold of {} votes", required_votes);
+        return Err(MultisigError::InvalidThreshold.into());
+    }
+
+    let (projection, winning_option) =
+        project_outcome(proposal_data, multisig_data, multisig_config_data, required_votes, current_time)?;
+
+    if !projection.is_terminal() {
+        log!("Proposal {} is still undecided", proposal_id);
+        return Err(MultisigError::ThresholdNotMet.into());
+    }
+
+    apply_projection(proposal_data, projection, winning_option, current_time)?;
+    multisig_data.record_outcome(projection)?;
+
+    log!("Proposal {} finalized with status {}", proposal_id, projection as u8);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_finalize_proposal_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::state::ProposalStatus;
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const PROPOSAL_ID: u64 = 1;
+
+    /// A two member multisig needing both For votes, and a proposal expiring at 1_000 with
+    /// `for_votes` recorded but its status left Active
+    fn setup(for_votes: u64) -> (Instruction, Vec<(Pubkey, Account)>) {
+        let creator = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let multisig_pda = Pubkey::new_unique();
+
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", multisig_pda.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", multisig_pda.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+
+        let mut multisig = Multisig::zeroed();
+        multisig.version = Multisig::VERSION;
+        multisig.num_members = 2;
+        multisig.members[0] = creator.to_bytes();
+        multisig.members[1] = member.to_bytes();
+
+        let mut config = MultisigConfig::zeroed();
+        config.version = MultisigConfig::VERSION;
+        config.min_threshold = 2;
+        config.bump = config_bump;
+
+        let mut proposal = ProposalState::zeroed();
+        proposal.proposal_id = PROPOSAL_ID;
+        proposal.expiry = 1_000;
+        proposal.set_status(ProposalStatus::Active);
+        proposal.bump = proposal_bump;
+        proposal.active_member_count = 2;
+        proposal.active_members[0] = creator.to_bytes();
+        proposal.active_members[1] = member.to_bytes();
+        proposal.for_votes = for_votes;
+
+        let mut data = vec![24u8]; // Instruction discriminator for finalize proposal
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(proposal_bump);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(multisig_pda, false),
+                AccountMeta::new_readonly(multisig_config_pda, false),
+                AccountMeta::new(proposal_state_pda, false),
+            ],
+        );
+
+        let accounts = vec![
+            (multisig_pda, Account::new_data(LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (proposal_state_pda, Account::new_data(LAMPORTS_PER_SOL, bytemuck::bytes_of(&proposal), &ID).unwrap()),
+        ];
+
+        (instruction, accounts)
+    }
+
+    fn status_of(instruction: &Instruction, accounts: &[(Pubkey, Account)]) -> u8 {
+        let proposal_account = &accounts.iter().find(|(key, _)| key == &instruction.accounts[2].pubkey).unwrap().1;
+        proposal_account.data[offset_of!(ProposalState, result)]
+    }
+
+    #[test]
+    fn test_finalize_decides_threshold_reached_proposal_without_a_vote() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 500;
+
+        let (instruction, accounts) = setup(2);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]);
+        assert_eq!(status_of(&instruction, &result.resulting_accounts), ProposalStatus::Succeeded as u8);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &instruction.accounts[2].pubkey).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data);
+        assert_eq!(proposal.succeeded_at, 500);
+
+        // Finalizing again leaves the decided proposal untouched
+        let again = mollusk.process_and_validate_instruction(&instruction, &result.resulting_accounts, &[Check::success()]);
+        assert_eq!(again.resulting_accounts, result.resulting_accounts);
+    }
+
+    #[test]
+    fn test_finalize_rejects_undecided_open_proposal() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 500;
+
+        let (instruction, accounts) = setup(1);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::ThresholdNotMet as u32))],
+        );
+    }
+
+    #[test]
+    fn test_finalize_cancels_undecided_expired_proposal() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_001;
+
+        let (instruction, accounts) = setup(1);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]);
+        assert_eq!(status_of(&instruction, &result.resulting_accounts), ProposalStatus::Cancelled as u8);
+    }
+}
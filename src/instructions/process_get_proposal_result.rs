@@ -0,0 +1,264 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::project_outcome,
+    state::{Multisig, MultisigConfig, ProposalState},
+};
+
+/// Read-only view of a proposal's tally. Logs the current For, Against and Abstain
+/// tallies and the status they project to, so clients can read them from simulation
+/// logs without casting a vote. Polls log each option's tally and the projected winner
+/// instead. The projection is the one a vote would apply, honoring unanimity and
+/// failing once approval is unreachable. No account is modified.
+///
+/// Accounts: [multisig, multisig_config, proposal_state]
+///
+/// Instruction data (after the discriminator): [proposal_id: u64][proposal_bump: u8]
+pub fn process_get_proposal_result_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 9 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, proposal_state, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    for accounts in [multisig, multisig_config, proposal_state] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let proposal_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let proposal_bump = data[8];
+
+    let multisig_data = Multisig::from_account_info(multisig)?;
+    let proposal_data = ProposalState::from_account_info(proposal_state)?;
+    let multisig_config_data = &MultisigConfig::load(multisig_config)?;
+
+    let proposal_seed = [
+        b"proposal".as_slice(),
+        multisig.key().as_slice(),
+        &proposal_id.to_le_bytes(),
+        &[proposal_bump],
+    ];
+    let proposal_pda = pubkey::checked_create_program_address(&proposal_seed, &crate::ID)?;
+
+    if &proposal_pda != proposal_state.key() || proposal_data.proposal_id != proposal_id {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    let required_votes = proposal_data.effective_required_votes(multisig_config_data)?;
+
+    let (projection, winning_option) =
+        project_outcome(proposal_data, multisig_data, multisig_config_data, required_votes, current_time)?;
+
+    if proposal_data.is_poll() {
+        for (i, votes) in proposal_data.poll_options().iter().enumerate() {
+            log!("Proposal {} option {}: {}", proposal_id, i + 1, *votes);
+        }
+        log!(
+            "Proposal {} poll: Required: {}, Status: {}, Winning option: {}",
+            proposal_id,
+            required_votes,
+            projection as u8,
+            winning_option
+        );
+
+        return Ok(());
+    }
+
+    log!(
+        "Proposal {} tally: For: {}, Against: {}, Abstain: {}, Required: {}, Status: {}",
+        proposal_id,
+        proposal_data.for_votes,
+        proposal_data.against_votes,
+        proposal_data.abstain_votes,
+        required_votes,
+        projection as u8
+    );
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_get_proposal_result_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::state::ProposalStatus;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const PROPOSAL_ID: u64 = 1;
+
+    fn get_proposal_result() -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = 2;
+            let members_offset = offset_of!(Multisig, members);
+            for i in 0..2 {
+                data[members_offset + i * 32..][..32].copy_from_slice(&[i as u8 + 1; 32]);
+            }
+            data
+        };
+
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&2u64.to_le_bytes());
+            data[offset_of!(MultisigConfig, bump)] = config_bump;
+            data
+        };
+
+        let proposal_data = {
+            let mut data = vec![0u8; ProposalState::BASE_LEN];
+            data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&PROPOSAL_ID.to_le_bytes());
+            data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&u64::MAX.to_le_bytes());
+            data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
+            data[offset_of!(ProposalState, bump)] = proposal_bump;
+            data[offset_of!(ProposalState, for_votes)..][..8].copy_from_slice(&1u64.to_le_bytes());
+            data
+        };
+
+        let mut data = vec![15u8]; // Instruction discriminator for get proposal result
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(proposal_bump);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(MULTISIG, false),             // multisig
+                AccountMeta::new_readonly(multisig_config_pda, false),  // multisig_config
+                AccountMeta::new_readonly(proposal_state_pda, false),   // proposal_state
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+            (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &proposal_data, &ID).unwrap()),
+        ];
+
+        (instruction, tx_accounts, proposal_state_pda)
+    }
+
+    #[test]
+    fn test_get_proposal_result_leaves_proposal_unchanged() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, proposal_state_pda) = get_proposal_result();
+        let proposal_before = tx_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1.data.clone();
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[
+                Check::success(),
+                Check::account(&proposal_state_pda).data(&proposal_before).build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_get_proposal_result_projects_unanimous_mode() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // An Against vote fails a unanimous proposal at once, although the raw tally with a
+        // threshold of 2 would still project Active
+        let (instruction, mut tx_accounts, proposal_state_pda) = get_proposal_result();
+        let config = &mut tx_accounts[1].1;
+        config.data[offset_of!(MultisigConfig, require_unanimous)] = 1;
+        let proposal = &mut tx_accounts.iter_mut().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        proposal.data[offset_of!(ProposalState, against_votes)..][..8].copy_from_slice(&1u64.to_le_bytes());
+        let proposal_before = proposal.data.clone();
+
+        // The projection is only logged, so the proposal itself stays Active
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[
+                Check::success(),
+                Check::account(&proposal_state_pda).data(&proposal_before).build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_get_proposal_result_rejects_foreign_proposal_account() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, mut tx_accounts, proposal_state_pda) = get_proposal_result();
+        let proposal = &mut tx_accounts.iter_mut().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        proposal.owner = Pubkey::new_unique();
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::IncorrectProgramId)],
+        );
+    }
+
+    #[test]
+    fn test_get_proposal_result_rejects_unknown_status_byte() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, mut tx_accounts, proposal_state_pda) = get_proposal_result();
+        let proposal = &mut tx_accounts.iter_mut().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        proposal.data[offset_of!(ProposalState, result)] = 0xee;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+}
@@ -0,0 +1,419 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    error::MultisigError,
+    instructions::common::require_signer,
+    state::{Multisig, MultisigConfig, ThresholdKind},
+};
+
+/// Creates the `MultisigConfig` PDA for an existing multisig, so a config can be set up
+/// for a multisig that was created without one. Only the multisig's creator may do this,
+/// and an already initialized config is never overwritten. `config_bump` must be the
+/// canonical bump, so no second config can exist beside it. The creator becomes the
+/// config authority.
+///
+/// Accounts: [creator, multisig, multisig_config, system_program]
+///
+/// Instruction data (after the discriminator):
+/// [config_bump: u8][min_threshold: u64][quorum: u64][threshold_kind: u8][threshold_value: u8][veto_authority: Pubkey]
+/// followed optionally by [execution_delay: u64], then [fail_when_unreachable: u8] and then
+/// [require_unanimous: u8][unanimous_abstain_fails: u8], then [defeat_threshold: u64] and
+/// then [allow_vote_change: u8] and then [governed_by_proposals: u8]; all default to 0
+pub fn process_initialize_config_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if !matches!(data.len(), 51 | 59 | 60 | 62 | 70 | 71 | 72) {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [creator, multisig, multisig_config, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(creator)?;
+
+    if multisig.owner() != &crate::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if multisig_config.owner() == &crate::ID {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let config_bump = data[0];
+    let min_threshold = u64::from_le_bytes(data[1..9].try_into().unwrap());
+    let quorum = u64::from_le_bytes(data[9..17].try_into().unwrap());
+    let threshold_kind = data[17];
+    let threshold_value = data[18];
+    let veto_authority: Pubkey = data[19..51].try_into().unwrap();
+    let execution_delay = data.get(51..59).map_or(0, |delay| u64::from_le_bytes(delay.try_into().unwrap()));
+    let fail_when_unreachable = data.get(59).copied().unwrap_or(0);
+    let require_unanimous = data.get(60).copied().unwrap_or(0);
+    let unanimous_abstain_fails = data.get(61).copied().unwrap_or(0);
+    let defeat_threshold = data.get(62..70).map_or(0, |threshold| u64::from_le_bytes(threshold.try_into().unwrap()));
+    let allow_vote_change = data.get(70).copied().unwrap_or(0);
+    let governed_by_proposals = data.get(71).copied().unwrap_or(0);
+
+    if fail_when_unreachable > 1 || require_unanimous > 1 || unanimous_abstain_fails > 1 || allow_vote_change > 1 || governed_by_proposals > 1 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let multisig_data = Multisig::from_account_info(multisig)?;
+
+    if creator.key() != &multisig_data.creator {
+        log!("Error: Only the multisig creator can initialize its config");
+        return Err(ProgramError::IncorrectAuthority);
+    }
+
+    let (eligible_members, eligible_weight) = multisig_data.eligible_voters();
+
+    // Votes carry the voters' weights, so a quorum above their sum could never be met
+    if quorum > eligible_weight {
+        log!("Error: Quorum can't exceed the members' combined weight of {}", eligible_weight);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // All zeros leaves the veto disabled
+    if veto_authority != Pubkey::default() && !multisig_data.is_member(&veto_authority) {
+        log!("Error: The veto authority must be a member");
+        return Err(MultisigError::NotAMember.into());
+    }
+
+    match ThresholdKind::try_from(&threshold_kind).map_err(|_| ProgramError::InvalidInstructionData)? {
+        ThresholdKind::Absolute => {
+            if min_threshold == 0 || min_threshold > eligible_members {
+                log!("Error: Threshold must be between 1 and the number of members");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+        ThresholdKind::Percentage => {
+            if threshold_value == 0 || threshold_value > 100 {
+                log!("Error: Threshold percentage must be between 1 and 100");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+    };
+
+    // Only the canonical bump is accepted, so each multisig has exactly one config address
+    // and, since it must not be initialized yet, exactly one config
+    let (config_pda, canonical_bump) = pubkey::find_program_address(
+        &[b"multisig_config".as_slice(), multisig.key().as_slice()],
+        &crate::ID,
+    );
+
+    if &config_pda != multisig_config.key() || config_bump != canonical_bump {
+        log!("Error: The config must be at the canonical multisig_config address");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    log!("Creating MultisigConfig Account");
+
+    let config_bump_bytes = [config_bump];
+    let config_signer_seeds = [
+        Seed::from(b"multisig_config"),
+        Seed::from(multisig.key()),
+        Seed::from(&config_bump_bytes),
+    ];
+
+    CreateAccount {
+        from: creator,
+        to: multisig_config,
+        lamports: Rent::get()?.minimum_balance(MultisigConfig::LEN),
+        space: MultisigConfig::LEN as u64,
+        owner: &crate::ID,
+    }.invoke_signed(&[Signer::from(&config_signer_seeds)])?;
+
+    let multisig_config_data = MultisigConfig::initialize(multisig_config)?;
+    multisig_config_data.min_threshold = min_threshold;
+    multisig_config_data.quorum = quorum;
+    multisig_config_data.execution_delay = execution_delay;
+    multisig_config_data.fail_when_unreachable = fail_when_unreachable;
+    multisig_config_data.require_unanimous = require_unanimous;
+    multisig_config_data.unanimous_abstain_fails = unanimous_abstain_fails;
+    multisig_config_data.defeat_threshold = defeat_threshold;
+    multisig_config_data.allow_vote_change = allow_vote_change;
+    multisig_config_data.governed_by_proposals = governed_by_proposals;
+    multisig_config_data.threshold_kind = threshold_kind;
+    multisig_config_data.threshold_value = threshold_value;
+    multisig_config_data.veto_authority = veto_authority;
+    multisig_config_data.config_authority = *creator.key();
+    multisig_config_data.bump = config_bump;
+
+    log!("Config initialized with threshold {}", min_threshold);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_initialize_config_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::state::{ProposalState, ProposalStatus, VoteReceipt, VoteState};
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const CREATOR: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const PROPOSAL_ID: u64 = 1;
+
+    fn initialize_config(signer: Pubkey, members: &[Pubkey], min_threshold: u64) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, creator)..][..32].copy_from_slice(CREATOR.as_ref());
+            data[offset_of!(Multisig, num_members)] = members.len() as u8;
+            let members_offset = offset_of!(Multisig, members);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data
+        };
+
+        let mut data = vec![14u8]; // Instruction discriminator for initialize config
+        data.push(config_bump);
+        data.extend_from_slice(&min_threshold.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes()); // quorum
+        data.push(ThresholdKind::Absolute as u8);
+        data.push(0); // threshold_value
+        data.extend_from_slice(&[0u8; 32]); // veto disabled
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(signer, true),                       // creator (signer)
+                AccountMeta::new_readonly(MULTISIG, false),           // multisig
+                AccountMeta::new(multisig_config_pda, false),         // multisig_config
+                AccountMeta::new_readonly(system_program_id, false),  // system_program
+            ],
+        );
+
+        let tx_accounts = vec![
+            (signer, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new(0, 0, &system_program_id)),
+            (system_program_id, system_account),
+        ];
+
+        (instruction, tx_accounts, multisig_config_pda)
+    }
+
+    #[test]
+    fn test_initialize_config_then_vote() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [CREATOR, Pubkey::new_unique()];
+        let (instruction, tx_accounts, multisig_config_pda) = initialize_config(CREATOR, &members, 1);
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[
+                Check::success(),
+                Check::account(&multisig_config_pda).owner(&ID).space(MultisigConfig::LEN).build(),
+            ],
+        );
+
+        // Vote For on an Active proposal; the vote reads its threshold of 1 from the new config
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (vote_state_pda, _) = Pubkey::find_program_address(
+            &[b"vote_state", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[b"receipt", proposal_state_pda.as_ref(), members[1].as_ref()],
+            &ID,
+        );
+
+        let proposal_data = {
+            let mut data = vec![0u8; ProposalState::BASE_LEN];
+            data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&PROPOSAL_ID.to_le_bytes());
+            data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&u64::MAX.to_le_bytes());
+            data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
+            data[offset_of!(ProposalState, bump)] = proposal_bump;
+            data[offset_of!(ProposalState, active_member_count)] = members.len() as u8;
+            let active_members_offset = offset_of!(ProposalState, active_members);
+            for (i, member) in members.iter().enumerate() {
+                data[active_members_offset + i * 32..active_members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data
+        };
+
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(1); // Vote choice: For
+        data.push(proposal_bump);
+
+        let vote = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(members[1], true),
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new(vote_state_pda, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(vote_receipt_pda, false),
+            ],
+        );
+
+        let vote_accounts = vec![
+            (members[1], Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().clone(),
+            (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &proposal_data, &ID).unwrap()),
+            (vote_state_pda, Account::new(0, 0, &system_program_id)),
+            result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().clone(),
+            (system_program_id, system_account),
+            (vote_receipt_pda, Account::new(0, 0, &system_program_id)),
+        ];
+
+        let result = mollusk.process_and_validate_instruction(
+            &vote,
+            &vote_accounts,
+            &[
+                Check::success(),
+                Check::account(&vote_state_pda).space(VoteState::LEN).build(),
+                Check::account(&vote_receipt_pda).owner(&ID).space(VoteReceipt::LEN).build(),
+            ],
+        );
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+    }
+
+    #[test]
+    fn test_initialize_config_rejects_unreachable_quorum() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // Two members of weight 1 can cast at most 2 votes
+        let (mut instruction, tx_accounts, _) = initialize_config(CREATOR, &[CREATOR, Pubkey::new_unique()], 1);
+        instruction.data[10..18].copy_from_slice(&3u64.to_le_bytes());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_initialize_config_rejects_non_member_veto_authority() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (mut instruction, tx_accounts, _) = initialize_config(CREATOR, &[CREATOR, Pubkey::new_unique()], 1);
+        instruction.data[20..52].copy_from_slice(Pubkey::new_unique().as_ref());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::NotAMember as u32))],
+        );
+    }
+
+    #[test]
+    fn test_initialize_config_bounds_threshold_by_members_root() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // The root holds 500 voters though only two members are listed inline
+        for (min_threshold, check) in [(300, Check::success()), (501, Check::err(ProgramError::InvalidInstructionData))] {
+            let (instruction, mut tx_accounts, _) = initialize_config(CREATOR, &[CREATOR, Pubkey::new_unique()], min_threshold);
+            let multisig = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+            multisig.data[offset_of!(Multisig, members_root)..][..32].copy_from_slice(&[7u8; 32]);
+            multisig.data[offset_of!(Multisig, root_member_count)..][..8].copy_from_slice(&500u64.to_le_bytes());
+
+            mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[check]);
+        }
+    }
+
+    #[test]
+    fn test_initialize_config_rejects_non_creator() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let signer = Pubkey::new_unique();
+        let (instruction, tx_accounts, _) = initialize_config(signer, &[CREATOR, signer], 1);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::IncorrectAuthority)],
+        );
+    }
+
+    #[test]
+    fn test_initialize_config_rejects_non_canonical_bump() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (mut instruction, mut tx_accounts, multisig_config_pda) = initialize_config(CREATOR, &[CREATOR], 1);
+
+        // A valid program address for the same seeds at a lower bump
+        let (alias_pda, alias_bump) = (0..instruction.data[1])
+            .rev()
+            .find_map(|bump| {
+                Pubkey::create_program_address(&[b"multisig_config", MULTISIG.as_ref(), &[bump]], &ID)
+                    .ok()
+                    .map(|pda| (pda, bump))
+            })
+            .unwrap();
+
+        instruction.data[1] = alias_bump;
+        instruction.accounts[2].pubkey = alias_pda;
+        tx_accounts.iter_mut().find(|(key, _)| key == &multisig_config_pda).unwrap().0 = alias_pda;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidSeeds)],
+        );
+    }
+
+    #[test]
+    fn test_initialize_config_rejects_reinitialization() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, mut tx_accounts, multisig_config_pda) = initialize_config(CREATOR, &[CREATOR], 1);
+        let config = &mut tx_accounts.iter_mut().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        *config = Account::new(1 * LAMPORTS_PER_SOL, MultisigConfig::LEN, &ID);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::AccountAlreadyInitialized)],
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::common::{require_signer, require_writable},
+    state::{Multisig, MultisigConfig},
+};
+
+/// Removes a member from the multisig, shifting the remaining members down.
+///
+/// The multisig PDA itself must sign, which only happens when the program invokes
+/// this instruction on behalf of an approved proposal. Removal is safe while proposals
+/// are Active: proposals tally votes by position in their own `active_members`
+/// snapshot, not by position in `Multisig.members`, so shifting members here does not
+/// remap any in-flight vote. A removed member can no longer vote since the vote
+/// instruction also requires current membership, and members that delegated to it get
+/// their own vote back.
+///
+/// Instruction data (after the discriminator): [member: Pubkey]
+pub fn process_remove_member_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 32 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(multisig)?;
+
+    for accounts in [multisig, multisig_config] {
+        require_writable(accounts)?;
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let member: Pubkey = data[0..32].try_into().unwrap();
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+    let multisig_config_data = MultisigConfig::from_account_info_mut(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let num_members = multisig_data.num_members as usize;
+
+    let member_index = multisig_data
+        .member_index(&member)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    if num_members == 1 {
+        log!("Error: Cannot remove the last member");
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    multisig_data.members.copy_within(member_index + 1..num_members, member_index);
+    multisig_data.members[num_members - 1] = Pubkey::default();
+    multisig_data.weights.copy_within(member_index + 1..num_members, member_index);
+    multisig_data.weights[num_members - 1] = 0;
+    multisig_data.delegations.copy_within(member_index + 1..num_members, member_index);
+    multisig_data.delegations[num_members - 1] = Pubkey::default();
+    multisig_data.roles.copy_within(member_index + 1..num_members, member_index);
+    multisig_data.roles[num_members - 1] = 0;
+    multisig_data.participation_count.copy_within(member_index + 1..num_members, member_index);
+    multisig_data.participation_count[num_members - 1] = 0;
+    multisig_data.num_members -= 1;
+
+    // Members that delegated to the removed member vote for themselves again
+    let remaining = multisig_data.num_members as usize;
+    for delegate in multisig_data.delegations[..remaining].iter_mut().filter(|delegate| **delegate == member) {
+        *delegate = Pubkey::default();
+    }
+
+    // A veto authority has to be a member, so removing it disables the veto
+    if multisig_config_data.veto_authority == member {
+        multisig_config_data.veto_authority = Pubkey::default();
+        log!("Veto disabled");
+    }
+
+    // Every requirement the remaining voters can no longer meet is lowered to what they
+    // can, so removing a member never leaves proposals unpassable. With a members root the
+    // root's voters vote, so removing an inline member lowers nothing.
+    let (remaining_members, remaining_weight) = multisig_data.eligible_voters();
+
+    if multisig_config_data.min_threshold > remaining_members {
+        multisig_config_data.min_threshold = remaining_members;
+        log!("Threshold lowered to {}", multisig_config_data.min_threshold);
+    }
+
+    for type_threshold in multisig_config_data.type_thresholds.iter_mut().filter(|threshold| **threshold > remaining_members) {
+        *type_threshold = remaining_members;
+    }
+
+    if multisig_config_data.defeat_threshold > remaining_members {
+        multisig_config_data.defeat_threshold = remaining_members;
+        log!("Defeat threshold lowered to {}", multisig_config_data.defeat_threshold);
+    }
+
+    if multisig_config_data.quorum > remaining_weight {
+        multisig_config_data.quorum = remaining_weight;
+        log!("Quorum lowered to {}", multisig_config_data.quorum);
+    }
+
+    log!("Member removed, multisig now has {} members", multisig_data.num_members);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_remove_member_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+
+    fn remove_member(members: &[Pubkey], min_threshold: u64, member: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = members.len() as u8;
+            let members_offset = offset_of!(Multisig, members);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data
+        };
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&min_threshold.to_le_bytes());
+            data[offset_of!(MultisigConfig, bump)] = config_bump;
+            data
+        };
+
+        let mut data = vec![7u8]; // Instruction discriminator for remove member
+        data.extend_from_slice(member.as_ref());
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(MULTISIG, true),             // multisig (signer via CPI)
+                AccountMeta::new(multisig_config_pda, false), // multisig_config
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+        ];
+
+        (instruction, tx_accounts, multisig_config_pda)
+    }
+
+    fn stored_members(data: &[u8]) -> Vec<Pubkey> {
+        let num_members = data[offset_of!(Multisig, num_members)] as usize;
+        let members_offset = offset_of!(Multisig, members);
+        (0..num_members)
+            .map(|i| Pubkey::try_from(&data[members_offset + i * 32..members_offset + (i + 1) * 32]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_remove_first_member() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = remove_member(&members, 2, members[0]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let multisig = &result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        assert_eq!(stored_members(&multisig.data), vec![members[1], members[2]]);
+
+        // The vacated trailing slot is cleared
+        let trailing_slot = offset_of!(Multisig, members) + 2 * 32;
+        assert_eq!(&multisig.data[trailing_slot..trailing_slot + 32], &[0u8; 32]);
+    }
+
+    #[test]
+    fn test_remove_middle_member_lowers_threshold() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, multisig_config_pda) = remove_member(&members, 3, members[1]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let multisig = &result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        assert_eq!(stored_members(&multisig.data), vec![members[0], members[2]]);
+
+        let config = &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let threshold_offset = offset_of!(MultisigConfig, min_threshold);
+        let min_threshold = u64::from_le_bytes(config.data[threshold_offset..threshold_offset + 8].try_into().unwrap());
+        assert_eq!(min_threshold, 2);
+    }
+
+    #[test]
+    fn test_remove_member_lowers_quorum_and_other_thresholds() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, multisig_config_pda) = remove_member(&members, 2, members[2]);
+
+        let config = &mut tx_accounts.iter_mut().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        config.data[offset_of!(MultisigConfig, quorum)..][..8].copy_from_slice(&3u64.to_le_bytes());
+        config.data[offset_of!(MultisigConfig, defeat_threshold)..][..8].copy_from_slice(&3u64.to_le_bytes());
+        config.data[offset_of!(MultisigConfig, type_thresholds)..][..8].copy_from_slice(&3u64.to_le_bytes());
+        config.data[offset_of!(MultisigConfig, type_thresholds) + 8..][..8].copy_from_slice(&1u64.to_le_bytes());
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let config_account = &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let config: MultisigConfig = bytemuck::pod_read_unaligned(&config_account.data);
+        assert_eq!((config.min_threshold, config.quorum, config.defeat_threshold), (2, 2, 2));
+        // Type thresholds the remaining members can still meet are kept
+        assert_eq!(config.type_thresholds[..2], [2, 1]);
+    }
+
+    #[test]
+    fn test_remove_member_keeps_thresholds_sized_to_members_root() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, multisig_config_pda) = remove_member(&members, 300, members[2]);
+
+        let multisig = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        multisig.data[offset_of!(Multisig, members_root)..][..32].copy_from_slice(&[7u8; 32]);
+        multisig.data[offset_of!(Multisig, root_member_count)..][..8].copy_from_slice(&500u64.to_le_bytes());
+        let config = &mut tx_accounts.iter_mut().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        config.data[offset_of!(MultisigConfig, quorum)..][..8].copy_from_slice(&400u64.to_le_bytes());
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let config_account = &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let config: MultisigConfig = bytemuck::pod_read_unaligned(&config_account.data);
+        assert_eq!((config.min_threshold, config.quorum), (300, 400));
+    }
+
+    #[test]
+    fn test_remove_member_clears_delegations_to_it() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, _) = remove_member(&members, 2, members[1]);
+
+        // Both remaining members delegated to the member being removed
+        let multisig = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        let delegations_offset = offset_of!(Multisig, delegations);
+        multisig.data[delegations_offset..][..32].copy_from_slice(members[1].as_ref());
+        multisig.data[delegations_offset + 2 * 32..][..32].copy_from_slice(members[1].as_ref());
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let multisig_account = &result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        let multisig: Multisig = bytemuck::pod_read_unaligned(&multisig_account.data);
+        assert_eq!(stored_members(&multisig_account.data), vec![members[0], members[2]]);
+        assert!(multisig.delegations.iter().all(|delegate| *delegate == Pubkey::default().to_bytes()));
+    }
+
+    #[test]
+    fn test_remove_veto_authority_disables_veto() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, multisig_config_pda) = remove_member(&members, 2, members[0]);
+
+        let config = &mut tx_accounts.iter_mut().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        config.data[offset_of!(MultisigConfig, veto_authority)..][..32].copy_from_slice(members[0].as_ref());
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let config_account = &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let config: MultisigConfig = bytemuck::pod_read_unaligned(&config_account.data);
+        assert_eq!(config.veto_authority, Pubkey::default().to_bytes());
+    }
+
+    #[test]
+    fn test_remove_nonexistent_member() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = remove_member(&members, 1, Pubkey::new_unique());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::common::{require_signer, require_writable},
+    state::{is_empty_slot, Multisig, MultisigConfig},
+};
+
+/// Swaps one member for another in place, for rotating a member's key without a remove
+/// and an add. The new key takes the old one's index, weight and roles, so the member
+/// count and threshold never change. If the old key was the veto authority, the new key
+/// becomes it.
+///
+/// The multisig PDA itself must sign, which only happens when the program invokes this
+/// instruction on behalf of an approved proposal. Votes already cast stay in the
+/// proposals' `active_members` snapshots under the old key; the new key can only vote on
+/// proposals created after the swap.
+///
+/// Accounts: [multisig, multisig_config]
+///
+/// Instruction data (after the discriminator): [old_member: Pubkey][new_member: Pubkey]
+pub fn process_replace_member_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 64 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(multisig)?;
+
+    for accounts in [multisig, multisig_config] {
+        require_writable(accounts)?;
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let old_member: Pubkey = data[0..32].try_into().unwrap();
+    let new_member: Pubkey = data[32..64].try_into().unwrap();
+
+    if is_empty_slot(&new_member) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+    let multisig_config_data = MultisigConfig::from_account_info_mut(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let member_index = multisig_data
+        .member_index(&old_member)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    if multisig_data.is_member(&new_member) {
+        log!("Error: New member is already a member");
+        return Err(MultisigError::DuplicateMember.into());
+    }
+
+    multisig_data.replace_member(member_index, new_member);
+
+    if multisig_config_data.veto_authority == old_member {
+        multisig_config_data.veto_authority = new_member;
+        log!("Veto authority moved to the new member");
+    }
+
+    log!("Member at index {} replaced", member_index);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_replace_member_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+
+    fn replace_member(members: &[Pubkey], old_member: Pubkey, new_member: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>) {
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = members.len() as u8;
+            let members_offset = offset_of!(Multisig, members);
+            let weights_offset = offset_of!(Multisig, weights);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+                data[weights_offset + i * 8..][..8].copy_from_slice(&(i as u64 + 1).to_le_bytes());
+            }
+            data
+        };
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&2u64.to_le_bytes());
+            data[offset_of!(MultisigConfig, bump)] = config_bump;
+            data
+        };
+
+        let mut data = vec![28u8]; // Instruction discriminator for replace member
+        data.extend_from_slice(old_member.as_ref());
+        data.extend_from_slice(new_member.as_ref());
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(MULTISIG, true),                      // multisig (signer via CPI)
+                AccountMeta::new(multisig_config_pda, false),          // multisig_config
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+        ];
+
+        (instruction, tx_accounts)
+    }
+
+    fn config_of(accounts: &[(Pubkey, Account)]) -> MultisigConfig {
+        let (multisig_config_pda, _) = Pubkey::find_program_address(&[b"multisig_config", MULTISIG.as_ref()], &ID);
+        let config = &accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        bytemuck::pod_read_unaligned(&config.data)
+    }
+
+    #[test]
+    fn test_replace_member_keeps_index_and_weight() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let new_member = Pubkey::new_unique();
+        let (instruction, tx_accounts) = replace_member(&members, members[1], new_member);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let multisig_account = &result.resulting_accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        let multisig: Multisig = bytemuck::pod_read_unaligned(&multisig_account.data);
+        assert_eq!(multisig.num_members, 3);
+        assert_eq!(multisig.members[..3], [members[0].to_bytes(), new_member.to_bytes(), members[2].to_bytes()]);
+        assert_eq!(multisig.weight_of(&new_member.to_bytes()), 2);
+        assert!(!multisig.is_member(&members[1].to_bytes()));
+    }
+
+    #[test]
+    fn test_replace_member_moves_veto_authority() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let new_member = Pubkey::new_unique();
+        let (instruction, mut tx_accounts) = replace_member(&members, members[0], new_member);
+
+        let config = &mut tx_accounts[1].1;
+        config.data[offset_of!(MultisigConfig, veto_authority)..][..32].copy_from_slice(members[0].as_ref());
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        assert_eq!(config_of(&result.resulting_accounts).veto_authority, new_member.to_bytes());
+    }
+
+    #[test]
+    fn test_replace_member_rejects_existing_new_member() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts) = replace_member(&members, members[0], members[1]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::DuplicateMember as u32))],
+        );
+    }
+
+    #[test]
+    fn test_replace_unknown_member_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts) = replace_member(&members, Pubkey::new_unique(), Pubkey::new_unique());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+}
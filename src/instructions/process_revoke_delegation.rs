@@ -0,0 +1,345 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::{close_account, common::{require_signer, require_writable}},
+    state::{Multisig, ProposalState, ProposalStatus, VoteReceipt, VoteState},
+};
+
+/// Revokes the delegation the signer gave, and decides what happens to votes the delegate
+/// already cast for them on proposals still being voted on. With `retract_votes` 0 those
+/// votes stand. With 1, the vote on each proposal passed in is taken back out of its
+/// tallies and its receipt closed, rent to the member, so the member can vote again.
+///
+/// Only the delegator can revoke: the delegate holds no delegation of their own to revoke.
+/// Votes are only retracted from Active proposals, so a decided outcome is never reopened,
+/// and a vote the member cast themselves is left alone.
+///
+/// Accounts: [member, multisig, ..(proposal_state, vote_state, vote_receipt) per proposal to retract from]
+///
+/// Instruction data (after the discriminator): [retract_votes: u8]
+pub fn process_revoke_delegation_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    let [retract_votes] = data else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    if *retract_votes > 1 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [member, multisig, proposals @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Each retraction takes exactly three accounts, and none are expected when votes stand
+    if proposals.len() % 3 != 0 || (*retract_votes == 0 && !proposals.is_empty()) {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    require_signer(member)?;
+    require_writable(multisig)?;
+
+    if multisig.owner() != &crate::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+    let member_index = multisig_data
+        .member_index(member.key())
+        .ok_or(MultisigError::NotAMember)?;
+
+    if multisig_data.delegations[member_index] == Pubkey::default() {
+        log!("Error: Member has no delegation to revoke");
+        return Err(MultisigError::NotDelegated.into());
+    }
+
+    multisig_data.delegations[member_index] = Pubkey::default();
+
+    if !proposals.is_empty() {
+        // Closed receipts refund their rent to the member
+        require_writable(member)?;
+    }
+
+    for accounts in proposals.chunks_exact(3) {
+        let [proposal_state, vote_state, vote_receipt] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        retract_delegated_vote(member, multisig, multisig_data, proposal_state, vote_state, vote_receipt)?;
+    }
+
+    log!("Delegation revoked");
+
+    Ok(())
+}
+
+/// Takes the delegated vote recorded in `vote_receipt` back out of an Active proposal
+fn retract_delegated_vote(
+    member: &AccountInfo,
+    multisig: &AccountInfo,
+    multisig_data: &Multisig,
+    proposal_state: &AccountInfo,
+    vote_state: &AccountInfo,
+    vote_receipt: &AccountInfo,
+) -> ProgramResult {
+    for account in [proposal_state, vote_state, vote_receipt] {
+        require_writable(account)?;
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let proposal_data = ProposalState::from_account_info(proposal_state)?;
+    let proposal_id = proposal_data.proposal_id.to_le_bytes();
+
+    let proposal_seed = [
+        b"proposal".as_slice(),
+        multisig.key().as_slice(),
+        proposal_id.as_slice(),
+        &[proposal_data.bump],
+    ];
+    if &pubkey::checked_create_program_address(&proposal_seed, &crate::ID)? != proposal_state.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if !matches!(proposal_data.status()?, ProposalStatus::Active) {
+        log!("Error: Votes can only be retracted from an active proposal");
+        return Err(MultisigError::ProposalNotActive.into());
+    }
+
+    let vote_state_data = VoteState::from_account_info(vote_state)?;
+
+    let vote_state_seed = [
+        b"vote_state".as_slice(),
+        multisig.key().as_slice(),
+        proposal_id.as_slice(),
+        &[vote_state_data.bump],
+    ];
+    if &pubkey::checked_create_program_address(&vote_state_seed, &crate::ID)? != vote_state.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let receipt_data = VoteReceipt::from_account_info(vote_receipt)?;
+
+    let receipt_seed = [
+        b"receipt".as_slice(),
+        proposal_state.key().as_slice(),
+        member.key().as_slice(),
+        &[receipt_data.bump],
+    ];
+    if &pubkey::checked_create_program_address(&receipt_seed, &crate::ID)? != vote_receipt.key()
+        || &receipt_data.voter != member.key()
+    {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if vote_state_data.proposal_nonce != proposal_data.nonce || receipt_data.proposal_nonce != proposal_data.nonce {
+        return Err(MultisigError::StaleVoteAccount.into());
+    }
+
+    if receipt_data.delegated == 0 {
+        log!("Vote on proposal {} was cast by the member and stands", proposal_data.proposal_id);
+        return Ok(());
+    }
+
+    let weight = proposal_data.weight_of(member.key(), multisig_data);
+    let tally = proposal_data.choice_tally_mut(receipt_data.choice)?;
+    *tally = tally.saturating_sub(weight);
+
+    let active_member_index = proposal_data
+        .active_member_index(member.key())
+        .ok_or(MultisigError::NotAMember)?;
+    vote_state_data.clear_voted(active_member_index)?;
+    vote_state_data.vote_count = vote_state_data.vote_count.saturating_sub(1);
+
+    close_account(vote_receipt, member)?;
+
+    log!("Delegated vote on proposal {} retracted", proposal_data.proposal_id);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_revoke_delegation_instruction {
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::{
+        instruction::{self, proposal_address, receipt_address, vote_state_address},
+        state::{MultisigConfig, VoteChoice},
+    };
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const MEMBERS: [Pubkey; 3] = [
+        Pubkey::new_from_array([0x03; 32]),
+        Pubkey::new_from_array([0x04; 32]),
+        Pubkey::new_from_array([0x05; 32]),
+    ];
+    const PROPOSAL_ID: u64 = 1;
+
+    /// Three members, member 0 having delegated to member 1, and one Active proposal
+    /// needing all three votes
+    fn setup() -> Vec<(Pubkey, Account)> {
+        let (multisig_config_pda, config_bump) = instruction::multisig_config_address(&MULTISIG);
+        let (proposal_state_pda, proposal_bump) = proposal_address(&MULTISIG, PROPOSAL_ID);
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+
+        let mut multisig = Multisig::zeroed();
+        multisig.version = Multisig::VERSION;
+        multisig.num_members = MEMBERS.len() as u8;
+        for (slot, member) in MEMBERS.iter().enumerate() {
+            multisig.members[slot] = member.to_bytes();
+        }
+        multisig.delegations[0] = MEMBERS[1].to_bytes();
+
+        let mut config = MultisigConfig::zeroed();
+        config.version = MultisigConfig::VERSION;
+        config.min_threshold = 3;
+        config.bump = config_bump;
+
+        let mut proposal = ProposalState::zeroed();
+        proposal.proposal_id = PROPOSAL_ID;
+        proposal.expiry = 9999999999;
+        proposal.set_status(ProposalStatus::Active);
+        proposal.bump = proposal_bump;
+        proposal.active_member_count = MEMBERS.len() as u8;
+        for (slot, member) in MEMBERS.iter().enumerate() {
+            proposal.active_members[slot] = member.to_bytes();
+        }
+
+        let mut accounts = vec![
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&proposal), &ID).unwrap()),
+            (vote_state_address(&MULTISIG, PROPOSAL_ID).0, Account::new(0, 0, &system_program_id)),
+            (system_program_id, system_account),
+        ];
+        for member in MEMBERS {
+            accounts.push((member, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)));
+            accounts.push((receipt_address(&proposal_state_pda, &member).0, Account::new(0, 0, &system_program_id)));
+        }
+        accounts
+    }
+
+    fn revoke(signer: Pubkey, retract_from: Option<u64>) -> Instruction {
+        let mut accounts = vec![
+            AccountMeta::new(signer, true),     // member (signer)
+            AccountMeta::new(MULTISIG, false),  // multisig
+        ];
+        if let Some(proposal_id) = retract_from {
+            let (proposal_state_pda, _) = proposal_address(&MULTISIG, proposal_id);
+            accounts.push(AccountMeta::new(proposal_state_pda, false));
+            accounts.push(AccountMeta::new(vote_state_address(&MULTISIG, proposal_id).0, false));
+            accounts.push(AccountMeta::new(receipt_address(&proposal_state_pda, &signer).0, false));
+        }
+
+        Instruction::new_with_bytes(ID, &[23u8, retract_from.is_some() as u8], accounts) // Instruction discriminator for revoke delegation
+    }
+
+    /// Member 1 votes For on member 0's behalf
+    fn delegated_vote() -> Instruction {
+        instruction::delegated_vote(&MEMBERS[1], &MEMBERS[0], 0, &MULTISIG, PROPOSAL_ID, VoteChoice::For as u8, false)
+    }
+
+    fn proposal(accounts: &[(Pubkey, Account)]) -> ProposalState {
+        let proposal_state_pda = proposal_address(&MULTISIG, PROPOSAL_ID).0;
+        let proposal_account = &accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN])
+    }
+
+    #[test]
+    fn test_revoke_before_vote() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let accounts = mollusk.process_and_validate_instruction(&revoke(MEMBERS[0], None), &setup(), &[Check::success()]).resulting_accounts;
+
+        // The former delegate can no longer vote for member 0, who votes directly instead
+        mollusk.process_and_validate_instruction(
+            &delegated_vote(),
+            &accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::NotDelegated as u32))],
+        );
+        let own_vote = instruction::vote(&MEMBERS[0], &MULTISIG, PROPOSAL_ID, VoteChoice::Against as u8);
+        let accounts = mollusk.process_and_validate_instruction(&own_vote, &accounts, &[Check::success()]).resulting_accounts;
+
+        assert_eq!((proposal(&accounts).for_votes, proposal(&accounts).against_votes), (0, 1));
+    }
+
+    #[test]
+    fn test_revoke_after_vote_retracts_the_delegated_vote() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let accounts = mollusk.process_and_validate_instruction(&delegated_vote(), &setup(), &[Check::success()]).resulting_accounts;
+        assert_eq!(proposal(&accounts).for_votes, 1);
+
+        let result = mollusk.process_and_validate_instruction(&revoke(MEMBERS[0], Some(PROPOSAL_ID)), &accounts, &[Check::success()]);
+        let accounts = result.resulting_accounts;
+
+        let proposal_state_pda = proposal_address(&MULTISIG, PROPOSAL_ID).0;
+        let receipt = &accounts.iter().find(|(key, _)| key == &receipt_address(&proposal_state_pda, &MEMBERS[0]).0).unwrap().1;
+        assert_eq!(receipt.lamports, 0);
+        assert!(matches!(proposal(&accounts).status(), Ok(ProposalStatus::Active)));
+        assert_eq!(proposal(&accounts).for_votes, 0);
+
+        let vote_state_account = &accounts.iter().find(|(key, _)| key == &vote_state_address(&MULTISIG, PROPOSAL_ID).0).unwrap().1;
+        let vote_state: VoteState = bytemuck::pod_read_unaligned(&vote_state_account.data);
+        assert_eq!(vote_state.vote_count, 0);
+        assert!(!vote_state.has_voted(0));
+
+        // Member 0 now casts their own vote from scratch
+        let own_vote = instruction::vote(&MEMBERS[0], &MULTISIG, PROPOSAL_ID, VoteChoice::Against as u8);
+        let accounts = mollusk.process_and_validate_instruction(&own_vote, &accounts, &[Check::success()]).resulting_accounts;
+        assert_eq!((proposal(&accounts).for_votes, proposal(&accounts).against_votes), (0, 1));
+    }
+
+    #[test]
+    fn test_revoke_after_vote_lets_the_delegated_vote_stand() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let accounts = mollusk.process_and_validate_instruction(&delegated_vote(), &setup(), &[Check::success()]).resulting_accounts;
+        let accounts = mollusk.process_and_validate_instruction(&revoke(MEMBERS[0], None), &accounts, &[Check::success()]).resulting_accounts;
+
+        assert_eq!(proposal(&accounts).for_votes, 1);
+
+        // The vote is member 0's to change now, but not to cast again
+        let own_vote = instruction::vote(&MEMBERS[0], &MULTISIG, PROPOSAL_ID, VoteChoice::For as u8);
+        mollusk.process_and_validate_instruction(
+            &own_vote,
+            &accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::AlreadyVoted as u32))],
+        );
+    }
+
+    #[test]
+    fn test_delegate_cannot_revoke() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        mollusk.process_and_validate_instruction(
+            &revoke(MEMBERS[1], None),
+            &setup(),
+            &[Check::err(ProgramError::Custom(MultisigError::NotDelegated as u32))],
+        );
+    }
+}
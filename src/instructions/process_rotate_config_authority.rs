@@ -0,0 +1,180 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::common::require_writable,
+    state::MultisigConfig,
+};
+
+/// Hands the config's `config_authority` over to a new key, e.g. when a team member leaves
+/// or the key is compromised. Only the current authority, signing and passed among the
+/// trailing accounts, can rotate it.
+///
+/// The all-zeros pubkey is rejected as the new authority, as nobody could sign for it.
+///
+/// Accounts: [multisig, multisig_config, ..signers]
+///
+/// Instruction data (after the discriminator): [new_authority: Pubkey]
+pub fn process_rotate_config_authority_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 32 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig_config)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let new_authority: Pubkey = data[0..32].try_into().unwrap();
+
+    if new_authority == Pubkey::default() {
+        log!("Error: The config authority can't be the all-zeros pubkey");
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let multisig_config_data = MultisigConfig::from_account_info_mut(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    multisig_config_data.check_config_authority(signers)?;
+
+    multisig_config_data.config_authority = new_authority;
+
+    log!("Config authority rotated to {}", &new_authority);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_rotate_config_authority_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::error::MultisigError;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const CONFIG_AUTHORITY: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const NEW_AUTHORITY: Pubkey = Pubkey::new_from_array([0x03; 32]);
+
+    fn rotate(signer: Pubkey, new_authority: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, config_authority)..][..32].copy_from_slice(CONFIG_AUTHORITY.as_ref());
+            data[offset_of!(MultisigConfig, bump)] = config_bump;
+            data
+        };
+
+        let mut data = vec![19u8]; // Instruction discriminator for rotate config authority
+        data.extend_from_slice(new_authority.as_ref());
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(MULTISIG, false),      // multisig
+                AccountMeta::new(multisig_config_pda, false),    // multisig_config
+                AccountMeta::new_readonly(signer, true),         // config authority (signer)
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MULTISIG, Account::new(1 * LAMPORTS_PER_SOL, 0, &ID)),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+            (signer, Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default())),
+        ];
+
+        (instruction, tx_accounts, multisig_config_pda)
+    }
+
+    #[test]
+    fn test_rotate_config_authority() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, multisig_config_pda) = rotate(CONFIG_AUTHORITY, NEW_AUTHORITY);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let config_account = &result.resulting_accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let config: MultisigConfig = bytemuck::pod_read_unaligned(&config_account.data);
+        assert_eq!(config.config_authority, NEW_AUTHORITY.to_bytes());
+
+        // The old authority can't rotate it back
+        let (instruction, _, _) = rotate(CONFIG_AUTHORITY, CONFIG_AUTHORITY);
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &result.resulting_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::UnauthorizedConfigChange as u32))],
+        );
+    }
+
+    #[test]
+    fn test_rotate_by_non_authority_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = rotate(Pubkey::new_unique(), NEW_AUTHORITY);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::UnauthorizedConfigChange as u32))],
+        );
+    }
+
+    #[test]
+    fn test_rotate_to_zero_authority_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = rotate(CONFIG_AUTHORITY, Pubkey::default());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
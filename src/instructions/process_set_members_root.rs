@@ -0,0 +1,221 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::common::require_writable,
+    state::{Multisig, MultisigConfig},
+};
+
+/// Sets `Multisig.members_root`, switching proposals created from then on to voters proving
+/// membership in the root, for multisigs with more voters than `members` can hold. The
+/// all-zeros root with a count of 0 switches back to `members`. Proposals that already
+/// exist keep the membership they were created with. Only the config's `config_authority`,
+/// signing and passed among the trailing accounts, can set it.
+///
+/// Accounts: [multisig, multisig_config, ..signers]
+///
+/// Instruction data (after the discriminator): [members_root: 32 bytes][root_member_count: u64]
+pub fn process_set_members_root_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 40 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let members_root: [u8; 32] = data[0..32].try_into().unwrap();
+    let root_member_count = u64::from_le_bytes(data[32..40].try_into().unwrap());
+
+    // A root commits to at least one member, and no root has none
+    if (members_root == [0u8; 32]) != (root_member_count == 0) {
+        log!("Error: A members root needs a non-zero member count, and only a root has one");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+    let multisig_config_data = MultisigConfig::from_account_info(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    multisig_config_data.require_direct_changes_allowed()?;
+    multisig_config_data.check_config_authority(signers)?;
+
+    multisig_data.members_root = members_root;
+    multisig_data.root_member_count = root_member_count;
+
+    log!("Members root set for {} members", root_member_count);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_set_members_root_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::{error::MultisigError, state::ProposalState};
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const PROPOSER: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const AUTHORITY: Pubkey = Pubkey::new_from_array([0x04; 32]);
+
+    fn setup() -> (Pubkey, Vec<(Pubkey, Account)>) {
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let mut multisig = Multisig::zeroed();
+        multisig.version = Multisig::VERSION;
+        multisig.num_members = 1;
+        multisig.members[0] = PROPOSER.to_bytes();
+
+        let mut config = MultisigConfig::zeroed();
+        config.version = MultisigConfig::VERSION;
+        config.min_threshold = 1;
+        config.config_authority = AUTHORITY.to_bytes();
+        config.bump = config_bump;
+
+        let accounts = vec![
+            (PROPOSER, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (AUTHORITY, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (system_program_id, system_account),
+        ];
+
+        (multisig_config_pda, accounts)
+    }
+
+    fn set_members_root(multisig_config_pda: Pubkey, signer: Pubkey, members_root: [u8; 32], root_member_count: u64) -> Instruction {
+        let mut data = vec![27u8]; // Instruction discriminator for set members root
+        data.extend_from_slice(&members_root);
+        data.extend_from_slice(&root_member_count.to_le_bytes());
+
+        Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new_readonly(multisig_config_pda, false),
+                AccountMeta::new_readonly(signer, true),
+            ],
+        )
+    }
+
+    fn create_proposal(multisig_config_pda: Pubkey, proposal_id: u64) -> (Instruction, Pubkey) {
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+
+        let mut data = vec![2u8]; // Instruction discriminator for create proposal
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.extend_from_slice(&3_600u64.to_le_bytes());
+        data.push(proposal_bump);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(PROPOSER, true),
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new_readonly(multisig_config_pda, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+            ],
+        );
+
+        (instruction, proposal_state_pda)
+    }
+
+    #[test]
+    fn test_members_root_is_snapshotted_by_new_proposals() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        let (multisig_config_pda, mut accounts) = setup();
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+
+        accounts = mollusk.process_and_validate_instruction(
+            &set_members_root(multisig_config_pda, AUTHORITY, [7u8; 32], 300),
+            &accounts,
+            &[Check::success()],
+        ).resulting_accounts;
+
+        let multisig_account = &accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        assert_eq!(&multisig_account.data[offset_of!(Multisig, members_root)..][..32], &[7u8; 32]);
+
+        let (instruction, proposal_state_pda) = create_proposal(multisig_config_pda, 1);
+        accounts.push((proposal_state_pda, Account::new(0, 0, &system_program_id)));
+        let accounts = mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]).resulting_accounts;
+
+        let proposal_account = &accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!(proposal.members_root, [7u8; 32]);
+        assert_eq!(proposal.root_member_count, 300);
+        assert_eq!(proposal.members_eligible(), 300);
+    }
+
+    #[test]
+    fn test_set_members_root_rejects_non_authority_and_mismatched_count() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        let (multisig_config_pda, accounts) = setup();
+
+        mollusk.process_and_validate_instruction(
+            &set_members_root(multisig_config_pda, PROPOSER, [7u8; 32], 300),
+            &accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::UnauthorizedConfigChange as u32))],
+        );
+
+        for (members_root, root_member_count) in [([7u8; 32], 0), ([0u8; 32], 300)] {
+            mollusk.process_and_validate_instruction(
+                &set_members_root(multisig_config_pda, AUTHORITY, members_root, root_member_count),
+                &accounts,
+                &[Check::err(ProgramError::InvalidInstructionData)],
+            );
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::common::require_writable,
+    state::{Multisig, MultisigConfig},
+};
+
+/// Designates the member who can veto proposals, see `process_veto_instruction`, or
+/// disables veto with the all-zeros pubkey. Only the config's `config_authority`, signing
+/// and passed among the trailing accounts, can set it.
+///
+/// The veto authority must be a current member of the multisig.
+///
+/// Accounts: [multisig, multisig_config, ..signers]
+///
+/// Instruction data (after the discriminator): [veto_authority: Pubkey]
+pub fn process_set_veto_authority_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 32 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig_config)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let veto_authority: Pubkey = data[0..32].try_into().unwrap();
+
+    let multisig_data = Multisig::from_account_info(multisig)?;
+    let multisig_config_data = MultisigConfig::from_account_info_mut(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    multisig_config_data.require_direct_changes_allowed()?;
+    multisig_config_data.check_config_authority(signers)?;
+
+    if veto_authority == Pubkey::default() {
+        multisig_config_data.veto_authority = veto_authority;
+        log!("Veto disabled");
+        return Ok(());
+    }
+
+    if !multisig_data.is_member(&veto_authority) {
+        log!("Error: The veto authority must be a member");
+        return Err(MultisigError::NotAMember.into());
+    }
+
+    multisig_config_data.veto_authority = veto_authority;
+
+    log!("Veto authority set to {}", &veto_authority);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_set_veto_authority_instruction {
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const CONFIG_AUTHORITY: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const MEMBERS: [Pubkey; 2] = [Pubkey::new_from_array([0x03; 32]), Pubkey::new_from_array([0x04; 32])];
+
+    fn set_veto_authority(signer: Pubkey, veto_authority: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let mut multisig = Multisig::zeroed();
+        multisig.version = Multisig::VERSION;
+        multisig.num_members = MEMBERS.len() as u8;
+        for (slot, member) in MEMBERS.iter().enumerate() {
+            multisig.members[slot] = member.to_bytes();
+        }
+
+        let mut config = MultisigConfig::zeroed();
+        config.version = MultisigConfig::VERSION;
+        config.config_authority = CONFIG_AUTHORITY.to_bytes();
+        config.bump = config_bump;
+
+        let mut data = vec![22u8]; // Instruction discriminator for set veto authority
+        data.extend_from_slice(veto_authority.as_ref());
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(MULTISIG, false),      // multisig
+                AccountMeta::new(multisig_config_pda, false),    // multisig_config
+                AccountMeta::new_readonly(signer, true),         // config authority (signer)
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (signer, Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default())),
+        ];
+
+        (instruction, tx_accounts, multisig_config_pda)
+    }
+
+    fn veto_authority_of(accounts: &[(Pubkey, Account)], multisig_config_pda: &Pubkey) -> Pubkey {
+        let config_account = &accounts.iter().find(|(key, _)| key == multisig_config_pda).unwrap().1;
+        let config: MultisigConfig = bytemuck::pod_read_unaligned(&config_account.data);
+        Pubkey::new_from_array(config.veto_authority)
+    }
+
+    #[test]
+    fn test_set_change_and_disable_veto_authority() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, multisig_config_pda) = set_veto_authority(CONFIG_AUTHORITY, MEMBERS[0]);
+        let accounts = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]).resulting_accounts;
+        assert_eq!(veto_authority_of(&accounts, &multisig_config_pda), MEMBERS[0]);
+
+        let (instruction, _, _) = set_veto_authority(CONFIG_AUTHORITY, MEMBERS[1]);
+        let accounts = mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]).resulting_accounts;
+        assert_eq!(veto_authority_of(&accounts, &multisig_config_pda), MEMBERS[1]);
+
+        let (instruction, _, _) = set_veto_authority(CONFIG_AUTHORITY, Pubkey::default());
+        let accounts = mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]).resulting_accounts;
+        assert_eq!(veto_authority_of(&accounts, &multisig_config_pda), Pubkey::default());
+    }
+
+    #[test]
+    fn test_non_member_veto_authority_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = set_veto_authority(CONFIG_AUTHORITY, Pubkey::new_unique());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::NotAMember as u32))],
+        );
+    }
+
+    #[test]
+    fn test_set_by_non_authority_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = set_veto_authority(MEMBERS[0], MEMBERS[0]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::UnauthorizedConfigChange as u32))],
+        );
+    }
+}
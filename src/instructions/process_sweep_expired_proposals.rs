@@ -0,0 +1,199 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::common::require_writable,
+    state::{Multisig, ProposalState},
+};
+
+/// Cancels every given proposal of the multisig that is past its expiry but still
+/// non-terminal. Anyone can call it, so keepers can clean up stale proposals in batches;
+/// votes on them are rejected instead. Expired Polls are decided by plurality.
+///
+/// Proposals that are still open or already terminal are skipped rather than rejected, so
+/// a batch never fails because one of its proposals changed state in the meantime. Accounts
+/// that aren't proposals of this multisig fail the instruction.
+///
+/// Accounts: [multisig, ..proposal_states]
+///
+/// Instruction data (after the discriminator): empty
+pub fn process_sweep_expired_proposals_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if !data.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, proposal_states @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if proposal_states.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig)?;
+
+    if multisig.owner() != &crate::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let mut cancelled = 0u64;
+
+    for proposal_state in proposal_states {
+        if proposal_state.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        require_writable(proposal_state)?;
+
+        let proposal_data = ProposalState::from_account_info(proposal_state)?;
+
+        let proposal_seed = [
+            b"proposal".as_slice(),
+            multisig.key().as_slice(),
+            &proposal_data.proposal_id.to_le_bytes(),
+            &[proposal_data.bump],
+        ];
+        let proposal_pda = pubkey::checked_create_program_address(&proposal_seed, &crate::ID)?;
+
+        if &proposal_pda != proposal_state.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if proposal_data.status()?.is_terminal() || !proposal_data.is_expired(current_time) {
+            continue;
+        }
+
+        multisig_data.record_outcome(proposal_data.resolve_expired()?)?;
+        cancelled += 1;
+
+        log!("Proposal {} finalized due to expiry", proposal_data.proposal_id);
+    }
+
+    log!("Swept {} expired proposals", cancelled);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_sweep_expired_proposals_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::state::ProposalStatus;
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const KEEPER: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+
+    fn proposal_account(proposal_id: u64, expiry: u64, status: ProposalStatus) -> (Pubkey, Account) {
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+
+        let mut proposal = ProposalState::zeroed();
+        proposal.proposal_id = proposal_id;
+        proposal.expiry = expiry;
+        proposal.bump = proposal_bump;
+        proposal.set_status(status);
+
+        (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&proposal), &ID).unwrap())
+    }
+
+    fn multisig_account() -> (Pubkey, Account) {
+        let mut multisig = Multisig::zeroed();
+        multisig.version = Multisig::VERSION;
+
+        (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap())
+    }
+
+    fn sweep_instruction(proposals: &[Pubkey]) -> Instruction {
+        let mut accounts = vec![AccountMeta::new(MULTISIG, false)];
+        accounts.extend(proposals.iter().map(|proposal| AccountMeta::new(*proposal, false)));
+
+        Instruction::new_with_bytes(
+            ID,
+            &[18u8], // Instruction discriminator for sweep expired proposals
+            accounts,
+        )
+    }
+
+    #[test]
+    fn test_sweep_cancels_only_expired_proposals() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 2_000;
+
+        let expired = proposal_account(1, 1_000, ProposalStatus::Active);
+        let live = proposal_account(2, 3_000, ProposalStatus::Active);
+        let expired_succeeded = proposal_account(3, 1_000, ProposalStatus::Succeeded);
+        let expiring_now = proposal_account(4, 2_000, ProposalStatus::Active);
+
+        let proposals = [expired.0, live.0, expired_succeeded.0, expiring_now.0];
+        let tx_accounts = vec![
+            multisig_account(),
+            expired,
+            live,
+            expired_succeeded,
+            expiring_now,
+        ];
+
+        let result = mollusk.process_and_validate_instruction(&sweep_instruction(&proposals), &tx_accounts, &[Check::success()]);
+
+        let status_of = |proposal: &Pubkey| {
+            let account = &result.resulting_accounts.iter().find(|(key, _)| key == proposal).unwrap().1;
+            account.data[offset_of!(ProposalState, result)]
+        };
+        assert_eq!(status_of(&proposals[0]), ProposalStatus::Cancelled as u8);
+        assert_eq!(status_of(&proposals[1]), ProposalStatus::Active as u8);
+        assert_eq!(status_of(&proposals[2]), ProposalStatus::Succeeded as u8);
+        // Still open through its expiry second
+        assert_eq!(status_of(&proposals[3]), ProposalStatus::Active as u8);
+    }
+
+    #[test]
+    fn test_sweep_rejects_proposal_of_another_multisig() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 2_000;
+
+        let (_, account) = proposal_account(1, 1_000, ProposalStatus::Active);
+        let foreign_proposal = Pubkey::new_unique();
+
+        let tx_accounts = vec![
+            multisig_account(),
+            (foreign_proposal, account),
+        ];
+
+        mollusk.process_and_validate_instruction(
+            &sweep_instruction(&[foreign_proposal]),
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    instructions::common::require_writable,
+    state::Multisig,
+};
+
+/// Action of an executed `ProposalType::Transfer` proposal: pays `lamports` from the
+/// multisig's treasury PDA to the recipient stored in the payload, signed with
+/// `treasury_signer`. Only reachable through `process_execute_proposal_instruction`.
+///
+/// The recipient must be writable, and the treasury must keep its rent-exempt minimum
+/// after the transfer.
+///
+/// Accounts: [treasury, recipient, system_program]
+///
+/// Payload: [recipient: Pubkey][lamports: u64]
+pub fn process_transfer_from_multisig_instruction(
+    multisig_data: &Multisig,
+    accounts: &[AccountInfo],
+    payload: &[u8],
+    treasury_signer: Signer,
+) -> ProgramResult {
+
+    let [treasury, recipient, system_program, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let expected_recipient: Pubkey = payload[0..32].try_into().unwrap();
+    let lamports = u64::from_le_bytes(payload[32..40].try_into().unwrap());
+
+    if treasury.key() != &multisig_data.treasury || recipient.key() != &expected_recipient {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    require_writable(treasury)?;
+    require_writable(recipient)?;
+
+    if system_program.key() != &pinocchio_system::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let spendable = treasury.lamports().saturating_sub(Rent::get()?.minimum_balance(treasury.data_len()));
+
+    if lamports > spendable {
+        log!("Error: Treasury can only spend {} lamports and stay rent-exempt", spendable);
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    Transfer {
+        from: treasury,
+        to: recipient,
+        lamports,
+    }.invoke_signed(&[treasury_signer])?;
+
+    log!("Transferred {} lamports from the treasury", lamports);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_transfer_from_multisig_instruction {
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::state::{MultisigConfig, ProposalType};
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const CREATOR: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const RECIPIENT: Pubkey = Pubkey::new_from_array([0x03; 32]);
+
+    /// Proposes a Transfer of `amount` to RECIPIENT, votes it to Succeeded and returns the
+    /// execute instruction, with the recipient writable or not, and the resulting accounts
+    fn approved_transfer(mollusk: &Mollusk, amount: u64, recipient_writable: bool) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let multisig_seed = 7u64;
+        let proposal_id = 1u64;
+        let member = Pubkey::new_unique();
+
+        let (multisig_pda, multisig_bump) = Pubkey::find_program_address(
+            &[b"multisig", CREATOR.as_ref(), &multisig_seed.to_le_bytes()],
+            &ID,
+        );
+        let (treasury_pda, treasury_bump) = Pubkey::find_program_address(
+            &[b"treasury", multisig_pda.as_ref()],
+            &ID,
+        );
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", multisig_pda.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", multisig_pda.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (vote_state_pda, _) = Pubkey::find_program_address(
+            &[b"vote_state", multisig_pda.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[b"receipt", proposal_state_pda.as_ref(), member.as_ref()],
+            &ID,
+        );
+
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+
+        let mut multisig = Multisig::zeroed();
+
+        multisig.version = Multisig::VERSION;
+        multisig.creator = CREATOR.to_bytes();
+        multisig.num_members = 2;
+        multisig.members[0] = CREATOR.to_bytes();
+        multisig.members[1] = member.to_bytes();
+        multisig.bump = multisig_bump;
+        multisig.treasury = treasury_pda.to_bytes();
+        multisig.treasury_bump = treasury_bump;
+
+        let mut config = MultisigConfig::zeroed();
+
+        config.version = MultisigConfig::VERSION;
+        config.min_threshold = 1;
+        config.bump = config_bump;
+
+        let mut accounts = vec![
+            (CREATOR, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (member, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (multisig_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (proposal_state_pda, Account::new(0, 0, &system_program_id)),
+            (vote_state_pda, Account::new(0, 0, &system_program_id)),
+            (vote_receipt_pda, Account::new(0, 0, &system_program_id)),
+            (treasury_pda, Account::new(2 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (RECIPIENT, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (system_program_id, system_account),
+        ];
+
+        // 1. Propose the transfer
+        let mut data = vec![2u8]; // Instruction discriminator for create proposal
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.extend_from_slice(&3_600u64.to_le_bytes());
+        data.push(proposal_bump);
+        data.push(ProposalType::Transfer as u8);
+        data.extend_from_slice(RECIPIENT.as_ref());
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let create = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(CREATOR, true),
+                AccountMeta::new(multisig_pda, false),
+                AccountMeta::new_readonly(multisig_config_pda, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+            ],
+        );
+        accounts = mollusk.process_and_validate_instruction(&create, &accounts, &[Check::success()]).resulting_accounts;
+
+        // 2. One For vote meets the threshold of 1
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.push(1); // Vote choice: For
+        data.push(proposal_bump);
+
+        let vote = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(member, true),
+                AccountMeta::new(multisig_pda, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new(vote_state_pda, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(vote_receipt_pda, false),
+            ],
+        );
+        accounts = mollusk.process_and_validate_instruction(&vote, &accounts, &[Check::success()]).resulting_accounts;
+
+        // 3. Execute the transfer out of the treasury
+        let mut data = vec![8u8]; // Instruction discriminator for execute proposal
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.push(proposal_bump);
+        data.extend_from_slice(&multisig_seed.to_le_bytes());
+
+        let execute = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(CREATOR, true),                      // executor (signer)
+                AccountMeta::new(multisig_pda, false),                // multisig
+                AccountMeta::new(proposal_state_pda, false),          // proposal_state
+                AccountMeta::new_readonly(multisig_config_pda, false), // multisig_config
+                AccountMeta::new(treasury_pda, false),                // treasury
+                if recipient_writable {
+                    AccountMeta::new(RECIPIENT, false)
+                } else {
+                    AccountMeta::new_readonly(RECIPIENT, false)
+                },                                                    // recipient
+                AccountMeta::new_readonly(system_program_id, false),  // system_program
+            ],
+        );
+
+        (execute, accounts, treasury_pda)
+    }
+
+    #[test]
+    fn test_approved_transfer_moves_lamports() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let amount = LAMPORTS_PER_SOL / 2;
+        let (execute, accounts, treasury_pda) = approved_transfer(&mollusk, amount, true);
+
+        mollusk.process_and_validate_instruction(
+            &execute,
+            &accounts,
+            &[
+                Check::success(),
+                Check::account(&treasury_pda).lamports(2 * LAMPORTS_PER_SOL - amount).build(),
+                Check::account(&RECIPIENT).lamports(LAMPORTS_PER_SOL + amount).build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_transfer_to_readonly_recipient_is_rejected() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let (execute, accounts, _) = approved_transfer(&mollusk, LAMPORTS_PER_SOL / 2, false);
+
+        mollusk.process_and_validate_instruction(&execute, &accounts, &[Check::err(ProgramError::InvalidAccountData)]);
+    }
+
+    #[test]
+    fn test_transfer_below_treasury_rent_exemption_is_rejected() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        // Draining the whole treasury would leave it below its rent-exempt minimum
+        let (execute, accounts, _) = approved_transfer(&mollusk, 2 * LAMPORTS_PER_SOL, true);
+
+        mollusk.process_and_validate_instruction(&execute, &accounts, &[Check::err(ProgramError::InsufficientFunds)]);
+    }
+}
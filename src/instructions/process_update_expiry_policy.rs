@@ -0,0 +1,227 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    instructions::common::require_writable,
+    state::{MultisigConfig, MAX_PROPOSAL_DURATION},
+};
+
+/// Sets `MultisigConfig.max_proposal_duration`, the longest duration new proposals can be
+/// created with. 0 restores the program-wide `MAX_PROPOSAL_DURATION`, which no limit may
+/// exceed. Proposals that already exist keep their expiry. Only the config's
+/// `config_authority`, signing and passed among the trailing accounts, can set it.
+///
+/// Accounts: [multisig, multisig_config, ..signers]
+///
+/// Instruction data (after the discriminator): [max_proposal_duration: u64]
+pub fn process_update_expiry_policy_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig_config)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let max_proposal_duration = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    if max_proposal_duration > MAX_PROPOSAL_DURATION {
+        log!("Error: Proposals can't run longer than {} seconds", MAX_PROPOSAL_DURATION);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let multisig_config_data = MultisigConfig::from_account_info_mut(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    multisig_config_data.require_direct_changes_allowed()?;
+    multisig_config_data.check_config_authority(signers)?;
+
+    multisig_config_data.max_proposal_duration = max_proposal_duration;
+
+    log!("Proposals can now run for up to {} seconds", multisig_config_data.proposal_duration_limit());
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_update_expiry_policy_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::{error::MultisigError, state::Multisig};
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const PROPOSER: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const AUTHORITY: Pubkey = Pubkey::new_from_array([0x04; 32]);
+
+    fn setup() -> (Pubkey, Vec<(Pubkey, Account)>) {
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let mut multisig = Multisig::zeroed();
+        multisig.version = Multisig::VERSION;
+        multisig.num_members = 1;
+        multisig.members[0] = PROPOSER.to_bytes();
+
+        let mut config = MultisigConfig::zeroed();
+        config.version = MultisigConfig::VERSION;
+        config.min_threshold = 1;
+        config.config_authority = AUTHORITY.to_bytes();
+        config.bump = config_bump;
+
+        let accounts = vec![
+            (PROPOSER, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (AUTHORITY, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (system_program_id, system_account),
+        ];
+
+        (multisig_config_pda, accounts)
+    }
+
+    fn update_expiry_policy(multisig_config_pda: Pubkey, signer: Pubkey, max_proposal_duration: u64) -> Instruction {
+        let mut data = vec![25u8]; // Instruction discriminator for update expiry policy
+        data.extend_from_slice(&max_proposal_duration.to_le_bytes());
+
+        Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(MULTISIG, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(signer, true),
+            ],
+        )
+    }
+
+    fn create_proposal(multisig_config_pda: Pubkey, proposal_id: u64, duration: u64) -> (Instruction, Pubkey) {
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+
+        let mut data = vec![2u8]; // Instruction discriminator for create proposal
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.extend_from_slice(&duration.to_le_bytes());
+        data.push(proposal_bump);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(PROPOSER, true),
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new_readonly(multisig_config_pda, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+            ],
+        );
+
+        (instruction, proposal_state_pda)
+    }
+
+    #[test]
+    fn test_max_proposal_duration_bounds_new_proposals() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        let (multisig_config_pda, mut accounts) = setup();
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+
+        accounts = mollusk.process_and_validate_instruction(
+            &update_expiry_policy(multisig_config_pda, AUTHORITY, 3_600),
+            &accounts,
+            &[Check::success()],
+        ).resulting_accounts;
+
+        let config_account = &accounts.iter().find(|(key, _)| key == &multisig_config_pda).unwrap().1;
+        let max_proposal_duration = u64::from_le_bytes(config_account.data[offset_of!(MultisigConfig, max_proposal_duration)..][..8].try_into().unwrap());
+        assert_eq!(max_proposal_duration, 3_600);
+
+        // One second over the limit is rejected
+        let (instruction, proposal_state_pda) = create_proposal(multisig_config_pda, 1, 3_601);
+        accounts.push((proposal_state_pda, Account::new(0, 0, &system_program_id)));
+        mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::err(ProgramError::InvalidInstructionData)]);
+
+        // The limit itself is accepted
+        let (instruction, _) = create_proposal(multisig_config_pda, 1, 3_600);
+        mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]);
+    }
+
+    #[test]
+    fn test_default_policy_still_caps_at_max_proposal_duration() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        let (multisig_config_pda, mut accounts) = setup();
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+
+        let (instruction, proposal_state_pda) = create_proposal(multisig_config_pda, 1, MAX_PROPOSAL_DURATION + 1);
+        accounts.push((proposal_state_pda, Account::new(0, 0, &system_program_id)));
+        mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::err(ProgramError::InvalidInstructionData)]);
+
+        let (instruction, _) = create_proposal(multisig_config_pda, 1, MAX_PROPOSAL_DURATION);
+        mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]);
+    }
+
+    #[test]
+    fn test_update_expiry_policy_rejects_non_authority_and_over_long_limit() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        let (multisig_config_pda, accounts) = setup();
+
+        mollusk.process_and_validate_instruction(
+            &update_expiry_policy(multisig_config_pda, PROPOSER, 3_600),
+            &accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::UnauthorizedConfigChange as u32))],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &update_expiry_policy(multisig_config_pda, AUTHORITY, MAX_PROPOSAL_DURATION + 1),
+            &accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::common::require_writable,
+    state::{Multisig, MultisigConfig},
+};
+
+/// Sets the vote weight of an existing member.
+///
+/// Authorized either by the multisig PDA signing (when invoked from an approved proposal)
+/// or by the config's `config_authority` signing this instruction directly, passed among
+/// the trailing accounts. A member's weight can't be set to zero; remove the member instead.
+///
+/// Proposals snapshot every member's weight into `active_weights` at creation, so the new
+/// weight only applies to proposals created after the change; in-flight proposals keep
+/// the old one.
+///
+/// Accounts: [multisig, multisig_config, ..signers]
+///
+/// Instruction data (after the discriminator): [member: Pubkey][weight: u64]
+pub fn process_update_member_weight_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 40 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let member: Pubkey = data[0..32].try_into().unwrap();
+    let weight = u64::from_le_bytes(data[32..40].try_into().unwrap());
+
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
+    let multisig_config_data = MultisigConfig::from_account_info(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if !multisig.is_signer() {
+        multisig_config_data.require_direct_changes_allowed()?;
+        multisig_config_data.check_config_authority(signers)?;
+    }
+
+    let member_index = multisig_data
+        .member_index(&member)
+        .ok_or(MultisigError::NotAMember)?;
+
+    if weight == 0 {
+        log!("Error: A member's weight must be at least 1");
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    multisig_data.weights[member_index] = weight;
+
+    log!("Weight of member {} set to {}", member_index, weight);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_update_member_weight_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use crate::state::{ProposalState, ProposalStatus};
+    use {
+        mollusk_svm::{program, Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const CONFIG_AUTHORITY: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+
+    /// A two member multisig, both of weight 1, with a threshold of 2
+    fn setup(members: &[Pubkey; 2]) -> Vec<(Pubkey, Account)> {
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = members.len() as u8;
+            let members_offset = offset_of!(Multisig, members);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data
+        };
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&2u64.to_le_bytes());
+            data[offset_of!(MultisigConfig, config_authority)..][..32].copy_from_slice(CONFIG_AUTHORITY.as_ref());
+            data[offset_of!(MultisigConfig, bump)] = config_bump;
+            data
+        };
+
+        let mut accounts = vec![
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+            (system_program_id, system_account),
+        ];
+        for member in members {
+            accounts.push((*member, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)));
+        }
+        accounts
+    }
+
+    fn update_member_weight(signer: Pubkey, member: Pubkey, weight: u64) -> Instruction {
+        let (multisig_config_pda, _) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let mut data = vec![17u8]; // Instruction discriminator for update member weight
+        data.extend_from_slice(member.as_ref());
+        data.extend_from_slice(&weight.to_le_bytes());
+
+        Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(MULTISIG, false),                       // multisig
+                AccountMeta::new_readonly(multisig_config_pda, false),   // multisig_config
+                AccountMeta::new_readonly(signer, true),                 // config authority (signer)
+            ],
+        )
+    }
+
+    fn signer_account(signer: Pubkey) -> (Pubkey, Account) {
+        (signer, Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default()))
+    }
+
+    /// Creates proposal `proposal_id`, proposed by `proposer`
+    fn create_proposal(mollusk: &Mollusk, accounts: Vec<(Pubkey, Account)>, proposer: Pubkey, proposal_id: u64) -> (Vec<(Pubkey, Account)>, Pubkey) {
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let (multisig_config_pda, _) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+
+        let mut data = vec![2u8]; // Instruction discriminator for create proposal
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.extend_from_slice(&3_600u64.to_le_bytes());
+        data.push(proposal_bump);
+
+        let create = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(proposer, true),
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new_readonly(multisig_config_pda, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+            ],
+        );
+
+        let mut accounts = accounts;
+        accounts.push((proposal_state_pda, Account::new(0, 0, &system_program_id)));
+        let result = mollusk.process_and_validate_instruction(&create, &accounts, &[Check::success()]);
+
+        (result.resulting_accounts, proposal_state_pda)
+    }
+
+    /// `voter` votes For on proposal `proposal_id`
+    fn vote_for(mollusk: &Mollusk, accounts: Vec<(Pubkey, Account)>, voter: Pubkey, proposal_id: u64) -> Vec<(Pubkey, Account)> {
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let (multisig_config_pda, _) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (vote_state_pda, _) = Pubkey::find_program_address(
+            &[b"vote_state", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[b"receipt", proposal_state_pda.as_ref(), voter.as_ref()],
+            &ID,
+        );
+
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.push(1); // Vote choice: For
+        data.push(proposal_bump);
+
+        let vote = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(voter, true),
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new(vote_state_pda, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(vote_receipt_pda, false),
+            ],
+        );
+
+        let mut accounts = accounts;
+        accounts.push((vote_state_pda, Account::new(0, 0, &system_program_id)));
+        accounts.push((vote_receipt_pda, Account::new(0, 0, &system_program_id)));
+        mollusk.process_and_validate_instruction(&vote, &accounts, &[Check::success()]).resulting_accounts
+    }
+
+    fn proposal(accounts: &[(Pubkey, Account)], proposal_state_pda: &Pubkey) -> ProposalState {
+        let proposal_account = &accounts.iter().find(|(key, _)| key == proposal_state_pda).unwrap().1;
+        bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN])
+    }
+
+    #[test]
+    fn test_new_weight_applies_to_new_proposals_only() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [CONFIG_AUTHORITY, Pubkey::new_unique()];
+        let accounts = setup(&members);
+
+        // Proposal 1 is created while member 1 still has a weight of 1
+        let (accounts, in_flight_pda) = create_proposal(&mollusk, accounts, members[0], 1);
+
+        // The config authority is member 0, already among the accounts
+        let accounts = mollusk.process_and_validate_instruction(
+            &update_member_weight(CONFIG_AUTHORITY, members[1], 2),
+            &accounts,
+            &[Check::success()],
+        ).resulting_accounts;
+
+        let multisig_account = &accounts.iter().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        let multisig: Multisig = bytemuck::pod_read_unaligned(&multisig_account.data);
+        assert_eq!(multisig.weights[1], 2);
+
+        // Proposal 2 snapshots the new weight
+        let (accounts, new_pda) = create_proposal(&mollusk, accounts, members[0], 2);
+        assert_eq!(proposal(&accounts, &in_flight_pda).active_weights[1], 1);
+        assert_eq!(proposal(&accounts, &new_pda).active_weights[1], 2);
+
+        // With a threshold of 2, member 1's For vote alone only passes the new proposal
+        let accounts = vote_for(&mollusk, accounts, members[1], 1);
+        let accounts = vote_for(&mollusk, accounts, members[1], 2);
+
+        let in_flight = proposal(&accounts, &in_flight_pda);
+        assert_eq!(in_flight.for_votes, 1);
+        assert!(matches!(in_flight.status(), Ok(ProposalStatus::Active)));
+
+        let new = proposal(&accounts, &new_pda);
+        assert_eq!(new.for_votes, 2);
+        assert!(matches!(new.status(), Ok(ProposalStatus::Succeeded)));
+    }
+
+    #[test]
+    fn test_update_member_weight_rejects_non_authority() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [CONFIG_AUTHORITY, Pubkey::new_unique()];
+        let mut accounts = setup(&members);
+        let signer = Pubkey::new_unique();
+        accounts.push(signer_account(signer));
+
+        mollusk.process_and_validate_instruction(
+            &update_member_weight(signer, members[1], 2),
+            &accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::UnauthorizedConfigChange as u32))],
+        );
+    }
+
+    #[test]
+    fn test_update_member_weight_rejects_zero_weight() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [CONFIG_AUTHORITY, Pubkey::new_unique()];
+        let accounts = setup(&members);
+
+        mollusk.process_and_validate_instruction(
+            &update_member_weight(CONFIG_AUTHORITY, members[1], 0),
+            &accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
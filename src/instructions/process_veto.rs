@@ -0,0 +1,211 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::common::{require_signer, require_writable},
+    state::{MultisigConfig, ProposalState, ProposalStatus},
+};
+
+/// Cancels any non-terminal proposal on behalf of the config's `veto_authority`,
+/// regardless of its vote counts.
+///
+/// The veto is disabled while `veto_authority` is all zeros. Cancelled is a terminal
+/// state, so the vote instruction rejects any further votes on a vetoed proposal.
+///
+/// Accounts: [veto_authority, multisig, multisig_config, proposal_state]
+///
+/// Instruction data (after the discriminator): [proposal_id: u64][proposal_bump: u8]
+pub fn process_veto_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() != 9 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [veto_authority, multisig, multisig_config, proposal_state, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_signer(veto_authority)?;
+    require_writable(proposal_state)?;
+
+    for accounts in [multisig, multisig_config, proposal_state] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    let proposal_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let proposal_bump = data[8];
+
+    let multisig_config_data = &MultisigConfig::load(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if multisig_config_data.veto_authority == Pubkey::default() {
+        log!("Error: Veto is disabled for this multisig");
+        return Err(MultisigError::VetoDisabled.into());
+    }
+
+    if veto_authority.key() != &multisig_config_data.veto_authority {
+        log!("Error: Signer is not the veto authority");
+        return Err(ProgramError::IncorrectAuthority);
+    }
+
+    let proposal_data = ProposalState::from_account_info(proposal_state)?;
+
+    let proposal_seed = [
+        b"proposal".as_slice(),
+        multisig.key().as_slice(),
+        &proposal_id.to_le_bytes(),
+        &[proposal_bump],
+    ];
+    let proposal_pda = pubkey::checked_create_program_address(&proposal_seed, &crate::ID)?;
+
+    if &proposal_pda != proposal_state.key() || proposal_data.proposal_id != proposal_id {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    match proposal_data.status()? {
+        ProposalStatus::Draft | ProposalStatus::Active => {},
+        _ => return Err(MultisigError::ProposalNotActive.into()), // Proposal already reached a terminal state
+    };
+
+    proposal_data.set_status(ProposalStatus::Cancelled);
+
+    log!("Proposal {} vetoed", proposal_id);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_veto_instruction {
+    use core::mem::offset_of;
+
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const VETO_AUTHORITY: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const PROPOSAL_ID: u64 = 1;
+
+    fn veto(signer: Pubkey, veto_authority: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, veto_authority)..][..32].copy_from_slice(veto_authority.as_ref());
+            data[offset_of!(MultisigConfig, bump)] = config_bump;
+            data
+        };
+
+        let proposal_data = {
+            let mut data = vec![0u8; ProposalState::BASE_LEN];
+            data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&PROPOSAL_ID.to_le_bytes());
+            data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&u64::MAX.to_le_bytes());
+            data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
+            data[offset_of!(ProposalState, bump)] = proposal_bump;
+            data
+        };
+
+        let mut data = vec![13u8]; // Instruction discriminator for veto
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(proposal_bump);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(signer, true),                 // veto_authority (signer)
+                AccountMeta::new_readonly(MULTISIG, false),              // multisig
+                AccountMeta::new_readonly(multisig_config_pda, false),   // multisig_config
+                AccountMeta::new(proposal_state_pda, false),             // proposal_state
+            ],
+        );
+
+        let tx_accounts = vec![
+            (signer, Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default())),
+            (MULTISIG, Account::new(1 * LAMPORTS_PER_SOL, 0, &ID)),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+            (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &proposal_data, &ID).unwrap()),
+        ];
+
+        (instruction, tx_accounts, proposal_state_pda)
+    }
+
+    #[test]
+    fn test_veto_cancels_active_proposal() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, proposal_state_pda) = veto(VETO_AUTHORITY, VETO_AUTHORITY);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Cancelled as u8);
+    }
+
+    #[test]
+    fn test_veto_rejects_non_authority() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = veto(Pubkey::new_unique(), VETO_AUTHORITY);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::IncorrectAuthority)],
+        );
+    }
+
+    #[test]
+    fn test_veto_rejected_when_disabled() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = veto(VETO_AUTHORITY, Pubkey::default());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::VetoDisabled as u32))],
+        );
+    }
+}
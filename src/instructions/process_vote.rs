@@ -1,78 +1,312 @@
 use pinocchio::{
     account_info::AccountInfo,
+    instruction::Seed,
+    log::sol_log_data,
     program_error::ProgramError,
-    pubkey::{self},
+    pubkey::{self, Pubkey},
     sysvars::{clock::Clock, Sysvar, rent::Rent},
     ProgramResult,
 };
 
+use bytemuck::Zeroable;
+
 use pinocchio_log::log;
 
-use pinocchio_system::instructions::CreateAccount;
+use pinocchio_system::instructions::Transfer;
 
-use crate::state::{Multisig, MultisigConfig, ProposalState, ProposalStatus, VoteState};
+use crate::{
+    error::MultisigError,
+    events::VoteCast,
+    instructions::common::{create_pda_account, require_signer, require_slot, require_writable},
+    state::{verify_member_proof, Multisig, MultisigConfig, ProposalState, ProposalStatus, VoteReceipt, VoteState, MAX_MEMBERS, MAX_MEMBERS_PROOF_DEPTH, MAX_PROPOSAL_DATA_LEN, PROOF_NODE_LEN, ROLE_CAN_VOTE},
+};
 
-pub fn process_vote_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+/// Vote flag: change the voter's existing vote instead of casting a first vote
+pub const VOTE_FLAG_CHANGE: u8 = 1;
 
-    if data.len() < 10 {
-        return Err(ProgramError::InvalidInstructionData);
-    };
+/// Vote flag: validate the vote and log the status it would lead to, without writing any
+/// account or creating the vote state and receipt. Meant for transaction simulation.
+pub const VOTE_FLAG_DRY_RUN: u8 = 2;
 
-    let [voter, multisig, proposal_state, vote_state, multisig_config, _remaining @ ..] = accounts else {
-        return Err(ProgramError::NotEnoughAccountKeys);
-    };
+/// Reads the proposal id from the start of the vote payload. Instruction data has no
+/// alignment guarantee, so the bytes are copied out instead of dereferencing a `*const u64`.
+fn read_proposal_id(data: &[u8]) -> u64 {
+    u64::from_le_bytes(data[0..8].try_into().unwrap())
+}
+
+/// Copy of a vote state account. A legacy vote state reads with an empty voted bitmap, as
+/// growing it into the current layout leaves it.
+fn read_vote_state(vote_state: &AccountInfo) -> VoteState {
+    let mut copy = VoteState::zeroed();
+    let data = unsafe { vote_state.borrow_data_unchecked() };
+    let len = data.len().min(VoteState::LEN);
+    bytemuck::bytes_of_mut(&mut copy)[..len].copy_from_slice(&data[..len]);
+    copy
+}
+
+/// Time-dependent gate of a vote at `now`, kept apart from `Clock` so the boundaries can
+/// be tested directly. Voting is open from `start_time` through `expiry`; earlier votes
+/// are rejected, and so are later ones, with `ProposalExpired`. Finalizing an expired
+/// proposal is left to `FinalizeProposal` and `SweepExpiredProposals`.
+fn check_voting_window(proposal: &ProposalState, now: u64) -> ProgramResult {
+    if now < proposal.start_time {
+        log!("Voting has not started yet");
+        return Err(MultisigError::VotingNotStarted.into());
+    }
+
+    if proposal.is_expired(now) {
+        log!("Error: Proposal has expired");
+        return Err(MultisigError::ProposalExpired.into());
+    }
+
+    Ok(())
+}
+
+/// Status the proposal's current votes decide at `now`, with the winning option of a
+/// decided Poll, under every setting of the config: the approval and defeat thresholds,
+/// the proposal's quorum snapshot, unanimity, and failing once approval is unreachable.
+/// Active while undecided; the proposal itself is not modified.
+pub(crate) fn project_outcome(
+    proposal: &ProposalState,
+    multisig: &Multisig,
+    config: &MultisigConfig,
+    required_votes: u64,
+    now: u64,
+) -> Result<(ProposalStatus, u8), ProgramError> {
+    // A Poll is decided between its own options; the settings below only apply to
+    // For/Against/Abstain proposals
+    if proposal.is_poll() {
+        return proposal.poll_projection(required_votes, proposal.quorum, now);
+    }
 
-    if !voter.is_signer() {
-        log!("Error: Voter account must be a signer");
-        return Err(ProgramError::MissingRequiredSignature);
+    // Unanimity replaces the threshold and quorum altogether
+    let projection = if config.require_unanimous == 1 {
+        proposal.unanimous_projection(proposal.total_weight(multisig), config.unanimous_abstain_fails == 1, now)?
+    } else {
+        proposal.tally(required_votes, config.defeat_votes(required_votes), proposal.quorum, now)?.3
     };
 
-    let writable_accounts = [multisig, proposal_state, vote_state];
+    // Opt-in: once abstains (or Against votes) leave too little unvoted weight for the For
+    // votes to ever reach the threshold, fail the proposal now instead of at expiry
+    if projection == ProposalStatus::Active
+        && config.fail_when_unreachable == 1
+        && !proposal.approval_reachable(required_votes, proposal.total_weight(multisig))?
+    {
+        log!("Approval is no longer reachable");
+        return Ok((ProposalStatus::Failed, 0));
+    }
 
-    for accounts in writable_accounts {
-        if !accounts.is_writable() {
-            log!("Error: Account {} must be writable", accounts.key());
-            return Err(ProgramError::InvalidAccountData);
+    Ok((projection, 0))
+}
+
+/// Writes a decided `projection` to the proposal at `now`, starting the execution timelock
+/// when it succeeds. An undecided projection leaves the proposal Active.
+pub(crate) fn apply_projection(proposal: &mut ProposalState, projection: ProposalStatus, winning_option: u8, now: u64) -> ProgramResult {
+    if !projection.is_terminal() {
+        return Ok(());
+    }
+
+    proposal.finalize(projection)?;
+    proposal.winning_option = winning_option;
+
+    if projection == ProposalStatus::Succeeded && proposal.succeeded_at == 0 {
+        proposal.succeeded_at = now;
+    }
+    Ok(())
+}
+
+/// Accounts of the vote instruction, structurally validated: the slot count, the voter's
+/// signature, ownership of the program accounts, and each program account matching its
+/// slot. Checks that need account data, such as PDA seeds, are left to the handler, as is
+/// writability, which a dry run doesn't need.
+pub struct VoteAccounts<'a> {
+    pub voter: &'a AccountInfo,
+    pub multisig: &'a AccountInfo,
+    pub proposal_state: &'a AccountInfo,
+    pub vote_state: &'a AccountInfo,
+    pub multisig_config: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub vote_receipt: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for VoteAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [voter, multisig, proposal_state, vote_state, multisig_config, system_program, vote_receipt, _remaining @ ..] = accounts else {
+            log!("Error: Expected voter, multisig, proposal_state, vote_state, multisig_config, system_program and vote_receipt accounts");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(voter)?;
+
+        for accounts in [multisig, proposal_state, multisig_config] {
+            if accounts.owner() != &crate::ID {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+        }
+
+        let proposal_len = ProposalState::BASE_LEN..=ProposalState::BASE_LEN + MAX_PROPOSAL_DATA_LEN;
+        require_slot(multisig, 1, "multisig", multisig.data_len() == Multisig::LEN)?;
+        require_slot(proposal_state, 2, "proposal_state", proposal_len.contains(&proposal_state.data_len()))?;
+        require_slot(multisig_config, 4, "multisig_config", multisig_config.data_len() == MultisigConfig::LEN)?;
+
+        // The vote state and receipt don't exist before the first vote, so only existing ones
+        // can be told apart
+        if vote_state.owner() == &crate::ID {
+            let vote_state_len = vote_state.data_len();
+            require_slot(vote_state, 3, "vote_state", vote_state_len == VoteState::LEN || vote_state_len == VoteState::LEGACY_LEN)?;
         }
+        if vote_receipt.owner() == &crate::ID {
+            require_slot(vote_receipt, 6, "vote_receipt", vote_receipt.data_len() == VoteReceipt::LEN)?;
+        }
+
+        Ok(Self { voter, multisig, proposal_state, vote_state, multisig_config, system_program, vote_receipt })
     }
+}
+
+/// Records the vote in a per-voter `VoteReceipt` and updates the proposal's cached tallies.
+///
+/// Accounts: [voter, multisig, proposal_state, vote_state, multisig_config, system_program, vote_receipt]
+///
+/// The order is fixed. Program accounts are checked against their slot by size, and one
+/// passed in the wrong slot fails with `AccountOutOfOrder`, naming the slot in the log.
+///
+/// Instruction data (after the discriminator, which the entrypoint strips):
+/// [proposal_id: u64][vote_choice: u8][proposal_bump: u8] followed optionally by
+/// [flags: u8] and then [delegator_index: u8]. `flags` combines `VOTE_FLAG_CHANGE` and
+/// `VOTE_FLAG_DRY_RUN`. A change is only accepted when the config's `allow_vote_change` is
+/// set; otherwise a second vote fails with `AlreadyVoted`.
+///
+/// A dry run goes through every check and the tally, logs the status the vote would lead
+/// to and returns without modifying any account.
+///
+/// On a proposal created with a members root, the data is instead
+/// [proposal_id: u64][vote_choice: u8][proposal_bump: u8][flags: u8][proof: 32 bytes per node],
+/// the proof leading from the voter's leaf to the root (see `verify_member_proof`).
+/// Such votes can't be delegated.
+///
+/// `vote_choice` is a `VoteChoice`, or on a Poll proposal the 1-based option voted for.
+/// A Poll is decided by the first option to reach the threshold, or by plurality at expiry.
+pub fn process_vote_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if data.len() < 10 {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let VoteAccounts { voter, multisig, proposal_state, vote_state, multisig_config, system_program, vote_receipt } =
+        VoteAccounts::try_from(accounts)?;
 
-    let proposal_id = unsafe { *(data.as_ptr() as *const u64) };
+    let proposal_id = read_proposal_id(data);
 
+    // Checked against the proposal once it is loaded, since Polls take their own options
     let vote_choice = data[8];
     let bump = data[9];
+    // Optional flag byte, see `VOTE_FLAG_CHANGE` and `VOTE_FLAG_DRY_RUN`
+    let flags = data.get(10).copied().unwrap_or(0);
 
-    // Validate vote choice
-    if vote_choice > 3 {
+    if flags & !(VOTE_FLAG_CHANGE | VOTE_FLAG_DRY_RUN) != 0 {
         return Err(ProgramError::InvalidInstructionData);
-    };
+    }
 
-    let program_owned_accounts = [multisig, proposal_state, multisig_config];
-    for accounts in program_owned_accounts {
-        if accounts.owner() != &crate::ID {
-            return Err(ProgramError::IncorrectProgramId);
+    let change_vote = flags & VOTE_FLAG_CHANGE != 0;
+    let dry_run = flags & VOTE_FLAG_DRY_RUN != 0;
+
+    // The multisig records decided outcomes; the config is only read, so it may be passed
+    // read-only. A dry run writes nothing, so it can be simulated with every account read-only.
+    if !dry_run {
+        for account in [multisig, proposal_state, vote_state, vote_receipt] {
+            require_writable(account)?;
         }
     }
 
     // Load account data
-    let multisig_data = Multisig::from_account_info(multisig)?;
+    let multisig_data = Multisig::from_account_info_mut(multisig)?;
     let proposal_data = ProposalState::from_account_info(proposal_state)?;
-    let multisig_config_data = MultisigConfig::from_account_info(multisig_config)?;
+    let multisig_config_data = &MultisigConfig::load(multisig_config)?;
+
+    // A members root proposal takes the change flag and then the voter's membership proof;
+    // other proposals take at most the change flag and delegator byte
+    let proof = if proposal_data.uses_members_root() {
+        let proof = data.get(11..).unwrap_or_default();
+
+        if data.len() < 11 || proof.len() % PROOF_NODE_LEN != 0 || proof.len() / PROOF_NODE_LEN > MAX_MEMBERS_PROOF_DEPTH {
+            log!("Error: Expected a membership proof of up to {} nodes", MAX_MEMBERS_PROOF_DEPTH);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        proof
+    } else {
+        if data.len() > 12 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        &[][..]
+    };
+
+    // The config must be this multisig's own, or another config could lift its pause
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
 
-    // Check if voter is a member of the multisig
-    // let mut voter_index = None;
-    // for i in 0..multisig_data.num_members as usize {
-    //    if multisig_data.members[i] == *voter.key() {
-    //         voter_index = Some(i);
-    //         break;
-    //    }
-    // };
+    if let Err(error) = multisig_config_data.require_not_paused() {
+        log!("Error: Voting is paused");
+        return Err(error);
+    }
+
+    let member: Pubkey = if proposal_data.uses_members_root() {
+        // Proven against the root snapshotted at creation, so a later root doesn't change
+        // who votes on this proposal. Delegations and roles only apply to `members`.
+        if !verify_member_proof(&proposal_data.members_root, voter.key(), proof) {
+            log!("Error: Membership proof doesn't lead to the members root");
+            return Err(MultisigError::NotAMember.into());
+        }
+
+        *voter.key()
+    } else {
+        let voter_member_index = multisig_data
+            .member_index(voter.key())
+            .ok_or(MultisigError::NotAMember)?;
+
+        // Optional delegator byte: index in `Multisig.members` of the member whose vote the
+        // signer casts. A member who delegated can't vote directly, so no vote counts twice.
+        let member = match data.get(11) {
+            Some(&delegator_index) => {
+                let delegator_index = delegator_index as usize;
+
+                if delegator_index >= multisig_data.num_members as usize
+                    || &multisig_data.delegations[delegator_index] != voter.key()
+                {
+                    log!("Error: Voter holds no delegation from member {}", delegator_index);
+                    return Err(MultisigError::NotDelegated.into());
+                }
+
+                multisig_data.members[delegator_index]
+            },
+            None => {
+                if multisig_data.delegations[voter_member_index] != Pubkey::default() {
+                    log!("Error: Voter has delegated their vote");
+                    return Err(MultisigError::VoteDelegated.into());
+                }
+
+                *voter.key()
+            },
+        };
 
-    // let voter_index = voter_index.ok_or(ProgramError::InvalidAccountData)?;
-    // log!("Voter found at index: {}", voter_index);
+        // The role belongs to the member whose vote is counted, so delegating can't lend a
+        // vote to a member without it
+        if !multisig_data.has_role(&member, ROLE_CAN_VOTE) {
+            log!("Error: Member {} is not allowed to vote", &member);
+            return Err(MultisigError::MissingPermission.into());
+        }
 
-    let voter_index = (0..multisig_data.num_members as usize)
-        .find(|&i| multisig_data.members[i] == *voter.key())
-        .ok_or(ProgramError::InvalidAccountData)?;
+        member
+    };
 
     let proposal_seed = [
         b"proposal",
@@ -91,118 +325,317 @@ pub fn process_vote_instruction(accounts: &[AccountInfo], data: &[u8]) -> Progra
         return Err(ProgramError::InvalidAccountData);
     }
 
-    match proposal_data.result {
+    match proposal_data.status()? {
         ProposalStatus::Active => {},
-        _ => return Err(ProgramError::InvalidAccountData), //Proposal is not active
+        _ => return Err(MultisigError::ProposalNotActive.into()),
     };
 
+    // Rejects 0 ("not voted") and anything past the last choice the proposal offers
+    proposal_data.validate_choice(vote_choice)?;
+
     //Check wether the proposal has expired
-    let current_time = Clock::get()?.unix_timestamp as u64;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
 
-    if current_time > proposal_data.expiry {
-        log!("Proposal has expired");
+    check_voting_window(proposal_data, current_time)?;
+
+    let active_member_count = proposal_data.active_member_count as usize;
+
+    if active_member_count > MAX_MEMBERS {
         return Err(ProgramError::InvalidAccountData);
+    }
+
+    // A threshold of 0 would let the first vote rubber-stamp the proposal, and one above the
+    // members' combined weight could never be met
+    let required_votes = proposal_data.effective_required_votes(multisig_config_data)?;
+
+    if required_votes == 0 || required_votes > proposal_data.total_weight(multisig_data) {
+        log!("Error: Invalid threshold of {} votes", required_votes);
+        return Err(MultisigError::InvalidThreshold.into());
+    }
+
+    // Only members in the proposal's snapshot may vote, so members added after the
+    // proposal was created can't. The vote itself is keyed by pubkey in its receipt.
+    // Members root proposals proved membership above and keep no voted bitmap.
+    let active_member_index = match proposal_data.uses_members_root() {
+        true => None,
+        false => Some(proposal_data.active_member_index(&member).ok_or(MultisigError::NotAMember)?),
     };
 
-    if !proposal_data.active_members.contains(voter.key()) {
-        return Err(ProgramError::InvalidAccountData);
+    // Vote states created before the voted bitmap existed grow into the current layout
+    if !dry_run && vote_state.owner() == &crate::ID && vote_state.data_len() == VoteState::LEGACY_LEN {
+        let required_lamports = Rent::get()?.minimum_balance(VoteState::LEN);
+        if required_lamports > vote_state.lamports() {
+            Transfer {
+                from: voter,
+                to: vote_state,
+                lamports: required_lamports - vote_state.lamports(),
+            }.invoke()?;
+        }
+
+        log!("Reallocating VoteState Account");
+        vote_state.realloc(VoteState::LEN, true)?;
     }
 
+    // The vote_state PDA has its own seeds and canonical bump, independent of the proposal
+    // bump. The bump search only runs once, for the vote that creates the account; later
+    // votes verify the address with the bump stored in it.
+    let vote_state_bump = if vote_state.owner() == &crate::ID {
+        let vote_state_data = read_vote_state(vote_state);
+        let vote_state_bump = vote_state_data.bump;
+
+        // A proposal closed without its vote state leaves it behind at the same address;
+        // its votes must not carry over to a new proposal reusing the id
+        if vote_state_data.proposal_nonce != proposal_data.nonce {
+            log!("Error: Vote state belongs to an earlier proposal {}", proposal_id);
+            return Err(MultisigError::StaleVoteAccount.into());
+        }
+
+        let vote_state_seed = [
+            b"vote_state".as_slice(),
+            multisig.key().as_slice(),
+            &proposal_id.to_le_bytes(),
+            &[vote_state_bump],
+        ];
+        let vote_state_pda = pubkey::checked_create_program_address(&vote_state_seed, &crate::ID)?;
+
+        if &vote_state_pda != vote_state.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        vote_state_bump
+    } else {
+        let (vote_state_pda, vote_state_bump) = pubkey::find_program_address(
+            &[b"vote_state", multisig.key().as_ref(), &proposal_id.to_le_bytes()],
+            &crate::ID,
+        );
+
+        if vote_state_pda != *vote_state.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        vote_state_bump
+    };
 
-    let (vote_state_pda, _bump) = pubkey::find_program_address(
-        &[b"vote_state", multisig.key().as_ref(), &proposal_id.to_le_bytes(), &[bump]],
+    let (receipt_pda, receipt_bump) = pubkey::find_program_address(
+        &[b"receipt", proposal_state.key().as_ref(), member.as_ref()],
         &crate::ID,
     );
 
-    if vote_state_pda != *vote_state.key() {
+    if receipt_pda != *vote_receipt.key() {
         return Err(ProgramError::InvalidAccountData);
     }
 
-    // Handle vote state account creation or update
-    if vote_state.owner() != &crate::ID {
-        let minimum_balance = Rent::get()?.minimum_balance(VoteState::LEN);
-        let vote_state_space = VoteState::LEN as u64;
-
-        // Create vote state account if it doesn't exist
-        log!("Creating VoteState Account");
-
-        CreateAccount {
-            from: voter,
-            to: vote_state,
-            lamports: minimum_balance,
-            space: vote_state_space,
-            owner: &crate::ID,
-        }.invoke()?;
-
-        // Initialize vote state
-        let vote_state_data = VoteState::from_account_info(vote_state)?;
-        vote_state_data.has_permission = true;
-        vote_state_data.vote_count = 1;
-        vote_state_data.bump = bump;
-
-    } else {
-        // Update existing vote state
-        let vote_state_data = VoteState::from_account_info(vote_state)?;
+    // An existing receipt holds the member's previous choice. Receipts are keyed by the
+    // member, not the signer, so a member's vote is recorded once whether it was cast
+    // directly or by a delegate.
+    let delegated = &member != voter.key();
+    let previous_vote = if vote_receipt.owner() == &crate::ID {
+        let receipt_data = VoteReceipt::from_account_info(vote_receipt)?;
 
-        if !vote_state_data.has_permission {
+        if receipt_data.voter != member {
             return Err(ProgramError::InvalidAccountData);
-        };
+        }
+
+        if receipt_data.proposal_nonce != proposal_data.nonce {
+            log!("Error: Vote receipt belongs to an earlier proposal {}", proposal_id);
+            return Err(MultisigError::StaleVoteAccount.into());
+        }
+
+        proposal_data.validate_choice(receipt_data.choice).map_err(|_| ProgramError::InvalidAccountData)?;
+
+        // Only the path that cast the vote may change it: a delegate can't override the
+        // member's own vote, nor the member one its delegate cast
+        if (receipt_data.delegated == 1) != delegated {
+            log!("Error: Member {} already voted through another path", &member);
+            return Err(MultisigError::AlreadyVoted.into());
+        }
+
+        Some(receipt_data.choice)
+    } else {
+        None
+    };
 
-        // Check if already voted (assuming we want to allow vote changes)
-        if vote_state_data.votes[voter_index] != 0 {
+    match previous_vote {
+        Some(_) if change_vote && multisig_config_data.allow_vote_change != 1 => {
+            log!("Error: This multisig doesn't allow changing a vote");
+            return Err(MultisigError::AlreadyVoted.into());
+        },
+        Some(previous_vote) if change_vote => {
+            log!("Changing vote from {} to {}", previous_vote, vote_choice);
+        },
+        Some(_) => {
+            // Changing a vote requires the change flag
             log!("Voter has already voted");
-            return Err(ProgramError::InvalidAccountData);
-        };
+            return Err(MultisigError::AlreadyVoted.into());
+        },
+        None if change_vote => {
+            log!("Voter has no vote to change");
+            return Err(MultisigError::NoVoteToChange.into());
+        },
+        None => {},
+    };
 
-        vote_state_data.vote_count += 1;
+    // A first vote creates the receipt, and possibly the vote state, through the system program
+    if previous_vote.is_none() && system_program.key() != &pinocchio_system::ID {
+        log!("Error: The system program account is required to create vote accounts");
+        return Err(ProgramError::IncorrectProgramId);
     }
 
-    proposal_data.votes[voter_index] = vote_choice;
+    // A dry run works on a copy of the vote state, as a vote would find it
+    let mut preview_vote_state;
+
+    // Handle vote state account creation or update
+    let vote_state_data = if dry_run {
+        preview_vote_state = match vote_state.owner() == &crate::ID {
+            true => read_vote_state(vote_state),
+            false => VoteState { has_permission: 1, ..VoteState::zeroed() },
+        };
+        &mut preview_vote_state
+    } else {
+        if vote_state.owner() != &crate::ID {
+            // Create vote state account if it doesn't exist
+            log!("Creating VoteState Account");
+
+            let proposal_id_bytes = proposal_id.to_le_bytes();
+            let vote_state_bump_bytes = [vote_state_bump];
+            let vote_state_signer_seeds = [
+                Seed::from(b"vote_state"),
+                Seed::from(multisig.key()),
+                Seed::from(&proposal_id_bytes),
+                Seed::from(&vote_state_bump_bytes),
+            ];
+
+            create_pda_account(voter, vote_state, VoteState::LEN, &vote_state_signer_seeds)?;
+
+            // Initialize vote state
+            let vote_state_data = VoteState::from_account_info(vote_state)?;
+            vote_state_data.has_permission = 1;
+            vote_state_data.bump = vote_state_bump;
+            vote_state_data.proposal_nonce = proposal_data.nonce;
+        }
 
-    let mut for_votes = 0;
-    let mut against_votes = 0;
-    let mut abstain_votes = 0;
-    let mut total_votes = 0;
+        VoteState::from_account_info(vote_state)?
+    };
 
-    let active_member_count = multisig_data.num_members.min(10) as usize; // Adjust size as needed
+    if vote_state_data.has_permission == 0 {
+        return Err(ProgramError::InvalidAccountData);
+    };
 
-    for i in 0..active_member_count {
-        match proposal_data.votes[i] {
-            1 => {
-                for_votes += 1;
-                total_votes += 1;
-            },
-            2 => {
-                against_votes += 1;
-                total_votes += 1;
-            },
-            3 => {
-                abstain_votes += 1;
-                total_votes += 1;
-            },
-            _ => {}, // Not voted
+    // The bitmap and the receipts must agree: a member without a receipt can't have voted
+    if previous_vote.is_none() && active_member_index.is_some_and(|i| vote_state_data.has_voted(i)) {
+        log!("Voter has already voted");
+        return Err(MultisigError::AlreadyVoted.into());
+    }
+
+    if previous_vote.is_none() {
+        vote_state_data.vote_count = vote_state_data.vote_count
+            .checked_add(1)
+            .ok_or(MultisigError::ArithmeticOverflow)?;
+        if let Some(active_member_index) = active_member_index {
+            vote_state_data.mark_voted(active_member_index)?;
         }
     }
 
-    log!("Vote counts : For: {}, Against: {}, Abstain: {}, Total: {}", for_votes, against_votes, abstain_votes, total_votes);
+    if previous_vote.is_none() && !dry_run {
+        // Credited to the member the vote is recorded for, once per proposal; changing the
+        // vote later doesn't count again
+        multisig_data.record_participation(&member)?;
 
-    //Check if proposal should succeed or fail
+        log!("Creating VoteReceipt Account");
+
+        let receipt_bump_bytes = [receipt_bump];
+        let receipt_signer_seeds = [
+            Seed::from(b"receipt"),
+            Seed::from(proposal_state.key()),
+            Seed::from(&member),
+            Seed::from(&receipt_bump_bytes),
+        ];
+
+        create_pda_account(voter, vote_receipt, VoteReceipt::LEN, &receipt_signer_seeds)?;
+
+        let receipt_data = VoteReceipt::from_account_info(vote_receipt)?;
+        receipt_data.voter = member;
+        receipt_data.bump = receipt_bump;
+        receipt_data.proposal_nonce = proposal_data.nonce;
+    }
 
-    if for_votes >= multisig_config_data.min_threshold {
-        proposal_data.result = ProposalStatus::Succeeded;
-        log!("Proposal succeeded");
-    } else if against_votes >= multisig_config_data.min_threshold {
-        proposal_data.result = ProposalStatus::Failed;
-        log!("Proposal failed");
-    } else if current_time > proposal_data.expiry {
-        proposal_data.result = ProposalStatus::Cancelled;
-        log!("Proposal cancelled due to expiry");
+    if !dry_run {
+        let receipt_data = VoteReceipt::from_account_info(vote_receipt)?;
+        receipt_data.choice = vote_choice;
+        receipt_data.timestamp = current_time;
+        receipt_data.delegated = delegated as u8;
+
+        proposal_data.last_voter = *voter.key();
+        proposal_data.last_vote_slot = clock.slot;
+    }
+
+    // A dry run tallies a copy of the proposal, so the account is never written
+    let mut preview_proposal;
+    let tallied_proposal = if dry_run {
+        preview_proposal = *proposal_data;
+        &mut preview_proposal
     } else {
-        proposal_data.result = ProposalStatus::Active;
-        log!("Proposal remains active");
+        &mut *proposal_data
+    };
+
+    // Update the cached tallies instead of rescanning every receipt. A changed vote is
+    // taken out of its previous tally first so it is never counted twice.
+    let weight = tallied_proposal.weight_of(&member, multisig_data);
+
+    if let Some(previous_vote) = previous_vote {
+        let previous_tally = tallied_proposal.choice_tally_mut(previous_vote)?;
+        *previous_tally = previous_tally.saturating_sub(weight);
+    }
+    let tally = tallied_proposal.choice_tally_mut(vote_choice)?;
+    *tally = tally.checked_add(weight).ok_or(MultisigError::ArithmeticOverflow)?;
+
+    //Check if proposal should succeed or fail
+    let (projection, winning_option) =
+        project_outcome(tallied_proposal, multisig_data, multisig_config_data, required_votes, current_time)?;
+    let (for_votes, against_votes, abstain_votes) =
+        (tallied_proposal.for_votes, tallied_proposal.against_votes, tallied_proposal.abstain_votes);
+    let total_votes = tallied_proposal.total_votes()?;
+
+    log!("Vote counts : For: {}, Against: {}, Abstain: {}, Total: {}", for_votes, against_votes, abstain_votes, total_votes);
+    log!(
+        "Participation: {} of {} members voted",
+        tallied_proposal.members_voted(vote_state_data),
+        tallied_proposal.members_eligible()
+    );
+
+    // Only a decided outcome writes the status; an undecided vote leaves the proposal Active
+    match projection {
+        ProposalStatus::Succeeded => log!("Proposal succeeded"),
+        ProposalStatus::Failed => log!("Proposal failed"),
+        ProposalStatus::Cancelled => log!("Proposal cancelled due to expiry"),
+        _ => {
+            if total_votes < proposal_data.quorum {
+                log!("Quorum not reached: {} of {} votes", total_votes, proposal_data.quorum);
+            }
+        }
+    };
+
+    if dry_run {
+        log!("Dry run: the vote would leave proposal {} with status {}", proposal_id, projection as u8);
+        return Ok(());
     }
 
-    log!("Vote processed successfully for user: {}", voter.key());
+    apply_projection(proposal_data, projection, winning_option, current_time)?;
+    multisig_data.record_outcome(projection)?;
+
+    VoteCast {
+        proposal_id,
+        voter: member,
+        vote_choice,
+        for_votes,
+        against_votes,
+        abstain_votes,
+        status: proposal_data.result,
+    }.emit();
+    sol_log_data(&[&proposal_data.serialize_for_log()]);
+
+    log!("Vote processed successfully for user: {}", &member);
 
     Ok(())
 }
@@ -211,6 +644,9 @@ pub fn process_vote_instruction(accounts: &[AccountInfo], data: &[u8]) -> Progra
 
 #[cfg(test)]
 mod testing_process_vote_instruction {
+    use core::mem::offset_of;
+
+    use bytemuck::Zeroable;
     use solana_sdk::native_token::LAMPORTS_PER_SOL;
 
     use super::*;
@@ -247,12 +683,15 @@ mod testing_process_vote_instruction {
         println!("Proposal PDA: {}, Bump: {}", proposal_state_pda, proposal_bump);
 
         let (vote_state_pda, vote_bump) = Pubkey::find_program_address(
-            &[b"vote_state", MULTISIG.as_ref(), &proposal_id.to_le_bytes(), &[proposal_bump]],
+            &[b"vote_state", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
             &ID,
         );
 
         println!("Vote State PDA: {}, Bump: {}", vote_state_pda, vote_bump);
 
+        let vote_receipt_pda = receipt_pda(&proposal_state_pda, &USER);
+        println!("Vote Receipt PDA: {}", vote_receipt_pda);
+
         let (multisig_config_pda, _config_bump) = Pubkey::find_program_address(
             &[b"multisig_config", MULTISIG.as_ref()],
             &ID,
@@ -267,12 +706,14 @@ mod testing_process_vote_instruction {
         println!("User lamports: {}", user_account.lamports);
         println!("User owner: {}", user_account.owner);
         
+        let members_offset = offset_of!(Multisig, members);
         let mut multisig_data = vec![0u8; Multisig::LEN];
-        multisig_data[0] = 2; 
-        multisig_data[1..33].copy_from_slice(USER.as_ref()); 
+        multisig_data[offset_of!(Multisig, version)] = Multisig::VERSION;
+        multisig_data[offset_of!(Multisig, num_members)] = 2;
+        multisig_data[members_offset..members_offset + 32].copy_from_slice(USER.as_ref());
 
         let dummy_member = Pubkey::new_unique();
-        multisig_data[33..65].copy_from_slice(dummy_member.as_ref());
+        multisig_data[members_offset + 32..members_offset + 64].copy_from_slice(dummy_member.as_ref());
         let multisig_account = Account::new_data(
             1 * LAMPORTS_PER_SOL,
             &multisig_data,
@@ -284,16 +725,17 @@ mod testing_process_vote_instruction {
         println!("Multisig owner: {}", multisig_account.owner);
         println!("Multisig lamports: {}", multisig_account.lamports);
         println!("Multisig data length: {}", multisig_account.data.len());
-        println!("Number of members: {}", multisig_data[0]);
+        println!("Number of members: {}", multisig_data[offset_of!(Multisig, num_members)]);
 
-        let mut proposal_data = vec![0u8; ProposalState::LEN];
-        proposal_data[0..8].copy_from_slice(&proposal_id.to_le_bytes()); 
-        proposal_data[8] = 0; 
+        let mut proposal_data = vec![0u8; ProposalState::BASE_LEN];
+        proposal_data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&proposal_id.to_le_bytes());
+        proposal_data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
         
         let future_time: u64 = 9999999999;
-        proposal_data[16..24].copy_from_slice(&future_time.to_le_bytes());
+        proposal_data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&future_time.to_le_bytes());
         
-        let active_members_offset = 50; 
+        let active_members_offset = offset_of!(ProposalState, active_members);
+        proposal_data[offset_of!(ProposalState, active_member_count)] = 1;
         proposal_data[active_members_offset..active_members_offset + 32]
             .copy_from_slice(USER.as_ref());
             
@@ -309,9 +751,9 @@ mod testing_process_vote_instruction {
         println!("Proposal state lamports: {}", proposal_state_account.lamports);
         println!("Proposal state data length: {}", proposal_state_account.data.len());
         
-        let stored_proposal_id = u64::from_le_bytes(proposal_data[0..8].try_into().unwrap());
-        let stored_status = proposal_data[8];
-        let stored_expiry = u64::from_le_bytes(proposal_data[16..24].try_into().unwrap());
+        let stored_proposal_id = u64::from_le_bytes(proposal_data[offset_of!(ProposalState, proposal_id)..][..8].try_into().unwrap());
+        let stored_status = proposal_data[offset_of!(ProposalState, result)];
+        let stored_expiry = u64::from_le_bytes(proposal_data[offset_of!(ProposalState, expiry)..][..8].try_into().unwrap());
 
         println!("Stored proposal ID: {}", stored_proposal_id);
         println!("Stored proposal status: {}", stored_status);
@@ -329,7 +771,10 @@ mod testing_process_vote_instruction {
         println!("Vote state data length: {}", vote_state_account.data.len());
 
         let mut multisig_config_data = vec![0u8; MultisigConfig::LEN];
-        multisig_config_data[0..8].copy_from_slice(&1u64.to_le_bytes());
+
+        multisig_config_data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+        multisig_config_data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&1u64.to_le_bytes());
+        multisig_config_data[offset_of!(MultisigConfig, bump)] = config_bump();
         let multisig_config_account = Account::new_data(
             1 * LAMPORTS_PER_SOL,
             &multisig_config_data,
@@ -342,7 +787,7 @@ mod testing_process_vote_instruction {
         println!("Config lamports: {}", multisig_config_account.lamports);
         println!("Config data length: {}", multisig_config_account.data.len());
         
-        let min_threshold = u64::from_le_bytes(multisig_config_data[0..8].try_into().unwrap());
+        let min_threshold = u64::from_le_bytes(multisig_config_data[offset_of!(MultisigConfig, min_threshold)..][..8].try_into().unwrap());
         println!("Min threshold: {}", min_threshold);
 
         let ix_accounts = vec![
@@ -352,9 +797,10 @@ mod testing_process_vote_instruction {
             AccountMeta::new(vote_state_pda, false),         // vote_state
             AccountMeta::new(multisig_config_pda, false),    // multisig_config
             AccountMeta::new_readonly(system_program_id, false), // system_program
+            AccountMeta::new(vote_receipt_pda, false),       // vote_receipt
         ];
 
-        let mut data = vec![1u8]; // Instruction discriminator for vote
+        let mut data = vec![3u8]; // Instruction discriminator for vote
         data.extend_from_slice(&proposal_id.to_le_bytes()); 
         data.push(1); // Vote choice (1(dor))
         data.push(proposal_bump); 
@@ -376,17 +822,23 @@ mod testing_process_vote_instruction {
             (vote_state_pda, vote_state_account),
             (multisig_config_pda, multisig_config_account),
             (system_program_id, system_account),
+            (vote_receipt_pda, Account::new(0, 0, &system_program_id)),
         ];
 
         println!("PROCESSING INSTRUCTION");
         
         // Process and validate the instruction
-        mollusk.process_and_validate_instruction(
+        let result = mollusk.process_and_validate_instruction(
             &instruction,
             &tx_accounts,
             &[Check::success()],
         );
 
+        // The vote went through the entrypoint dispatcher and was recorded for USER
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(receipt_choice(&result.resulting_accounts, &proposal_state_pda, &USER), 1);
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+
         println!("INSTRUCTION PROCESSING COMPLETE");
         println!("TEST COMPLETE");
     }
@@ -406,7 +858,7 @@ mod testing_process_vote_instruction {
         println!("Proposal PDA: {}, Bump: {}", proposal_state_pda, proposal_bump);
 
         let (vote_state_pda, vote_bump) = Pubkey::find_program_address(
-            &[b"vote_state", MULTISIG.as_ref(), &proposal_id.to_le_bytes(), &[proposal_bump]],
+            &[b"vote_state", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
             &ID,
         );
         println!("Vote State PDA: {}, Bump: {}", vote_state_pda, vote_bump);
@@ -422,11 +874,13 @@ mod testing_process_vote_instruction {
         let user_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id);
         println!("User Account - Pubkey: {}, Lamports: {}", USER, user_account.lamports);
 
+        let members_offset = offset_of!(Multisig, members);
         let mut multisig_data = vec![0u8; Multisig::LEN];
-        multisig_data[0] = 2;
-        multisig_data[1..33].copy_from_slice(USER.as_ref());
+        multisig_data[offset_of!(Multisig, version)] = Multisig::VERSION;
+        multisig_data[offset_of!(Multisig, num_members)] = 2;
+        multisig_data[members_offset..members_offset + 32].copy_from_slice(USER.as_ref());
         let dummy_member = Pubkey::new_unique();
-        multisig_data[33..65].copy_from_slice(dummy_member.as_ref()); 
+        multisig_data[members_offset + 32..members_offset + 64].copy_from_slice(dummy_member.as_ref());
         
         let wrong_owner = Pubkey::new_unique(); 
         let multisig_account = Account::new_data(
@@ -437,17 +891,18 @@ mod testing_process_vote_instruction {
         
         println!("Multisig Account - Expected Owner: {}, Actual Owner: {}", ID, wrong_owner);
         println!("Multisig Account - Pubkey: {}, Lamports: {}", MULTISIG, multisig_account.lamports);
-        println!("Multisig Members: {} (count: {})", USER, multisig_data[0]);
+        println!("Multisig Members: {} (count: {})", USER, multisig_data[offset_of!(Multisig, num_members)]);
         
         // Create valid proposal account (owned by correct program)
-        let mut proposal_data = vec![0u8; ProposalState::LEN];
-        proposal_data[0..8].copy_from_slice(&proposal_id.to_le_bytes()); // proposal_id
-        proposal_data[8] = 0; // status = Active (ProposalStatus::Active)
+        let mut proposal_data = vec![0u8; ProposalState::BASE_LEN];
+        proposal_data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&proposal_id.to_le_bytes()); // proposal_id
+        proposal_data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
         let future_time = 9999999999u64; // Far future expiry
-        proposal_data[16..24].copy_from_slice(&future_time.to_le_bytes());
+        proposal_data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&future_time.to_le_bytes());
         
         // Set active members - USER is an active member
-        let active_members_offset = 50; 
+        let active_members_offset = offset_of!(ProposalState, active_members);
+        proposal_data[offset_of!(ProposalState, active_member_count)] = 1;
         proposal_data[active_members_offset..active_members_offset + 32]
             .copy_from_slice(USER.as_ref());
             
@@ -465,7 +920,9 @@ mod testing_process_vote_instruction {
 
         // Create valid multisig config account
         let mut multisig_config_data = vec![0u8; MultisigConfig::LEN];
-        multisig_config_data[0..8].copy_from_slice(&1u64.to_le_bytes()); // min_threshold = 1
+        multisig_config_data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+        multisig_config_data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&1u64.to_le_bytes()); // min_threshold = 1
+        multisig_config_data[offset_of!(MultisigConfig, bump)] = config_bump();
         let multisig_config_account = Account::new_data(
             1 * LAMPORTS_PER_SOL,
             &multisig_config_data,
@@ -482,10 +939,11 @@ mod testing_process_vote_instruction {
             AccountMeta::new(vote_state_pda, false),         // vote_state
             AccountMeta::new(multisig_config_pda, false),    // multisig_config
             AccountMeta::new_readonly(system_program_id, false), // system_program
+            AccountMeta::new(receipt_pda(&proposal_state_pda, &USER), false), // vote_receipt
         ];
 
         // Create instruction data
-        let mut data = vec![1u8]; // Instruction discriminator for vote
+        let mut data = vec![3u8]; // Instruction discriminator for vote
         data.extend_from_slice(&proposal_id.to_le_bytes()); // proposal_id (8 bytes)
         data.push(1); // vote_choice = 1 (For)
         data.push(proposal_bump); // bump for PDA derivation
@@ -508,6 +966,7 @@ mod testing_process_vote_instruction {
             (vote_state_pda, vote_state_account),
             (multisig_config_pda, multisig_config_account),
             (system_program_id, system_account),
+            (receipt_pda(&proposal_state_pda, &USER), Account::new(0, 0, &system_program_id)),
         ];
 
         println!("Processing instruction - expecting failure due to wrong multisig owner...");
@@ -534,8 +993,8 @@ mod testing_process_vote_instruction {
             &[b"proposal", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
             &ID,
         );
-        let (vote_state_pda, _) = Pubkey::find_program_address(
-            &[b"vote_state", MULTISIG.as_ref(), &proposal_id.to_le_bytes(), &[proposal_bump]],
+        let (vote_state_pda, vote_state_bump) = Pubkey::find_program_address(
+            &[b"vote_state", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
             &ID,
         );
         let (multisig_config_pda, _) = Pubkey::find_program_address(
@@ -549,20 +1008,22 @@ mod testing_process_vote_instruction {
 
         let multisig_data = {
             let mut data = vec![0u8; Multisig::LEN];
-            data[0] = 2; // member count
-            data[1..33].copy_from_slice(USER.as_ref());
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = 2; // member count
+            data[offset_of!(Multisig, members)..][..32].copy_from_slice(USER.as_ref());
+            data[offset_of!(Multisig, members) + 32..][..32].copy_from_slice(Pubkey::new_unique().as_ref());
             data
         };
         let multisig_account = Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap();
 
         let proposal_data = {
-            let mut data = vec![0u8; ProposalState::LEN];
-            data[0..8].copy_from_slice(&proposal_id.to_le_bytes()); // ID
-            data[8] = 0; // Active
-            data[16..24].copy_from_slice(&9999999999u64.to_le_bytes()); // deadline
-            data[24] = 1; // USER already voted
-            let member_offset = 50;
-            data[member_offset..member_offset + 32].copy_from_slice(USER.as_ref()); // member
+            let mut data = vec![0u8; ProposalState::BASE_LEN];
+            data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&proposal_id.to_le_bytes()); // ID
+            data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&9999999999u64.to_le_bytes()); // deadline
+            data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
+            data[offset_of!(ProposalState, active_member_count)] = 1;
+            data[offset_of!(ProposalState, active_members)..][..32].copy_from_slice(USER.as_ref()); // member
+            data[offset_of!(ProposalState, for_votes)..][..8].copy_from_slice(&1u64.to_le_bytes());
             data
         };
 
@@ -570,31 +1031,35 @@ mod testing_process_vote_instruction {
 
         let vote_state_data = {
             let mut data = vec![0u8; VoteState::LEN];
-            data[0] = 1; // has_permission
-            data[8..16].copy_from_slice(&1u64.to_le_bytes()); // vote count
-            data[16] = proposal_bump; // bump
-            data[17] = 1; // USER already voted
+            data[offset_of!(VoteState, has_permission)] = 1;
+            data[offset_of!(VoteState, vote_count)..][..8].copy_from_slice(&1u64.to_le_bytes());
+            data[offset_of!(VoteState, bump)] = vote_state_bump;
             data
         };
 
         let vote_state_account = Account::new_data(1 * LAMPORTS_PER_SOL, &vote_state_data, &ID).unwrap();
 
+        let vote_receipt_pda = receipt_pda(&proposal_state_pda, &USER);
+        let vote_receipt_account = receipt_account(USER, 1); // USER already voted
+
         let config_data = {
             let mut data = vec![0u8; MultisigConfig::LEN];
-            data[0..8].copy_from_slice(&1u64.to_le_bytes()); // threshold = 1
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&2u64.to_le_bytes()); // threshold = 2
+            data[offset_of!(MultisigConfig, bump)] = config_bump();
             data
         };
         let config_account = Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap();
 
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.push(2); // vote choice: Against
+        data.push(proposal_bump);
+
         // Attempt second vote (should fail)
         let instruction = Instruction::new_with_bytes(
             ID,
-            &[
-                1, // vote instruction
-                proposal_id as u8,
-                2, // vote choice: Against
-                proposal_bump,
-            ],
+            &data,
             vec![
                 AccountMeta::new(USER, true),
                 AccountMeta::new(MULTISIG, false),
@@ -602,6 +1067,7 @@ mod testing_process_vote_instruction {
                 AccountMeta::new(vote_state_pda, false),
                 AccountMeta::new(multisig_config_pda, false),
                 AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(vote_receipt_pda, false),
             ],
         );
 
@@ -611,7 +1077,8 @@ mod testing_process_vote_instruction {
             (proposal_state_pda, proposal_state_account),
             (vote_state_pda, vote_state_account),
             (multisig_config_pda, config_account),
-            (system_program_id, Account::default()),
+            (system_program_id, system_account),
+            (vote_receipt_pda, vote_receipt_account),
         ];
 
         println!("Attempting second vote should fail...");
@@ -619,10 +1086,1507 @@ mod testing_process_vote_instruction {
         mollusk.process_and_validate_instruction(
             &instruction,
             &tx_accounts,
-            &[Check::err(ProgramError::InvalidAccountData)],
+            &[Check::err(ProgramError::Custom(MultisigError::AlreadyVoted as u32))],
         );
 
         println!("✓ Test passed: Duplicate vote correctly prevented.");
 }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_eleventh_member_vote_crosses_threshold() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        let proposal_id = 12345u64;
+        let num_members = 12usize;
+        let threshold = 11u64;
+
+        let members: Vec<Pubkey> = (0..num_members).map(|_| Pubkey::new_unique()).collect();
+        let voter = members[10];
+
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (vote_state_pda, vote_state_bump) = Pubkey::find_program_address(
+            &[b"vote_state", MULTISIG.as_ref(), &proposal_id.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, _) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+        let voter_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id);
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = num_members as u8;
+            let members_offset = offset_of!(Multisig, members);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data
+        };
+        let multisig_account = Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap();
+
+        let proposal_data = {
+            let mut data = vec![0u8; ProposalState::BASE_LEN];
+            data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&proposal_id.to_le_bytes());
+            data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&9999999999u64.to_le_bytes());
+            data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
+            data[offset_of!(ProposalState, active_member_count)] = members.len() as u8;
+            let active_members_offset = offset_of!(ProposalState, active_members);
+            for (i, member) in members.iter().enumerate() {
+                data[active_members_offset + i * 32..active_members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            // The first ten members have already voted For
+            data[offset_of!(ProposalState, for_votes)..][..8].copy_from_slice(&10u64.to_le_bytes());
+            data
+        };
+        let proposal_state_account = Account::new_data(1 * LAMPORTS_PER_SOL, &proposal_data, &ID).unwrap();
+
+        let vote_state_data = {
+            let mut data = vec![0u8; VoteState::LEN];
+            data[offset_of!(VoteState, has_permission)] = 1;
+            data[offset_of!(VoteState, vote_count)..][..8].copy_from_slice(&10u64.to_le_bytes());
+            data[offset_of!(VoteState, bump)] = vote_state_bump;
+            data
+        };
+        let vote_state_account = Account::new_data(1 * LAMPORTS_PER_SOL, &vote_state_data, &ID).unwrap();
+
+        let config_data = {
+            let mut data = vec![0u8; MultisigConfig::LEN];
+            data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+            data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&threshold.to_le_bytes());
+            data[offset_of!(MultisigConfig, bump)] = config_bump();
+            data
+        };
+        let config_account = Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap();
+
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        data.push(1); // Vote choice: For
+        data.push(proposal_bump);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(voter, true),
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new(vote_state_pda, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(receipt_pda(&proposal_state_pda, &voter), false),
+            ],
+        );
+
+        let tx_accounts = vec![
+            (voter, voter_account),
+            (MULTISIG, multisig_account),
+            (proposal_state_pda, proposal_state_account),
+            (vote_state_pda, vote_state_account),
+            (multisig_config_pda, config_account),
+            (system_program_id, system_account),
+            (receipt_pda(&proposal_state_pda, &voter), Account::new(0, 0, &system_program_id)),
+        ];
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::success()],
+        );
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(receipt_choice(&result.resulting_accounts, &proposal_state_pda, &voter), 1);
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+    }
+
+    const PROPOSAL_ID: u64 = 12345;
+
+    fn config_bump() -> u8 {
+        Pubkey::find_program_address(&[b"multisig_config", MULTISIG.as_ref()], &ID).1
+    }
+
+    fn config_data(threshold: u64) -> Vec<u8> {
+        let mut data = vec![0u8; MultisigConfig::LEN];
+        data[offset_of!(MultisigConfig, version)] = MultisigConfig::VERSION;
+        data[offset_of!(MultisigConfig, min_threshold)..][..8].copy_from_slice(&threshold.to_le_bytes());
+        data[offset_of!(MultisigConfig, bump)] = config_bump();
+        data
+    }
+
+    fn config_data_allowing_vote_change(threshold: u64) -> Vec<u8> {
+        let mut data = config_data(threshold);
+        data[offset_of!(MultisigConfig, allow_vote_change)] = 1;
+        data
+    }
+
+    fn receipt_pda(proposal_state_pda: &Pubkey, member: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"receipt", proposal_state_pda.as_ref(), member.as_ref()], &ID).0
+    }
+
+    /// A receipt recording `member`'s vote, owned by the program
+    fn receipt_account(member: Pubkey, choice: u8) -> Account {
+        let mut receipt = VoteReceipt::zeroed();
+        receipt.voter = member.to_bytes();
+        receipt.choice = choice;
+        Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&receipt), &ID).unwrap()
+    }
+
+    fn receipt_choice(accounts: &[(Pubkey, Account)], proposal_state_pda: &Pubkey, member: &Pubkey) -> u8 {
+        let receipt_pda = receipt_pda(proposal_state_pda, member);
+        let receipt_account = &accounts.iter().find(|(key, _)| key == &receipt_pda).unwrap().1;
+        let receipt: VoteReceipt = bytemuck::pod_read_unaligned(&receipt_account.data[..VoteReceipt::LEN]);
+        assert_eq!(receipt.voter, member.to_bytes());
+        receipt.choice
+    }
+
+    /// Builds a vote instruction against a proposal whose recorded votes are `votes`,
+    /// indexed like `members`. Only the receipt of the member voted for is passed.
+    fn vote_setup(
+        members: &[Pubkey],
+        votes: &[u8],
+        config_data: Vec<u8>,
+        voter: Pubkey,
+        vote_choice: u8,
+        flags: &[u8],
+    ) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let (proposal_state_pda, proposal_bump) = Pubkey::find_program_address(
+            &[b"proposal", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (vote_state_pda, vote_state_bump) = Pubkey::find_program_address(
+            &[b"vote_state", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        let (multisig_config_pda, _) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let (system_program_id, system_account) = program::keyed_account_for_system_program();
+
+        let multisig_data = {
+            let mut data = vec![0u8; Multisig::LEN];
+            data[offset_of!(Multisig, version)] = Multisig::VERSION;
+            data[offset_of!(Multisig, num_members)] = members.len() as u8;
+            let members_offset = offset_of!(Multisig, members);
+            for (i, member) in members.iter().enumerate() {
+                data[members_offset + i * 32..members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            data
+        };
+
+        let proposal_data = {
+            let mut data = vec![0u8; ProposalState::BASE_LEN];
+            data[offset_of!(ProposalState, proposal_id)..][..8].copy_from_slice(&PROPOSAL_ID.to_le_bytes());
+            data[offset_of!(ProposalState, expiry)..][..8].copy_from_slice(&9999999999u64.to_le_bytes());
+            data[offset_of!(ProposalState, result)] = ProposalStatus::Active as u8;
+            data[offset_of!(ProposalState, active_member_count)] = members.len() as u8;
+            let active_members_offset = offset_of!(ProposalState, active_members);
+            for (i, member) in members.iter().enumerate() {
+                data[active_members_offset + i * 32..active_members_offset + (i + 1) * 32].copy_from_slice(member.as_ref());
+            }
+            for (choice, field) in [(1, offset_of!(ProposalState, for_votes)), (2, offset_of!(ProposalState, against_votes)), (3, offset_of!(ProposalState, abstain_votes))] {
+                let tally = votes.iter().filter(|&&vote| vote == choice).count() as u64;
+                data[field..][..8].copy_from_slice(&tally.to_le_bytes());
+            }
+            // Snapshotted at creation, as `process_create_proposal` does
+            data[offset_of!(ProposalState, quorum)..][..8].copy_from_slice(&config_data[offset_of!(MultisigConfig, quorum)..][..8]);
+            data
+        };
+
+        let vote_state_data = {
+            let mut data = vec![0u8; VoteState::LEN];
+            data[offset_of!(VoteState, has_permission)] = 1;
+            let vote_count = votes.iter().filter(|&&vote| vote != 0).count() as u64;
+            data[offset_of!(VoteState, vote_count)..][..8].copy_from_slice(&vote_count.to_le_bytes());
+            data[offset_of!(VoteState, bump)] = vote_state_bump;
+            for (i, _) in votes.iter().enumerate().filter(|(_, &vote)| vote != 0) {
+                data[offset_of!(VoteState, voted) + i / 8] |= 1 << (i % 8);
+            }
+            data
+        };
+
+        // A delegated vote is recorded in the delegator's receipt
+        let receipt_member = flags.get(1).map_or(voter, |&delegator_index| members[delegator_index as usize]);
+        let vote_receipt_pda = receipt_pda(&proposal_state_pda, &receipt_member);
+        let vote_receipt_account = match members.iter().position(|member| member == &receipt_member).and_then(|i| votes.get(i)) {
+            Some(&choice) if choice != 0 => receipt_account(receipt_member, choice),
+            _ => Account::new(0, 0, &system_program_id),
+        };
+
+        let mut data = vec![3u8]; // Instruction discriminator for vote
+        data.extend_from_slice(&PROPOSAL_ID.to_le_bytes());
+        data.push(vote_choice);
+        data.push(proposal_bump);
+        data.extend_from_slice(flags);
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(voter, true),
+                AccountMeta::new(MULTISIG, false),
+                AccountMeta::new(proposal_state_pda, false),
+                AccountMeta::new(vote_state_pda, false),
+                AccountMeta::new(multisig_config_pda, false),
+                AccountMeta::new_readonly(system_program_id, false),
+                AccountMeta::new(vote_receipt_pda, false),
+            ],
+        );
+
+        let tx_accounts = vec![
+            (voter, Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id)),
+            (MULTISIG, Account::new_data(1 * LAMPORTS_PER_SOL, &multisig_data, &ID).unwrap()),
+            (proposal_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &proposal_data, &ID).unwrap()),
+            (vote_state_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &vote_state_data, &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, &config_data, &ID).unwrap()),
+            (system_program_id, system_account),
+            (vote_receipt_pda, vote_receipt_account),
+        ];
+
+        (instruction, tx_accounts, proposal_state_pda)
+    }
+
+    #[test]
+    fn test_dry_run_vote_leaves_accounts_unchanged() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // The second For vote would pass the proposal; a dry run only logs that it would
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[1], config_data(2), members[1], 1, &[VOTE_FLAG_DRY_RUN]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+        assert_eq!(result.resulting_accounts, tx_accounts);
+    }
+
+    #[test]
+    fn test_dry_run_vote_with_read_only_accounts() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (mut instruction, tx_accounts, _) = vote_setup(&members, &[1], config_data(2), members[1], 1, &[VOTE_FLAG_DRY_RUN]);
+        for account in instruction.accounts.iter_mut().skip(1) {
+            account.is_writable = false;
+        }
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+        assert_eq!(result.resulting_accounts, tx_accounts);
+
+        // The same vote for real needs the accounts it writes
+        instruction.data[11] = 0;
+        mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::err(ProgramError::InvalidAccountData)]);
+    }
+
+    #[test]
+    fn test_dry_run_first_vote_does_not_create_vote_state() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[VOTE_FLAG_DRY_RUN]);
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        tx_accounts[3].1 = Account::new(0, 0, &system_program_id);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+        assert_eq!(result.resulting_accounts, tx_accounts);
+    }
+
+    #[test]
+    fn test_unknown_vote_flag_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(2), members[0], 1, &[4]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_member_in_last_slot_vote_is_stored() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..MAX_MEMBERS).map(|_| Pubkey::new_unique()).collect();
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[], config_data(2), members[MAX_MEMBERS - 1], 1, &[]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!(proposal.for_votes, 1);
+
+        let vote_state_account = &result.resulting_accounts[3].1;
+        let vote_state: VoteState = bytemuck::pod_read_unaligned(&vote_state_account.data);
+        assert!(vote_state.has_voted(MAX_MEMBERS - 1));
+        assert!((0..MAX_MEMBERS - 1).all(|i| !vote_state.has_voted(i)));
+    }
+
+    #[test]
+    fn test_underfunded_voter_cannot_pay_vote_state_rent() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+
+        // The first vote creates the vote state, whose rent the voter can't cover
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        tx_accounts[0].1.lamports = 1_000;
+        tx_accounts[3].1 = Account::new(0, 0, &system_program_id);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::InsufficientFundsForRent as u32))],
+        );
+    }
+
+    #[test]
+    fn test_change_vote_for_to_against_flips_outcome() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Member 0 voted Against, member 1 voted For: with a threshold of 2 the proposal is Active.
+        // Member 1 switching to Against makes it fail.
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[2, 1], config_data_allowing_vote_change(2), members[1], 2, &[1]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(receipt_choice(&result.resulting_accounts, &proposal_state_pda, &members[1]), 2);
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Failed as u8);
+    }
+
+    #[test]
+    fn test_participation_counts_once_per_proposal() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let participation_of = |accounts: &[(Pubkey, Account)]| {
+            let multisig: Multisig = bytemuck::pod_read_unaligned(&accounts[1].1.data);
+            (0..members.len()).map(|i| multisig.participation_count[i]).collect::<Vec<_>>()
+        };
+
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[], config_data_allowing_vote_change(3), members[0], 1, &[]);
+        let first = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]).resulting_accounts;
+        assert_eq!(participation_of(&first), vec![1, 0, 0]);
+
+        // Changing the vote on the same proposal doesn't count again
+        let (change, _, _) = vote_setup(&members, &[], config_data_allowing_vote_change(3), members[0], 2, &[VOTE_FLAG_CHANGE]);
+        let changed = mollusk.process_and_validate_instruction(&change, &first, &[Check::success()]).resulting_accounts;
+        assert_eq!(participation_of(&changed), vec![1, 0, 0]);
+
+        // A vote on another proposal, with fresh proposal accounts, does
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data_allowing_vote_change(3), members[0], 1, &[]);
+        tx_accounts[1] = changed[1].clone();
+        let second = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]).resulting_accounts;
+        assert_eq!(participation_of(&second), vec![2, 0, 0]);
+    }
+
+    #[test]
+    fn test_second_vote_without_change_flag_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[2, 1], config_data(2), members[1], 2, &[]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::AlreadyVoted as u32))],
+        );
+    }
+
+    #[test]
+    fn test_vote_change_needs_config_to_allow_it() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Member 1 switches For to Against with the change flag
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[2, 1], config_data(2), members[1], 2, &[1]);
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::AlreadyVoted as u32))],
+        );
+
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[2, 1], config_data_allowing_vote_change(2), members[1], 2, &[1]);
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!((proposal.for_votes, proposal.against_votes), (0, 2));
+    }
+
+    #[test]
+    fn test_vote_choice_zero_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(2), members[0], 0, &[]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::InvalidVoteChoice as u32))],
+        );
+    }
+
+    #[test]
+    fn test_vote_with_read_only_config() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (mut instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[], config_data(2), members[0], 1, &[]);
+        instruction.accounts[4].is_writable = false;
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        assert_eq!(receipt_choice(&result.resulting_accounts, &proposal_state_pda, &members[0]), 1);
+
+        // The multisig records decided outcomes in its statistics, so it must be writable
+        instruction.accounts[1].is_writable = false;
+        mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::err(ProgramError::InvalidAccountData)]);
+    }
+
+    #[test]
+    fn test_cached_tallies_match_full_rescan() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let members: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+
+        // A threshold of 5 keeps the proposal Active for the whole sequence
+        let (instruction, mut tx_accounts, proposal_state_pda) = vote_setup(&members, &[], config_data_allowing_vote_change(5), members[0], 1, &[]);
+        for member in &members[1..] {
+            tx_accounts.push((receipt_pda(&proposal_state_pda, member), Account::new(0, 0, &system_program_id)));
+        }
+
+        // (voter, vote_choice, change flag): mixed votes, then member 1 changes Against -> For
+        let steps: [(usize, u8, &[u8]); 5] = [(0, 1, &[]), (1, 2, &[]), (2, 3, &[]), (3, 1, &[]), (1, 1, &[1])];
+
+        for (voter, vote_choice, flags) in steps {
+            let mut vote = instruction.clone();
+            vote.accounts[0].pubkey = members[voter];
+            vote.accounts[6].pubkey = receipt_pda(&proposal_state_pda, &members[voter]);
+            vote.data[9] = vote_choice;
+            vote.data.truncate(11);
+            vote.data.extend_from_slice(flags);
+            tx_accounts[0] = (members[voter], Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id));
+
+            let result = mollusk.process_and_validate_instruction(&vote, &tx_accounts, &[Check::success()]);
+            tx_accounts = result.resulting_accounts;
+        }
+
+        let proposal_account = &tx_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        // Member 4 never voted and has no receipt
+        let votes: Vec<u8> = members[..4].iter().map(|member| receipt_choice(&tx_accounts, &proposal_state_pda, member)).collect();
+        let read_tally = |offset: usize| u64::from_le_bytes(proposal_account.data[offset..offset + 8].try_into().unwrap());
+
+        for (choice, field) in [(1, offset_of!(ProposalState, for_votes)), (2, offset_of!(ProposalState, against_votes)), (3, offset_of!(ProposalState, abstain_votes))] {
+            let rescanned = votes.iter().filter(|&&vote| vote == choice).count() as u64;
+            assert_eq!(read_tally(field), rescanned);
+        }
+        assert_eq!(read_tally(offset_of!(ProposalState, for_votes)), 3);
+    }
+
+    #[test]
+    fn test_receipts_are_independent_of_member_positions() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // A threshold of 3 keeps the proposal Active throughout
+        let (instruction, mut tx_accounts, proposal_state_pda) = vote_setup(&members, &[], config_data_allowing_vote_change(3), members[0], 1, &[]);
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+        tx_accounts = result.resulting_accounts;
+
+        // Member 1 votes Against into its own receipt
+        let mut vote = instruction.clone();
+        vote.accounts[0].pubkey = members[1];
+        vote.accounts[6].pubkey = receipt_pda(&proposal_state_pda, &members[1]);
+        vote.data[9] = 2;
+        tx_accounts[0] = (members[1], Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program_id));
+        tx_accounts.push((receipt_pda(&proposal_state_pda, &members[1]), Account::new(0, 0, &system_program_id)));
+
+        let result = mollusk.process_and_validate_instruction(&vote, &tx_accounts, &[Check::success()]);
+        tx_accounts = result.resulting_accounts;
+
+        assert_eq!(receipt_choice(&tx_accounts, &proposal_state_pda, &members[0]), 1);
+        assert_eq!(receipt_choice(&tx_accounts, &proposal_state_pda, &members[1]), 2);
+
+        // Member 0 is removed, shifting member 1 into the first slot of the multisig
+        let multisig = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        let members_offset = offset_of!(Multisig, members);
+        multisig.data[offset_of!(Multisig, num_members)] = 2;
+        multisig.data[members_offset..][..32].copy_from_slice(members[1].as_ref());
+        multisig.data[members_offset + 32..][..32].copy_from_slice(members[2].as_ref());
+        multisig.data[members_offset + 64..][..32].fill(0);
+
+        // Member 1 changes its own Against vote, not member 0's For vote
+        vote.data[9] = 1;
+        vote.data.push(1); // change flag
+        let result = mollusk.process_and_validate_instruction(&vote, &tx_accounts, &[Check::success()]);
+
+        assert_eq!(receipt_choice(&result.resulting_accounts, &proposal_state_pda, &members[0]), 1);
+        assert_eq!(receipt_choice(&result.resulting_accounts, &proposal_state_pda, &members[1]), 1);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!((proposal.for_votes, proposal.against_votes, proposal.abstain_votes), (2, 0, 0));
+    }
+
+    #[test]
+    fn test_multisig_with_out_of_range_member_count_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+
+        let multisig = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        multisig.data[offset_of!(Multisig, num_members)] = u8::MAX;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    /// Vote by `voter` on a proposal created with a members root of `voter` and three other
+    /// members, carrying `voter`'s proof (tampered with when `tamper` is set)
+    fn members_root_vote_setup(voter: Pubkey, tamper: bool) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+        let leaves = [voter, Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()]
+            .map(|member| crate::state::member_leaf(&member.to_bytes()));
+        let left = crate::state::hash_pair(&leaves[0], &leaves[1]);
+        let right = crate::state::hash_pair(&leaves[2], &leaves[3]);
+        let members_root = crate::state::hash_pair(&left, &right);
+
+        let mut proof = leaves[1].to_vec();
+        proof.extend_from_slice(&right);
+        if tamper {
+            proof[0] ^= 1;
+        }
+
+        // The `members` array holds someone else entirely; only the root decides who votes
+        let (mut instruction, mut tx_accounts, proposal_state_pda) =
+            vote_setup(&[Pubkey::new_unique()], &[], config_data(2), voter, 1, &[]);
+        instruction.data.push(0); // change_vote
+        instruction.data.extend_from_slice(&proof);
+
+        let proposal = &mut tx_accounts.iter_mut().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        proposal.data[offset_of!(ProposalState, members_root)..][..32].copy_from_slice(&members_root);
+        proposal.data[offset_of!(ProposalState, root_member_count)..][..8].copy_from_slice(&4u64.to_le_bytes());
+
+        (instruction, tx_accounts, proposal_state_pda)
+    }
+
+    #[test]
+    fn test_members_root_vote_with_valid_proof_is_counted() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let voter = Pubkey::new_unique();
+        let (instruction, tx_accounts, proposal_state_pda) = members_root_vote_setup(voter, false);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!(proposal.for_votes, 1);
+        assert!(matches!(proposal.status(), Ok(ProposalStatus::Active)));
+        assert_eq!(receipt_choice(&result.resulting_accounts, &proposal_state_pda, &voter), 1);
+
+        // The receipt alone stops the member from voting twice
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &result.resulting_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::AlreadyVoted as u32))],
+        );
+    }
+
+    #[test]
+    fn test_members_root_vote_with_tampered_proof_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts, _) = members_root_vote_setup(Pubkey::new_unique(), true);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::NotAMember as u32))],
+        );
+    }
+
+    #[test]
+    fn test_vote_cast_before_member_replacement_still_counts() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        // members[0] voted For before being replaced in place by a new key
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let replacement = Pubkey::new_unique();
+        let (instruction, mut tx_accounts, proposal_state_pda) = vote_setup(&members, &[1], config_data(2), members[1], 1, &[]);
+
+        let multisig = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        multisig.data[offset_of!(Multisig, members)..][..32].copy_from_slice(replacement.as_ref());
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+
+        // The new key isn't in the proposal's snapshot
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[1], config_data(2), replacement, 1, &[]);
+        let multisig = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        multisig.data[offset_of!(Multisig, members)..][..32].copy_from_slice(replacement.as_ref());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::NotAMember as u32))],
+        );
+    }
+
+    #[test]
+    fn test_member_added_after_proposal_creation_cannot_vote() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let late_member = Pubkey::new_unique();
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data(1), late_member, 1, &[]);
+
+        // The multisig gains a third member, but the proposal snapshot still has two
+        let multisig = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        multisig.data[offset_of!(Multisig, num_members)] = 3;
+        multisig.data[offset_of!(Multisig, members) + 64..][..32].copy_from_slice(late_member.as_ref());
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::NotAMember as u32))],
+        );
+    }
+
+    #[test]
+    fn test_vote_without_system_program_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (mut instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+        instruction.accounts.remove(5);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::NotEnoughAccountKeys)],
+        );
+    }
+
+    #[test]
+    fn test_vote_with_wrong_system_program_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (mut instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+        let impostor = Pubkey::new_unique();
+        instruction.accounts[5].pubkey = impostor;
+        tx_accounts.push((impostor, Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default())));
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::IncorrectProgramId)],
+        );
+    }
+
+    #[test]
+    fn test_vote_payload_too_short_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (mut instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+        instruction.data.pop(); // Drop the bump: 9 payload bytes
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_vote_payload_too_long_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        // Change flag and delegator index, plus one stray byte: 13 payload bytes
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[0, 0, 0]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_threshold_change_does_not_apply_to_existing_proposal() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Created while the threshold was 2, lowered to 1 since
+        let (instruction, mut tx_accounts, proposal_state_pda) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+        let proposal = &mut tx_accounts.iter_mut().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        proposal.data[offset_of!(ProposalState, required_votes)..][..8].copy_from_slice(&2u64.to_le_bytes());
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Active as u8);
+    }
+
+    #[test]
+    fn test_zero_threshold_cannot_rubber_stamp_proposal() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(0), members[0], 1, &[]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::InvalidThreshold as u32))],
+        );
+    }
+
+    #[test]
+    fn test_threshold_above_member_count_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(3), members[0], 1, &[]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::InvalidThreshold as u32))],
+        );
+    }
+
+    #[test]
+    fn test_succeeded_proposal_is_not_reopened_by_later_vote() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+
+        // A later Against vote from the other member is rejected and leaves the proposal Succeeded
+        let (second_instruction, _, _) = vote_setup(&members, &[], config_data(1), members[1], 2, &[]);
+        let mut second_accounts = result.resulting_accounts.clone();
+        second_accounts[0] = (members[1], Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default()));
+
+        let second_result = mollusk.process_and_validate_instruction(
+            &second_instruction,
+            &second_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::ProposalNotActive as u32))],
+        );
+
+        let proposal = &second_result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+    }
+
+    fn set_start_time(tx_accounts: &mut [(Pubkey, Account)], proposal_state_pda: &Pubkey, start_time: u64) {
+        let proposal = &mut tx_accounts.iter_mut().find(|(key, _)| key == proposal_state_pda).unwrap().1;
+        proposal.data[offset_of!(ProposalState, start_time)..][..8].copy_from_slice(&start_time.to_le_bytes());
+    }
+
+    #[test]
+    fn test_vote_before_start_time_is_rejected() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, proposal_state_pda) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+        set_start_time(&mut tx_accounts, &proposal_state_pda, 1_001);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::VotingNotStarted as u32))],
+        );
+    }
+
+    #[test]
+    fn test_vote_inside_voting_window_succeeds() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, proposal_state_pda) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+        set_start_time(&mut tx_accounts, &proposal_state_pda, 1_000);
+
+        mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+    }
+
+    #[test]
+    fn test_undecided_projection_leaves_proposal_untouched() {
+        let mut proposal = ProposalState::zeroed();
+        proposal.set_status(ProposalStatus::Active);
+        proposal.for_votes = 1;
+        let before = bytemuck::bytes_of(&proposal).to_vec();
+
+        apply_projection(&mut proposal, ProposalStatus::Active, 0, 500).unwrap();
+
+        assert_eq!(bytemuck::bytes_of(&proposal), &before[..]);
+    }
+
+    #[test]
+    fn test_read_proposal_id_from_unaligned_buffer() {
+        let proposal_id = 0x0102_0304_0506_0708u64;
+
+        // Offset the payload by one byte so it cannot be 8-byte aligned
+        let mut buffer = vec![0u8; 1];
+        buffer.extend_from_slice(&proposal_id.to_le_bytes());
+        buffer.push(1); // vote choice
+        buffer.push(255); // bump
+
+        assert_eq!(read_proposal_id(&buffer[1..]), proposal_id);
+    }
+
+    fn config_data_with_quorum(threshold: u64, quorum: u64) -> Vec<u8> {
+        let mut data = config_data(threshold);
+        data[offset_of!(MultisigConfig, quorum)..][..8].copy_from_slice(&quorum.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_unreachable_approval_fails_early_when_enabled() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+
+        // Member 0 already abstained; a second abstain leaves 2 unvoted, short of the 3 For needed
+        let mut config = config_data(3);
+        config[offset_of!(MultisigConfig, fail_when_unreachable)] = 1;
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[3], config, members[1], 3, &[]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Failed as u8);
+    }
+
+    #[test]
+    fn test_against_votes_fail_unreachable_approval_before_expiry() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+
+        // Against from members 0 and 1 leaves at most 3 possible For votes, short of the 4 needed
+        let mut config = config_data(4);
+        config[offset_of!(MultisigConfig, fail_when_unreachable)] = 1;
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[2], config, members[1], 2, &[]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Failed as u8);
+    }
+
+    #[test]
+    fn test_unreachable_approval_stays_active_by_default() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[3], config_data(3), members[1], 3, &[]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Active as u8);
+    }
+
+    #[test]
+    fn test_against_votes_reaching_defeat_threshold_fail_proposal() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+
+        // Two Against votes fall short of the approval threshold of 3 but reach the defeat threshold
+        let mut config = config_data(3);
+        config[offset_of!(MultisigConfig, defeat_threshold)..][..8].copy_from_slice(&2u64.to_le_bytes());
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[2], config, members[1], 2, &[]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Failed as u8);
+    }
+
+    fn unanimous_config_data(abstain_fails: bool) -> Vec<u8> {
+        let mut data = config_data(1);
+        data[offset_of!(MultisigConfig, require_unanimous)] = 1;
+        data[offset_of!(MultisigConfig, unanimous_abstain_fails)] = abstain_fails as u8;
+        data
+    }
+
+    #[test]
+    fn test_unanimous_approval_needs_every_member() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+        // Two of three For would pass a threshold of 1, but not unanimity
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[1], unanimous_config_data(false), members[1], 1, &[]);
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Active as u8);
+
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[1, 1], unanimous_config_data(false), members[2], 1, &[]);
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+    }
+
+    #[test]
+    fn test_unanimous_single_against_fails_immediately() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[], unanimous_config_data(false), members[0], 2, &[]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Failed as u8);
+    }
+
+    #[test]
+    fn test_unanimous_abstain_fails_only_when_configured() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+        for (abstain_fails, expected) in [(false, ProposalStatus::Active), (true, ProposalStatus::Failed)] {
+            let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[], unanimous_config_data(abstain_fails), members[0], 3, &[]);
+
+            let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+            let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+            assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], expected as u8);
+        }
+    }
+
+    #[test]
+    fn test_enough_for_votes_without_quorum_stays_active() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+
+        // Two For votes meet the threshold of 2, but only 2 of the 4 required voters took part
+        let (instruction, tx_accounts, proposal_state_pda) =
+            vote_setup(&members, &[1], config_data_with_quorum(2, 4), members[1], 1, &[]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Active as u8);
+    }
+
+    #[test]
+    fn test_abstain_vote_satisfies_quorum() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+
+        // For, For, Abstain already cast; a second Abstain brings participation to the quorum of 4
+        let (instruction, tx_accounts, proposal_state_pda) =
+            vote_setup(&members, &[1, 1, 3], config_data_with_quorum(2, 4), members[3], 3, &[]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+    }
+
+    #[test]
+    fn test_quorum_raised_after_creation_does_not_apply() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let (instruction, mut tx_accounts, proposal_state_pda) =
+            vote_setup(&members, &[1, 1, 3], config_data_with_quorum(2, 4), members[3], 3, &[]);
+
+        // Raising the config's quorum to every member leaves the proposal's snapshot of 4
+        let config = &mut tx_accounts[4].1;
+        config.data[offset_of!(MultisigConfig, quorum)..][..8].copy_from_slice(&5u64.to_le_bytes());
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+    }
+
+    fn set_member_weight(tx_accounts: &mut [(Pubkey, Account)], member_index: usize, weight: u64) {
+        let multisig = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        let offset = offset_of!(Multisig, weights) + member_index * 8;
+        multisig.data[offset..offset + 8].copy_from_slice(&weight.to_le_bytes());
+    }
+
+    #[test]
+    fn test_weighted_member_crosses_threshold_alone() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, proposal_state_pda) =
+            vote_setup(&members, &[], config_data(3), members[0], 1, &[]);
+
+        set_member_weight(&mut tx_accounts, 0, 3);
+        set_member_weight(&mut tx_accounts, 1, 1);
+        set_member_weight(&mut tx_accounts, 2, 1);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+    }
+
+    fn set_snapshot_weight(tx_accounts: &mut [(Pubkey, Account)], proposal_state_pda: &Pubkey, member_index: usize, weight: u64) {
+        let proposal = &mut tx_accounts.iter_mut().find(|(key, _)| key == proposal_state_pda).unwrap().1;
+        let offset = offset_of!(ProposalState, active_weights) + member_index * 8;
+        proposal.data[offset..offset + 8].copy_from_slice(&weight.to_le_bytes());
+    }
+
+    #[test]
+    fn test_weight_raised_after_creation_does_not_count_on_in_flight_proposal() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, proposal_state_pda) =
+            vote_setup(&members, &[], config_data(3), members[0], 1, &[]);
+
+        // Every member had a weight of 1 when the proposal was created
+        for i in 0..members.len() {
+            set_snapshot_weight(&mut tx_accounts, &proposal_state_pda, i, 1);
+        }
+        set_member_weight(&mut tx_accounts, 0, 3);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!(proposal.for_votes, 1);
+        assert!(matches!(proposal.status(), Ok(ProposalStatus::Active)));
+    }
+
+    #[test]
+    fn test_sorted_members_lookup_costs_less_on_full_multisig() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let mut members: Vec<Pubkey> = (0..MAX_MEMBERS).map(|_| Pubkey::new_unique()).collect();
+        members.sort();
+        let voter = members[MAX_MEMBERS - 1];
+
+        // The voter sits in the last slot, the worst case for a linear scan
+        let (instruction, unsorted_accounts, _) =
+            vote_setup(&members, &[], config_data(MAX_MEMBERS as u64), voter, 1, &[]);
+        let mut sorted_accounts = unsorted_accounts.clone();
+        let multisig = &mut sorted_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        multisig.data[offset_of!(Multisig, members_sorted)] = 1;
+
+        let linear = mollusk.process_and_validate_instruction(&instruction, &unsorted_accounts, &[Check::success()]);
+        let binary = mollusk.process_and_validate_instruction(&instruction, &sorted_accounts, &[Check::success()]);
+
+        println!("Vote by member {} of {}: linear scan {} CUs, binary search {} CUs",
+            MAX_MEMBERS, MAX_MEMBERS, linear.compute_units_consumed, binary.compute_units_consumed);
+        assert!(binary.compute_units_consumed < linear.compute_units_consumed);
+    }
+
+    fn set_delegation(tx_accounts: &mut [(Pubkey, Account)], member_index: usize, delegate: Pubkey) {
+        let multisig = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        let offset = offset_of!(Multisig, delegations) + member_index * 32;
+        multisig.data[offset..offset + 32].copy_from_slice(delegate.as_ref());
+    }
+
+    #[test]
+    fn test_delegate_casts_vote_for_delegator() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Member 1 votes For on behalf of member 0; the delegate's own vote is untouched
+        let (instruction, mut tx_accounts, proposal_state_pda) =
+            vote_setup(&members, &[], config_data(1), members[1], 1, &[0, 0]);
+        set_delegation(&mut tx_accounts, 0, members[1]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(receipt_choice(&result.resulting_accounts, &proposal_state_pda, &members[0]), 1);
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+        // The audit trail names the delegate who signed
+        assert_eq!(&proposal_account.data[offset_of!(ProposalState, last_voter)..][..32], members[1].as_ref());
+    }
+
+    #[test]
+    fn test_delegate_cannot_vote_again_for_member_who_voted_directly() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Member 0 voted For directly, then delegated to member 1. Neither a new vote nor a
+        // change cast by the delegate counts for member 0 a second time.
+        for change_vote in [0, 1] {
+            let (instruction, mut tx_accounts, _) =
+                vote_setup(&members, &[1], config_data_allowing_vote_change(3), members[1], 2, &[change_vote, 0]);
+            set_delegation(&mut tx_accounts, 0, members[1]);
+
+            mollusk.process_and_validate_instruction(
+                &instruction,
+                &tx_accounts,
+                &[Check::err(ProgramError::Custom(MultisigError::AlreadyVoted as u32))],
+            );
+        }
+    }
+
+    #[test]
+    fn test_vote_records_last_voter_and_slot() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        mollusk.sysvars.clock.slot = 4_242;
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Member 0 already voted; member 1's vote is the latest
+        let (instruction, tx_accounts, proposal_state_pda) = vote_setup(&members, &[1], config_data(3), members[1], 2, &[]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        let proposal: ProposalState = bytemuck::pod_read_unaligned(&proposal_account.data[..ProposalState::BASE_LEN]);
+        assert_eq!(proposal.last_voter, members[1].to_bytes());
+        assert_eq!(proposal.last_vote_slot, 4_242);
+    }
+
+    #[test]
+    fn test_revoked_delegation_rejects_delegate() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Member 0's delegation to member 1 was revoked, so its slot is back to default
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[1], 1, &[0, 0]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::NotDelegated as u32))],
+        );
+    }
+
+    #[test]
+    fn test_delegator_cannot_vote_directly() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+        set_delegation(&mut tx_accounts, 0, members[1]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::VoteDelegated as u32))],
+        );
+    }
+
+    #[test]
+    fn test_vote_on_expired_proposal_is_rejected() {
+        let mut mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+        // One second past the 9999999999 expiry set up by `vote_setup`
+        mollusk.sysvars.clock.unix_timestamp = 10_000_000_000;
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        for flags in [&[][..], &[VOTE_FLAG_DRY_RUN]] {
+            let (instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, flags);
+
+            mollusk.process_and_validate_instruction(
+                &instruction,
+                &tx_accounts,
+                &[Check::err(ProgramError::Custom(MultisigError::ProposalExpired as u32))],
+            );
+        }
+    }
+
+    #[test]
+    fn test_propose_only_member_cannot_vote() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+
+        let multisig_account = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        multisig_account.data[offset_of!(Multisig, roles)] = crate::state::ROLE_CAN_PROPOSE;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::MissingPermission as u32))],
+        );
+    }
+
+    #[test]
+    fn test_delegate_cannot_cast_vote_for_propose_only_member() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[1], 1, &[0, 0]);
+        set_delegation(&mut tx_accounts, 0, members[1]);
+
+        // Member 0 may only propose; its delegate voting for it must not count its weight
+        let multisig_account = &mut tx_accounts.iter_mut().find(|(key, _)| key == &MULTISIG).unwrap().1;
+        multisig_account.data[offset_of!(Multisig, roles)] = crate::state::ROLE_CAN_PROPOSE;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::MissingPermission as u32))],
+        );
+    }
+
+    #[test]
+    fn test_wrong_stored_vote_state_bump_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data(2), members[0], 1, &[]);
+
+        let (vote_state_pda, vote_state_bump) = Pubkey::find_program_address(
+            &[b"vote_state", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes()],
+            &ID,
+        );
+        // A lower bump that still derives a valid, but different, program address
+        let wrong_bump = (0..vote_state_bump)
+            .rev()
+            .find(|&bump| Pubkey::create_program_address(&[b"vote_state", MULTISIG.as_ref(), &PROPOSAL_ID.to_le_bytes(), &[bump]], &ID).is_ok())
+            .unwrap();
+
+        let vote_state_account = &mut tx_accounts.iter_mut().find(|(key, _)| key == &vote_state_pda).unwrap().1;
+        vote_state_account.data[offset_of!(VoteState, bump)] = wrong_bump;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    /// Turns the proposal into a Poll with one tally per entry of `option_votes`
+    fn set_poll(tx_accounts: &mut [(Pubkey, Account)], proposal_state_pda: &Pubkey, option_votes: &[u64]) {
+        let proposal_account = &mut tx_accounts.iter_mut().find(|(key, _)| key == proposal_state_pda).unwrap().1;
+        proposal_account.data[offset_of!(ProposalState, proposal_type)] = crate::state::ProposalType::Poll as u8;
+        proposal_account.data[offset_of!(ProposalState, num_options)] = option_votes.len() as u8;
+        for (i, votes) in option_votes.iter().enumerate() {
+            proposal_account.data[offset_of!(ProposalState, option_votes) + i * 8..][..8].copy_from_slice(&votes.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn test_poll_option_crossing_threshold_wins() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        let (instruction, mut tx_accounts, proposal_state_pda) = vote_setup(&members, &[], config_data(3), members[5], 4, &[]);
+        set_poll(&mut tx_accounts, &proposal_state_pda, &[1, 0, 0, 2]);
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        assert_eq!(receipt_choice(&result.resulting_accounts, &proposal_state_pda, &members[5]), 4);
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, result)], ProposalStatus::Succeeded as u8);
+        assert_eq!(proposal_account.data[offset_of!(ProposalState, winning_option)], 4);
+    }
+
+    #[test]
+    fn test_poll_vote_for_missing_option_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, proposal_state_pda) = vote_setup(&members, &[], config_data(2), members[0], 4, &[]);
+        set_poll(&mut tx_accounts, &proposal_state_pda, &[0, 0, 0]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::InvalidVoteChoice as u32))],
+        );
+    }
+
+    #[test]
+    fn test_vote_accounts_reject_malformed_account_lists() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+
+        let malformed: [(fn(&mut Instruction), ProgramError); 6] = [
+            (|ix| { ix.accounts.truncate(6); }, ProgramError::NotEnoughAccountKeys),
+            (|ix| ix.accounts[0].is_signer = false, ProgramError::MissingRequiredSignature),
+            (|ix| ix.accounts[2].is_writable = false, ProgramError::InvalidAccountData),
+            (|ix| ix.accounts[3].is_writable = false, ProgramError::InvalidAccountData),
+            (|ix| ix.accounts[6].is_writable = false, ProgramError::InvalidAccountData),
+            // The system program is not owned by this program
+            (|ix| ix.accounts[1] = ix.accounts[5].clone(), ProgramError::IncorrectProgramId),
+        ];
+
+        for (malform, error) in malformed {
+            let mut instruction = instruction.clone();
+            malform(&mut instruction);
+            mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::err(error)]);
+        }
+    }
+
+    #[test]
+    fn test_accounts_out_of_order_name_the_slot() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (mut instruction, tx_accounts, _) = vote_setup(&members, &[], config_data(1), members[0], 1, &[]);
+        // Both are program owned, so only their roles tell them apart
+        instruction.accounts.swap(1, 4);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::AccountOutOfOrder as u32))],
+        );
+    }
+
+    fn active_proposal(start_time: u64, expiry: u64) -> ProposalState {
+        let mut proposal = ProposalState::zeroed();
+        proposal.set_status(ProposalStatus::Active);
+        proposal.start_time = start_time;
+        proposal.expiry = expiry;
+        proposal
+    }
+
+    #[test]
+    fn test_voting_window_boundaries() {
+        let expiry = 2_000;
+
+        // Open through expiry itself
+        let proposal = active_proposal(1_000, expiry);
+        for now in [expiry - 1, expiry] {
+            assert_eq!(check_voting_window(&proposal, now), Ok(()));
+        }
+
+        // Closed the second after, leaving the proposal Active for finalization
+        assert_eq!(
+            check_voting_window(&proposal, expiry + 1),
+            Err(ProgramError::Custom(MultisigError::ProposalExpired as u32))
+        );
+        assert!(matches!(proposal.status(), Ok(ProposalStatus::Active)));
+    }
+
+    #[test]
+    fn test_voting_window_opens_at_start_time() {
+        let proposal = active_proposal(1_000, 2_000);
+
+        assert_eq!(
+            check_voting_window(&proposal, 999),
+            Err(ProgramError::Custom(MultisigError::VotingNotStarted as u32))
+        );
+        assert_eq!(check_voting_window(&proposal, 1_000), Ok(()));
+    }
+
+    #[test]
+    fn test_apply_projection_starts_timelock_once() {
+        let mut proposal = active_proposal(1_000, 2_000);
+
+        apply_projection(&mut proposal, ProposalStatus::Active, 0, 1_500).unwrap();
+        assert!(matches!(proposal.status(), Ok(ProposalStatus::Active)));
+        assert_eq!(proposal.succeeded_at, 0);
+
+        apply_projection(&mut proposal, ProposalStatus::Succeeded, 0, 1_500).unwrap();
+        apply_projection(&mut proposal, ProposalStatus::Succeeded, 0, 1_800).unwrap();
+        assert!(matches!(proposal.status(), Ok(ProposalStatus::Succeeded)));
+        assert_eq!(proposal.succeeded_at, 1_500);
+    }
+
+    /// A config with a general threshold of 1 and a Transfer threshold of 3
+    fn tiered_config_data() -> Vec<u8> {
+        let mut data = config_data(1);
+        let transfer_threshold = offset_of!(MultisigConfig, type_thresholds) + crate::state::ProposalType::Transfer as usize * 8;
+        data[transfer_threshold..][..8].copy_from_slice(&3u64.to_le_bytes());
+        data
+    }
+
+    fn vote_result(proposal_type: crate::state::ProposalType, votes: &[u8], voter_index: usize) -> u8 {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, proposal_state_pda) = vote_setup(&members, votes, tiered_config_data(), members[voter_index], 1, &[]);
+        let proposal_account = &mut tx_accounts.iter_mut().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        proposal_account.data[offset_of!(ProposalState, proposal_type)] = proposal_type as u8;
+
+        let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+        let proposal_account = &result.resulting_accounts.iter().find(|(key, _)| key == &proposal_state_pda).unwrap().1;
+        proposal_account.data[offset_of!(ProposalState, result)]
+    }
+
+    #[test]
+    fn test_thresholds_are_selected_by_proposal_type() {
+        use crate::state::ProposalType;
+
+        // A stored instruction, say a memo, falls back to the general threshold of 1
+        assert_eq!(vote_result(ProposalType::ArbitraryCpi, &[], 0), ProposalStatus::Succeeded as u8);
+
+        // A Transfer needs all 3 approvals
+        assert_eq!(vote_result(ProposalType::Transfer, &[], 0), ProposalStatus::Active as u8);
+        assert_eq!(vote_result(ProposalType::Transfer, &[1, 0, 0], 1), ProposalStatus::Active as u8);
+        assert_eq!(vote_result(ProposalType::Transfer, &[1, 1, 0], 2), ProposalStatus::Succeeded as u8);
+    }
+
+    #[test]
+    fn test_pre_funded_vote_accounts_are_initialized() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (system_program_id, _) = program::keyed_account_for_system_program();
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let (instruction, mut tx_accounts, _) = vote_setup(&members, &[], config_data(2), members[0], 1, &[]);
+
+        // Someone sent lamports to both PDAs before the first vote, below their rent exemption
+        let (vote_state_pda, receipt_pda) = (instruction.accounts[3].pubkey, instruction.accounts[6].pubkey);
+        for pda in [vote_state_pda, receipt_pda] {
+            let account = &mut tx_accounts.iter_mut().find(|(key, _)| key == &pda).unwrap().1;
+            *account = Account::new(1_000, 0, &system_program_id);
+        }
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[
+                Check::success(),
+                Check::account(&vote_state_pda).owner(&ID).space(VoteState::LEN).rent_exempt().build(),
+                Check::account(&receipt_pda).owner(&ID).space(VoteReceipt::LEN).rent_exempt().build(),
+            ],
+        );
+
+        let vote_state_account = &result.resulting_accounts.iter().find(|(key, _)| key == &vote_state_pda).unwrap().1;
+        let vote_state: VoteState = bytemuck::pod_read_unaligned(&vote_state_account.data);
+        assert_eq!((vote_state.has_permission, vote_state.vote_count), (1, 1));
+        assert!(vote_state.has_voted(0));
+    }
+}
@@ -0,0 +1,193 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self},
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_log::log;
+
+use crate::{
+    error::MultisigError,
+    instructions::common::require_writable,
+    state::{Multisig, MultisigConfig},
+};
+
+/// Moves the lamports the multisig account holds above its rent-exempt minimum, e.g. from
+/// deposits or refunds sent to it by mistake, to `recipient`. The minimum is computed for
+/// the account's current data length, so the account always stays rent-exempt.
+///
+/// Authorized either by the multisig PDA signing (when invoked from an approved proposal)
+/// or by the config's `config_authority` signing this instruction directly, passed among
+/// the trailing accounts. A config `governed_by_proposals` only takes the multisig PDA's
+/// signature.
+///
+/// Accounts: [multisig, multisig_config, recipient, ..signers]
+///
+/// Instruction data (after the discriminator): none
+pub fn process_withdraw_excess_rent_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+
+    if !data.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let [multisig, multisig_config, recipient, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require_writable(multisig)?;
+    require_writable(recipient)?;
+
+    for accounts in [multisig, multisig_config] {
+        if accounts.owner() != &crate::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    if recipient.key() == multisig.key() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Multisig::from_account_info(multisig)?;
+    let multisig_config_data = MultisigConfig::load(multisig_config)?;
+
+    let config_seed = [
+        b"multisig_config".as_slice(),
+        multisig.key().as_slice(),
+        &[multisig_config_data.bump],
+    ];
+    let config_pda = pubkey::checked_create_program_address(&config_seed, &crate::ID)?;
+
+    if &config_pda != multisig_config.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if !multisig.is_signer() {
+        multisig_config_data.require_direct_changes_allowed()?;
+        multisig_config_data.check_config_authority(signers)?;
+    }
+
+    let excess = multisig.lamports().saturating_sub(Rent::get()?.minimum_balance(multisig.data_len()));
+
+    if excess == 0 {
+        log!("No lamports above the rent-exempt minimum to withdraw");
+        return Ok(());
+    }
+
+    let mut recipient_lamports = recipient.try_borrow_mut_lamports()?;
+    *recipient_lamports = recipient_lamports.checked_add(excess).ok_or(MultisigError::ArithmeticOverflow)?;
+    drop(recipient_lamports);
+    *multisig.try_borrow_mut_lamports()? -= excess;
+
+    log!("Withdrew {} lamports of excess rent", excess);
+
+    Ok(())
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_withdraw_excess_rent_instruction {
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+    use bytemuck::Zeroable;
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            account::Account,
+            pubkey::Pubkey,
+            instruction::AccountMeta,
+            pubkey,
+            instruction::Instruction,
+            program_error::ProgramError,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+    const CONFIG_AUTHORITY: Pubkey = Pubkey::new_from_array([0x01; 32]);
+    const MULTISIG: Pubkey = Pubkey::new_from_array([0x02; 32]);
+    const RECIPIENT: Pubkey = Pubkey::new_from_array([0x03; 32]);
+
+    fn withdraw_excess_rent(signer: Pubkey, multisig_lamports: u64) -> (Instruction, Vec<(Pubkey, Account)>) {
+        let (multisig_config_pda, config_bump) = Pubkey::find_program_address(
+            &[b"multisig_config", MULTISIG.as_ref()],
+            &ID,
+        );
+
+        let mut multisig = Multisig::zeroed();
+        multisig.version = Multisig::VERSION;
+        multisig.num_members = 1;
+        multisig.members[0] = CONFIG_AUTHORITY.to_bytes();
+
+        let mut config = MultisigConfig::zeroed();
+        config.version = MultisigConfig::VERSION;
+        config.config_authority = CONFIG_AUTHORITY.to_bytes();
+        config.bump = config_bump;
+
+        let data = vec![30u8]; // Instruction discriminator for withdraw excess rent
+
+        let instruction = Instruction::new_with_bytes(
+            ID,
+            &data,
+            vec![
+                AccountMeta::new(MULTISIG, false),                     // multisig
+                AccountMeta::new_readonly(multisig_config_pda, false), // multisig_config
+                AccountMeta::new(RECIPIENT, false),                    // recipient
+                AccountMeta::new_readonly(signer, true),               // config authority (signer)
+            ],
+        );
+
+        let tx_accounts = vec![
+            (MULTISIG, Account::new_data(multisig_lamports, bytemuck::bytes_of(&multisig), &ID).unwrap()),
+            (multisig_config_pda, Account::new_data(1 * LAMPORTS_PER_SOL, bytemuck::bytes_of(&config), &ID).unwrap()),
+            (RECIPIENT, Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default())),
+            (signer, Account::new(1 * LAMPORTS_PER_SOL, 0, &Pubkey::default())),
+        ];
+
+        (instruction, tx_accounts)
+    }
+
+    #[test]
+    fn test_withdraw_excess_rent_leaves_multisig_rent_exempt() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let minimum_balance = mollusk.sysvars.rent.minimum_balance(Multisig::LEN);
+        let excess = LAMPORTS_PER_SOL / 4;
+        let (instruction, tx_accounts) = withdraw_excess_rent(CONFIG_AUTHORITY, minimum_balance + excess);
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[
+                Check::success(),
+                Check::account(&MULTISIG).lamports(minimum_balance).build(),
+                Check::account(&RECIPIENT).lamports(LAMPORTS_PER_SOL + excess).build(),
+            ],
+        );
+
+        // Nothing is left to withdraw the second time
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &result.resulting_accounts,
+            &[
+                Check::success(),
+                Check::account(&MULTISIG).lamports(minimum_balance).build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_withdraw_excess_rent_requires_config_authority() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let (instruction, tx_accounts) = withdraw_excess_rent(Pubkey::new_unique(), 1 * LAMPORTS_PER_SOL);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &tx_accounts,
+            &[Check::err(ProgramError::Custom(MultisigError::UnauthorizedConfigChange as u32))],
+        );
+    }
+}
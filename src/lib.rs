@@ -14,6 +14,11 @@ use pinocchio::{
 
 mod state;
 mod instructions;
+mod events;
+mod error;
+
+#[cfg(any(test, feature = "client"))]
+pub mod instruction;
 
 use instructions::*;
 
@@ -21,23 +26,108 @@ entrypoint!(process_instruction);
 
 pinocchio_pubkey::declare_id!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
 
+/// Routes `data[0]` to its handler. Every handler receives the payload without the
+/// discriminator byte, so instruction offsets start at 0.
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: &[u8],
 
 ) -> ProgramResult {
-    assert_eq!(program_id, &ID);
+    if program_id != &ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
 
-    let (discriminator, data) = data.split_first().ok_or(ProgramError::InvalidAccountData)?;
+    let (discriminator, data) = data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
 
     match MultisigInstructions::try_from(discriminator)? {
-        MultisigInstructions::InitMultisig => instructions::process_init_multisig_instruction(accounts, data)?,
         MultisigInstructions::UpdateMultisig => {},
-        MultisigInstructions::CreateProposal => instructions::process_init_multisig_instruction(accounts, data)?,
-        MultisigInstructions::Vote => {},
-        MultisigInstructions::CloseProposal => {},
+        MultisigInstructions::CreateProposal => instructions::process_create_proposal_instruction(accounts, data)?,
+        MultisigInstructions::Vote => instructions::process_vote_instruction(accounts, data)?,
+        MultisigInstructions::CloseProposal => instructions::process_close_proposal_instruction(accounts, data)?,
+        MultisigInstructions::CreateMultisig => instructions::process_create_multisig_instruction(accounts, data)?,
+        MultisigInstructions::AddMember => instructions::process_add_member_instruction(accounts, data)?,
+        MultisigInstructions::RemoveMember => instructions::process_remove_member_instruction(accounts, data)?,
+        MultisigInstructions::ExecuteProposal => instructions::process_execute_proposal_instruction(accounts, data)?,
+        MultisigInstructions::CancelProposal => instructions::process_cancel_proposal_instruction(accounts, data)?,
+        MultisigInstructions::ExtendProposalExpiry => instructions::process_extend_proposal_expiry_instruction(accounts, data)?,
+        MultisigInstructions::DelegateVote => instructions::process_delegate_vote_instruction(accounts, data)?,
+        MultisigInstructions::ChangeThreshold => instructions::process_change_threshold_instruction(accounts, data)?,
+        MultisigInstructions::Veto => instructions::process_veto_instruction(accounts, data)?,
+        MultisigInstructions::InitializeConfig => instructions::process_initialize_config_instruction(accounts, data)?,
+        MultisigInstructions::GetProposalResult => instructions::process_get_proposal_result_instruction(accounts, data)?,
+        MultisigInstructions::BatchVote => instructions::process_batch_vote_instruction(accounts, data)?,
+        MultisigInstructions::UpdateMemberWeight => instructions::process_update_member_weight_instruction(accounts, data)?,
+        MultisigInstructions::SweepExpiredProposals => instructions::process_sweep_expired_proposals_instruction(accounts, data)?,
+        MultisigInstructions::RotateConfigAuthority => instructions::process_rotate_config_authority_instruction(accounts, data)?,
+        MultisigInstructions::CloseMultisig => instructions::process_close_multisig_instruction(accounts, data)?,
+        MultisigInstructions::EmergencyPause => instructions::process_emergency_pause_instruction(accounts, data)?,
+        MultisigInstructions::SetVetoAuthority => instructions::process_set_veto_authority_instruction(accounts, data)?,
+        MultisigInstructions::RevokeDelegation => instructions::process_revoke_delegation_instruction(accounts, data)?,
+        MultisigInstructions::FinalizeProposal => instructions::process_finalize_proposal_instruction(accounts, data)?,
+        MultisigInstructions::UpdateExpiryPolicy => instructions::process_update_expiry_policy_instruction(accounts, data)?,
+        MultisigInstructions::DepositToTreasury => instructions::process_deposit_to_treasury_instruction(accounts, data)?,
+        MultisigInstructions::SetMembersRoot => instructions::process_set_members_root_instruction(accounts, data)?,
+        MultisigInstructions::ReplaceMember => instructions::process_replace_member_instruction(accounts, data)?,
+        MultisigInstructions::ChangeConfig => instructions::process_change_config_instruction(accounts, data)?,
+        MultisigInstructions::WithdrawExcessRent => instructions::process_withdraw_excess_rent_instruction(accounts, data)?,
     }
 
     Ok(())
 }
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_process_instruction {
+    use {
+        mollusk_svm::{Mollusk, result::Check},
+        solana_sdk::{
+            instruction::Instruction,
+            program_error::ProgramError,
+            pubkey::Pubkey,
+            pubkey,
+        }
+    };
+
+    const ID: Pubkey = pubkey!("4ibrEMW5F6hKnkW4jVedswYv6H6VtwPN6ar6dvXDN1nT");
+
+    #[test]
+    fn test_missing_discriminator_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let instruction = Instruction::new_with_bytes(ID, &[], vec![]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[],
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_unknown_discriminator_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let instruction = Instruction::new_with_bytes(ID, &[u8::MAX], vec![]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[],
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_legacy_init_multisig_is_rejected() {
+        let mollusk = Mollusk::new(&ID, "target/deploy/pinocchio_multisig");
+
+        let instruction = Instruction::new_with_bytes(ID, &[0u8, 0, 0], vec![]);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[],
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use pinocchio::pubkey::Pubkey;
+
+/// Deepest proof a vote can carry, enough for a tree of 65_536 members
+pub const MAX_MEMBERS_PROOF_DEPTH: usize = 16;
+
+/// Size of each sibling hash in a membership proof
+pub const PROOF_NODE_LEN: usize = 32;
+
+// Leaves and inner nodes are hashed with different prefixes, so an inner node can never
+// be passed off as a member's leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+#[cfg(target_os = "solana")]
+fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    unsafe {
+        pinocchio::syscalls::sol_sha256(vals as *const _ as *const u8, vals.len() as u64, hash.as_mut_ptr());
+    }
+    hash
+}
+
+#[cfg(not(target_os = "solana"))]
+fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    solana_sdk::hash::hashv(vals).to_bytes()
+}
+
+/// Leaf committing to `member` in a members root
+pub fn member_leaf(member: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, member])
+}
+
+/// Parent of two nodes. The pair is sorted before hashing, so proofs don't need to say
+/// on which side each sibling sits.
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right])
+}
+
+/// True when `proof`, the sibling hashes from `member`'s leaf up to the root, leads to
+/// `root`. Proofs that aren't whole nodes or are deeper than `MAX_MEMBERS_PROOF_DEPTH` fail.
+pub fn verify_member_proof(root: &[u8; 32], member: &Pubkey, proof: &[u8]) -> bool {
+    if proof.len() % PROOF_NODE_LEN != 0 || proof.len() / PROOF_NODE_LEN > MAX_MEMBERS_PROOF_DEPTH {
+        return false;
+    }
+
+    let computed = proof
+        .chunks_exact(PROOF_NODE_LEN)
+        .fold(member_leaf(member), |node, sibling| hash_pair(&node, sibling.try_into().unwrap()));
+
+    &computed == root
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_merkle {
+    use super::*;
+
+    /// Root of four members, with the proof of the first one
+    fn tree(members: &[Pubkey; 4]) -> ([u8; 32], Vec<u8>) {
+        let leaves = members.map(|member| member_leaf(&member));
+        let left = hash_pair(&leaves[0], &leaves[1]);
+        let right = hash_pair(&leaves[2], &leaves[3]);
+
+        let mut proof = leaves[1].to_vec();
+        proof.extend_from_slice(&right);
+
+        (hash_pair(&left, &right), proof)
+    }
+
+    #[test]
+    fn test_member_proof_leads_to_root() {
+        let members = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+        let (root, proof) = tree(&members);
+
+        assert!(verify_member_proof(&root, &members[0], &proof));
+
+        // The proof only holds for the member it was built for
+        assert!(!verify_member_proof(&root, &members[2], &proof));
+        assert!(!verify_member_proof(&root, &[9u8; 32], &proof));
+    }
+
+    #[test]
+    fn test_tampered_or_malformed_proof_is_rejected() {
+        let members = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+        let (root, proof) = tree(&members);
+
+        let mut tampered = proof.clone();
+        tampered[40] ^= 1;
+        assert!(!verify_member_proof(&root, &members[0], &tampered));
+
+        assert!(!verify_member_proof(&root, &members[0], &proof[..proof.len() - 1]));
+        assert!(!verify_member_proof(&root, &members[0], &proof[..PROOF_NODE_LEN]));
+
+        let too_deep = proof.repeat(MAX_MEMBERS_PROOF_DEPTH);
+        assert!(!verify_member_proof(&root, &members[0], &too_deep));
+    }
+}
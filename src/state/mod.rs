@@ -2,9 +2,74 @@ pub mod multisig;
 pub mod proposal;
 pub mod multisig_config;
 pub mod vote;
+pub mod merkle;
 
 
 pub use vote::*;
 pub use proposal::*;
 pub use multisig::*;
-pub use multisig_config::*;
\ No newline at end of file
+pub use multisig_config::*;
+pub use merkle::*;
+
+use bytemuck::Pod;
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+/// Maximum number of members a multisig (and therefore a proposal snapshot) can hold
+pub const MAX_MEMBERS: usize = 64;
+
+/// Reinterprets the start of account data as `T`. Data shorter than `T` fails with
+/// `AccountDataTooSmall` instead of being read past its end.
+pub fn load<T: Pod>(data: &[u8]) -> Result<&T, ProgramError> {
+    let bytes = data.get(..core::mem::size_of::<T>()).ok_or(ProgramError::AccountDataTooSmall)?;
+    bytemuck::try_from_bytes(bytes).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Mutable `load`
+pub fn load_mut<T: Pod>(data: &mut [u8]) -> Result<&mut T, ProgramError> {
+    let bytes = data.get_mut(..core::mem::size_of::<T>()).ok_or(ProgramError::AccountDataTooSmall)?;
+    bytemuck::try_from_bytes_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Member arrays are zero-filled past their count, so the all-zeros pubkey marks an empty
+/// slot and is never a member
+pub fn is_empty_slot(key: &Pubkey) -> bool {
+    *key == Pubkey::default()
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_state {
+    use super::*;
+
+    /// Every length short of `T`, filled with arbitrary bytes, fails cleanly; the full
+    /// length and anything longer loads
+    fn check_truncations<T: Pod>() {
+        // Backed by u64s so every buffer is 8-byte aligned, as account data is
+        let mut backing = vec![0u64; core::mem::size_of::<T>() / 8 + 2];
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        for word in backing.iter_mut() {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            *word = seed;
+        }
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut backing);
+
+        for len in 0..core::mem::size_of::<T>() {
+            assert!(matches!(load::<T>(&bytes[..len]), Err(ProgramError::AccountDataTooSmall)), "length {}", len);
+            assert!(matches!(load_mut::<T>(&mut bytes[..len]), Err(ProgramError::AccountDataTooSmall)), "length {}", len);
+        }
+        assert!(load::<T>(&bytes[..core::mem::size_of::<T>()]).is_ok());
+        assert!(load_mut::<T>(bytes).is_ok());
+    }
+
+    #[test]
+    fn test_truncated_account_data_is_rejected() {
+        check_truncations::<Multisig>();
+        check_truncations::<MultisigConfig>();
+        check_truncations::<ProposalState>();
+        check_truncations::<VoteState>();
+        check_truncations::<VoteReceipt>();
+    }
+}
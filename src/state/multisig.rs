@@ -1,34 +1,486 @@
 use pinocchio::{
     account_info::AccountInfo, 
+    instruction::Seed,
+    program_error::ProgramError,
     pubkey::Pubkey
 };
 
+use bytemuck::{Pod, Zeroable};
+
+use crate::error::MultisigError;
+
+use super::{is_empty_slot, load, load_mut, ProposalStatus, MAX_MEMBERS};
+
+/// Permission bits of `Multisig::roles`
+pub const ROLE_CAN_PROPOSE: u8 = 1 << 0;
+pub const ROLE_CAN_VOTE: u8 = 1 << 1;
+pub const ROLE_CAN_EXECUTE: u8 = 1 << 2;
+pub const ROLE_ALL: u8 = ROLE_CAN_PROPOSE | ROLE_CAN_VOTE | ROLE_CAN_EXECUTE;
+
+/// Account layout, read in place: `#[repr(C)]` with every padding byte spelled out as a
+/// `_padding` field, so the struct has no implicit padding and any bytes are a valid value.
 #[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
 pub struct Multisig {
+    pub version: u8, // Layout version, `Multisig::VERSION` for accounts written by this code
     pub creator: Pubkey,
     pub num_members: u8,
-    pub members: [Pubkey; 10], // Adjust size as needed
+    pub members: [Pubkey; MAX_MEMBERS],
+    pub _padding: [u8; 6], // Aligns weights to 8 bytes
+    pub weights: [u64; MAX_MEMBERS], // Vote weight of each member, indexed like `members`
+    pub delegations: [Pubkey; MAX_MEMBERS], // Member each member delegated its vote to, default if none
     pub bump: u8, // Bump seed for PDA
     pub treasury: Pubkey, // Treasury account for the multisig
     pub treasury_bump: u8, // Bump seed for the treasury PDA
-    
+    pub members_sorted: u8, // 1 once `members` is kept in ascending order, 0 on older accounts
+    pub roles: [u8; MAX_MEMBERS], // ROLE_* bitmask of each member, indexed like `members`; 0 grants every role
+    pub _padding_counters: [u8; 5], // Aligns the counters below
+    pub open_proposals: u32, // Proposal accounts created and not yet closed
+    pub proposal_nonce: u32, // Nonce of the latest proposal created, see `ProposalState.nonce`
+    pub total_proposals: u64, // Proposals ever created
+    pub succeeded_count: u64, // Proposals decided Succeeded, whether or not they were executed since
+    pub failed_count: u64, // Proposals decided Failed
+    pub executed_count: u64, // Proposals executed
+    pub members_root: [u8; 32], // Merkle root of the members allowed to vote, see `uses_members_root`; all zeros when votes use `members`
+    pub root_member_count: u64, // Members committed to by `members_root`, each voting with a weight of 1
+    pub participation_count: [u64; MAX_MEMBERS], // Proposals each member has voted on, indexed like `members`
+
 
     //threshold
     //treasury
     //treasury_bump
 }
 
+const _: () = assert!(core::mem::size_of::<Multisig>() == Multisig::LEN);
+// `LEN` is derived from `MAX_MEMBERS`; these keep the hand-written padding in step with it
+const _: () = assert!(core::mem::offset_of!(Multisig, members) == 1 + 32 + 1);
+const _: () = assert!(core::mem::offset_of!(Multisig, weights) == 1 + 32 + 1 + 32 * MAX_MEMBERS + 6);
+const _: () = assert!(core::mem::offset_of!(Multisig, weights) % 8 == 0);
+const _: () = assert!(core::mem::offset_of!(Multisig, delegations) == core::mem::offset_of!(Multisig, weights) + 8 * MAX_MEMBERS);
+const _: () = assert!(core::mem::offset_of!(Multisig, total_proposals) % 8 == 0);
+
 impl Multisig {
-    pub const LEN: usize = 32 + 1 + 32 * 10 + 1; // 32 bytes for creator, 1 byte for num_members, and 32 bytes for each member
+    /// Layout version written by this code; accounts carrying any other version are rejected
+    pub const VERSION: u8 = 1;
+
+    pub const LEN: usize = 1 + 32 + 1 + 32 * MAX_MEMBERS + 6 + 8 * MAX_MEMBERS + 32 * MAX_MEMBERS + 1 + 32 + 1 + 1 + MAX_MEMBERS + 5 + 4 + 4 + 8 * 4 + 32 + 8 + 8 * MAX_MEMBERS; // version, creator, num_members, members, padding, weights, delegations, bump, treasury, treasury_bump, members_sorted, roles, padding, open_proposals, proposal_nonce, the four statistics counters, members_root, root_member_count and participation_count
 
     pub fn from_account_info_unchecked(account_info: &AccountInfo) -> &mut Self {
         unsafe { &mut *(account_info.borrow_mut_data_unchecked().as_ptr() as *mut Self) }
     }
 
-    pub fn from_account_info(account_info: &AccountInfo) -> Result<&mut Self, pinocchio::program_error::ProgramError> {
+    /// Read-only view of the account, for instructions that don't change the multisig and
+    /// so don't need it writable
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<&Self, pinocchio::program_error::ProgramError> {
+        let multisig: &Self = load(unsafe { account_info.borrow_data_unchecked() })?;
+        multisig.check_loaded()?;
+        Ok(multisig)
+    }
+
+    pub fn from_account_info_mut(account_info: &AccountInfo) -> Result<&mut Self, pinocchio::program_error::ProgramError> {
+        let multisig: &mut Self = load_mut(unsafe { account_info.borrow_mut_data_unchecked() })?;
+        multisig.check_loaded()?;
+        Ok(multisig)
+    }
+
+    /// Checks run on every load: the layout version, and `num_members` fitting `members`,
+    /// so indexing the used members is always in bounds
+    fn check_loaded(&self) -> Result<(), ProgramError> {
+        if self.version != Self::VERSION {
+            return Err(MultisigError::UnsupportedAccountVersion.into());
+        }
+        if self.num_members as usize > MAX_MEMBERS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    /// True once a members root is set. Proposals created from then on are voted on by
+    /// members proving they are in the root instead of by `members`, which can then hold
+    /// far more voters than `MAX_MEMBERS`. Proposing and executing still need `members`.
+    pub fn uses_members_root(&self) -> bool {
+        self.members_root != [0u8; 32]
+    }
+
+    /// Counts a proposal reaching `status` in the statistics: Succeeded, Failed and Executed
+    /// each have a counter, other statuses aren't counted
+    pub fn record_outcome(&mut self, status: ProposalStatus) -> Result<(), ProgramError> {
+        let counter = match status {
+            ProposalStatus::Succeeded => &mut self.succeeded_count,
+            ProposalStatus::Failed => &mut self.failed_count,
+            ProposalStatus::Executed => &mut self.executed_count,
+            _ => return Ok(()),
+        };
+        *counter = counter.checked_add(1).ok_or(MultisigError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Counts a first vote by `member` on a proposal in its participation count. Voters who
+    /// aren't in `members`, such as those proven against a members root, aren't counted.
+    pub fn record_participation(&mut self, member: &Pubkey) -> Result<(), ProgramError> {
+        if let Some(index) = self.member_index(member) {
+            let count = &mut self.participation_count[index];
+            *count = count.checked_add(1).ok_or(MultisigError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Seeds signing as the multisig PDA `[b"multisig", creator, seed, bump]`, from the
+    /// stored creator and bump and the little-endian `seed` it was created with
+    pub fn signer_seeds<'a>(&'a self, seed: &'a [u8; 8]) -> [Seed<'a>; 4] {
+        [
+            Seed::from(b"multisig"),
+            Seed::from(&self.creator),
+            Seed::from(seed),
+            Seed::from(core::slice::from_ref(&self.bump)),
+        ]
+    }
+
+    /// Stamps a freshly created account with the current `VERSION` and returns it
+    pub fn initialize(account_info: &AccountInfo) -> Result<&mut Self, pinocchio::program_error::ProgramError> {
         if account_info.data_len() < Self::LEN {
             return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
         }
-        Ok(Self::from_account_info_unchecked(account_info))
+        unsafe { account_info.borrow_mut_data_unchecked() }[core::mem::offset_of!(Multisig, version)] = Self::VERSION;
+        Self::from_account_info_mut(account_info)
+    }
+
+    /// Position of `member` in `members`, or `None` if it is not a current member.
+    /// Empty slots never match, even for the all-zeros pubkey.
+    ///
+    /// Sorted member lists are binary searched; older, unsorted ones are scanned.
+    pub fn member_index(&self, member: &Pubkey) -> Option<usize> {
+        if is_empty_slot(member) {
+            return None;
+        }
+
+        let num_members = (self.num_members as usize).min(MAX_MEMBERS);
+        let members = &self.members[..num_members];
+
+        if self.members_sorted == 1 {
+            members.binary_search(member).ok()
+        } else {
+            members.iter().position(|m| m == member)
+        }
+    }
+
+    /// Sorts the current members into ascending order, moving each member's weight,
+    /// delegation, roles and participation count along with it, and marks the list as sorted
+    pub fn sort_members(&mut self) {
+        let num_members = (self.num_members as usize).min(MAX_MEMBERS);
+
+        // Insertion sort: no allocation, and member lists are short
+        for i in 1..num_members {
+            let mut j = i;
+            while j > 0 && self.members[j - 1] > self.members[j] {
+                self.members.swap(j - 1, j);
+                self.weights.swap(j - 1, j);
+                self.delegations.swap(j - 1, j);
+                self.roles.swap(j - 1, j);
+                self.participation_count.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+
+        self.members_sorted = 1;
+    }
+
+    /// Inserts `member` with `weight`, every role, no delegation and no votes counted at its sorted position, sorting an
+    /// older unsorted list first. The caller checks there is room for another member.
+    pub fn insert_member(&mut self, member: Pubkey, weight: u64) -> usize {
+        if self.members_sorted != 1 {
+            self.sort_members();
+        }
+
+        let num_members = self.num_members as usize;
+        let index = self.members[..num_members].partition_point(|m| m < &member);
+
+        self.members.copy_within(index..num_members, index + 1);
+        self.weights.copy_within(index..num_members, index + 1);
+        self.delegations.copy_within(index..num_members, index + 1);
+        self.roles.copy_within(index..num_members, index + 1);
+        self.participation_count.copy_within(index..num_members, index + 1);
+
+        self.members[index] = member;
+        self.weights[index] = weight;
+        self.delegations[index] = Pubkey::default();
+        self.roles[index] = ROLE_ALL;
+        self.participation_count[index] = 0;
+        self.num_members += 1;
+
+        index
+    }
+
+    /// Puts `new_member` in the slot at `index`, keeping the slot's weight, roles, own
+    /// delegation and participation count, and moves delegations made to the old member over to the new one. The
+    /// index is kept, so a sorted list the new key doesn't fit into is marked unsorted and
+    /// scanned until `insert_member` sorts it again. The caller checks `new_member` isn't
+    /// already a member.
+    pub fn replace_member(&mut self, index: usize, new_member: Pubkey) {
+        let num_members = (self.num_members as usize).min(MAX_MEMBERS);
+        let old_member = core::mem::replace(&mut self.members[index], new_member);
+
+        for delegate in self.delegations[..num_members].iter_mut().filter(|delegate| **delegate == old_member) {
+            *delegate = new_member;
+        }
+
+        let in_order = (index == 0 || self.members[index - 1] < new_member)
+            && (index + 1 >= num_members || new_member < self.members[index + 1]);
+        if !in_order {
+            self.members_sorted = 0;
+        }
+    }
+
+    pub fn is_member(&self, member: &Pubkey) -> bool {
+        self.member_index(member).is_some()
+    }
+
+    /// True if any current member appears more than once
+    pub fn has_duplicate_members(&self) -> bool {
+        let num_members = (self.num_members as usize).min(MAX_MEMBERS);
+        let members = &self.members[..num_members];
+        members.iter().enumerate().any(|(i, member)| members[i + 1..].contains(member))
+    }
+
+    /// Vote weight of `member`, or 0 if it is not a current member.
+    /// Accounts created before weights existed store 0, which counts as the default weight of 1.
+    pub fn weight_of(&self, member: &Pubkey) -> u64 {
+        self.member_index(member)
+            .map(|i| self.weights[i].max(1))
+            .unwrap_or(0)
+    }
+
+    /// True if `member` is a current member holding every bit of `role`. Accounts created
+    /// before roles existed store 0, which grants every role.
+    pub fn has_role(&self, member: &Pubkey, role: u8) -> bool {
+        self.member_index(member).is_some_and(|i| {
+            let roles = match self.roles[i] {
+                0 => ROLE_ALL,
+                roles => roles,
+            };
+            roles & role == role
+        })
+    }
+
+    /// Combined vote weight of all current members, the most For votes a proposal can get
+    pub fn total_weight(&self) -> u64 {
+        let num_members = (self.num_members as usize).min(MAX_MEMBERS);
+        (0..num_members)
+            .filter(|&i| !is_empty_slot(&self.members[i]))
+            .fold(0u64, |total, i| total.saturating_add(self.weights[i].max(1)))
+    }
+
+    /// Members who can vote and their combined weight, what thresholds and quorums are
+    /// bounded by: the root's members, each weighing 1, while a members root is set
+    pub fn eligible_voters(&self) -> (u64, u64) {
+        if self.uses_members_root() {
+            return (self.root_member_count, self.root_member_count);
+        }
+
+        (self.num_members as u64, self.total_weight())
+    }
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_multisig {
+    use super::*;
+
+    #[test]
+    fn test_signer_seeds_derive_the_multisig_pda() {
+        let program_id = solana_sdk::pubkey::Pubkey::new_from_array([9u8; 32]);
+        let creator = solana_sdk::pubkey::Pubkey::new_unique();
+        let seed = 7u64.to_le_bytes();
+        let (multisig_pda, bump) = solana_sdk::pubkey::Pubkey::find_program_address(
+            &[b"multisig", creator.as_ref(), &seed],
+            &program_id,
+        );
+
+        let mut multisig = Multisig::zeroed();
+        multisig.creator = creator.to_bytes();
+        multisig.bump = bump;
+
+        // The runtime accepts a signed CPI when the seeds derive the signing account
+        let signer_seeds = multisig.signer_seeds(&seed);
+        let seeds: Vec<&[u8]> = signer_seeds.iter().map(|seed| &**seed).collect();
+        assert_eq!(solana_sdk::pubkey::Pubkey::create_program_address(&seeds, &program_id).unwrap(), multisig_pda);
+    }
+
+    #[test]
+    fn test_multisig_bytes_round_trip() {
+        let mut multisig = Multisig::zeroed();
+        multisig.creator = [1u8; 32];
+        multisig.num_members = 2;
+        multisig.members[0] = [2u8; 32];
+        multisig.members[1] = [3u8; 32];
+        multisig.weights[1] = 5;
+        multisig.bump = 254;
+        multisig.treasury_bump = 253;
+
+        let bytes = bytemuck::bytes_of(&multisig).to_vec();
+        assert_eq!(bytes.len(), Multisig::LEN);
+
+        let decoded: Multisig = bytemuck::pod_read_unaligned(&bytes);
+        assert_eq!(decoded.creator, [1u8; 32]);
+        assert_eq!(decoded.num_members, 2);
+        assert_eq!(decoded.members[1], [3u8; 32]);
+        assert_eq!(decoded.weight_of(&[3u8; 32]), 5);
+        assert_eq!(decoded.bump, 254);
+        assert_eq!(decoded.treasury_bump, 253);
+    }
+
+    #[test]
+    fn test_every_member_slot_round_trips() {
+        let mut multisig = Multisig::zeroed();
+        multisig.num_members = MAX_MEMBERS as u8;
+        for i in 0..MAX_MEMBERS {
+            multisig.members[i] = [(i + 1) as u8; 32];
+            multisig.weights[i] = i as u64 + 1;
+            multisig.delegations[i] = [(i + 101) as u8; 32];
+        }
+        multisig.bump = 254;
+
+        let bytes = bytemuck::bytes_of(&multisig).to_vec();
+        assert_eq!(bytes.len(), Multisig::LEN);
+
+        // The last member slot sits exactly where the derived layout puts it
+        let last_member = core::mem::offset_of!(Multisig, members) + (MAX_MEMBERS - 1) * 32;
+        assert_eq!(&bytes[last_member..last_member + 32], &[MAX_MEMBERS as u8; 32]);
+
+        let decoded: Multisig = bytemuck::pod_read_unaligned(&bytes);
+        for i in 0..MAX_MEMBERS {
+            assert_eq!(decoded.members[i], [(i + 1) as u8; 32]);
+            assert_eq!(decoded.weights[i], i as u64 + 1);
+            assert_eq!(decoded.delegations[i], [(i + 101) as u8; 32]);
+        }
+        assert_eq!(decoded.bump, 254);
+    }
+
+    #[test]
+    fn test_empty_slot_is_not_a_member() {
+        let mut multisig = Multisig::zeroed();
+        // A zero-filled slot inside the member count, as left by older accounts
+        multisig.num_members = 2;
+        multisig.members[0] = [2u8; 32];
+
+        assert_eq!(multisig.member_index(&[2u8; 32]), Some(0));
+        assert!(!multisig.is_member(&Pubkey::default()));
+        assert_eq!(multisig.weight_of(&Pubkey::default()), 0);
+    }
+
+    fn sorted_members(count: usize) -> Multisig {
+        let mut multisig = Multisig::zeroed();
+        multisig.num_members = count as u8;
+        for i in 0..count {
+            multisig.members[i] = [(i + 1) as u8; 32];
+        }
+        multisig.members_sorted = 1;
+        multisig
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_binary_search_finds_every_member() {
+        let multisig = sorted_members(MAX_MEMBERS);
+
+        for i in 0..MAX_MEMBERS {
+            assert_eq!(multisig.member_index(&[(i + 1) as u8; 32]), Some(i));
+        }
+        assert_eq!(multisig.member_index(&[0xff; 32]), None);
+        assert_eq!(multisig.member_index(&Pubkey::default()), None);
+    }
+
+    #[test]
+    fn test_binary_search_matches_linear_scan_on_full_multisig() {
+        let sorted = sorted_members(MAX_MEMBERS);
+        let mut unsorted = sorted;
+        unsorted.members_sorted = 0;
+
+        for byte in 0..=u8::MAX {
+            let key = [byte; 32];
+            assert_eq!(sorted.member_index(&key), unsorted.member_index(&key));
+        }
+    }
+
+    #[test]
+    fn test_sort_members_moves_weights_and_delegations_along() {
+        let mut multisig = Multisig::zeroed();
+        multisig.num_members = 3;
+        multisig.members[0] = [9u8; 32];
+        multisig.members[1] = [3u8; 32];
+        multisig.members[2] = [6u8; 32];
+        multisig.weights[0] = 9;
+        multisig.delegations[0] = [3u8; 32];
+        multisig.participation_count[0] = 4;
+
+        multisig.sort_members();
+
+        assert_eq!(&multisig.members[..3], &[[3u8; 32], [6u8; 32], [9u8; 32]]);
+        assert_eq!(multisig.weights[2], 9);
+        assert_eq!(multisig.delegations[2], [3u8; 32]);
+        assert_eq!(multisig.participation_count[2], 4);
+        assert_eq!(multisig.member_index(&[9u8; 32]), Some(2));
+    }
+
+    #[test]
+    fn test_insert_member_keeps_members_sorted() {
+        let mut multisig = sorted_members(3);
+        multisig.members[1] = [5u8; 32];
+        multisig.members[2] = [7u8; 32];
+
+        assert_eq!(multisig.insert_member([6u8; 32], 2), 2);
+        assert_eq!(&multisig.members[..4], &[[1u8; 32], [5u8; 32], [6u8; 32], [7u8; 32]]);
+        assert_eq!(multisig.weights[2], 2);
+        assert_eq!(multisig.num_members, 4);
+    }
+
+    #[test]
+    fn test_replace_member_keeps_slot_and_moves_delegations() {
+        let mut multisig = sorted_members(3);
+        multisig.weights[1] = 4;
+        multisig.roles[1] = ROLE_CAN_VOTE;
+        multisig.delegations[0] = [2u8; 32];
+
+        // [2; 32] -> [2, 0, ..] still sits between its neighbours
+        let mut in_order = [2u8; 32];
+        in_order[1] = 0;
+        multisig.replace_member(1, in_order);
+
+        assert_eq!(multisig.member_index(&in_order), Some(1));
+        assert_eq!(multisig.member_index(&[2u8; 32]), None);
+        assert_eq!(multisig.weight_of(&in_order), 4);
+        assert_eq!(multisig.roles[1], ROLE_CAN_VOTE);
+        assert_eq!(multisig.delegations[0], in_order);
+        assert_eq!(multisig.members_sorted, 1);
+
+        // A key past its right neighbour keeps the index and falls back to scanning
+        multisig.replace_member(1, [9u8; 32]);
+
+        assert_eq!(multisig.members_sorted, 0);
+        assert_eq!(multisig.member_index(&[9u8; 32]), Some(1));
+        assert_eq!(multisig.member_index(&[3u8; 32]), Some(2));
+    }
+
+    #[test]
+    fn test_unset_roles_grant_every_permission() {
+        let mut multisig = sorted_members(2);
+        multisig.roles[1] = ROLE_CAN_VOTE;
+
+        assert!(multisig.has_role(&[1u8; 32], ROLE_ALL));
+        assert!(multisig.has_role(&[2u8; 32], ROLE_CAN_VOTE));
+        assert!(!multisig.has_role(&[2u8; 32], ROLE_CAN_PROPOSE));
+        assert!(!multisig.has_role(&[3u8; 32], ROLE_CAN_VOTE));
+    }
+
+    #[test]
+    fn test_total_weight_counts_legacy_members_as_one() {
+        let mut multisig = Multisig::zeroed();
+        multisig.num_members = 3;
+        multisig.members[0] = [2u8; 32];
+        multisig.members[1] = [3u8; 32];
+        multisig.members[2] = [4u8; 32];
+        multisig.weights[1] = 5;
+
+        assert_eq!(multisig.total_weight(), 7);
+    }
+}
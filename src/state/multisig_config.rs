@@ -1,27 +1,231 @@
 use pinocchio::{
     account_info::AccountInfo, 
-    pubkey::Pubkey
+    program_error::ProgramError,
+    pubkey::Pubkey,
 };
 
+use bytemuck::{Pod, Zeroable};
+
+use crate::error::MultisigError;
+
+use super::{load, load_mut, MAX_PROPOSAL_DURATION};
+
+pub const MAX_PROPOSAL_TYPES: usize = 8; // Slots in `MultisigConfig.type_thresholds`, one per `ProposalType` value
+
+/// Account layout, read in place: `#[repr(C)]` with explicit `_padding` fields aligning
+/// each `u64`, so no byte of the struct is implicit padding.
 #[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
 pub struct MultisigConfig {
+    pub version: u8, // Layout version, `MultisigConfig::VERSION` for accounts written by this code
+    pub governed_by_proposals: u8, // 1 refuses direct config changes, leaving them to proposals the multisig executes
+    pub _padding_version: [u8; 6], // Aligns min_threshold to 8 bytes
     pub min_threshold: u64, // minimum number of signers required to execute a proposal
-    pub max_expiry: u64,// Adjust size as needed
+    pub max_proposal_duration: u64, // Longest duration a new proposal may be created with, in seconds; 0 uses MAX_PROPOSAL_DURATION
     pub proposal_count: u64, // proposal counter
+    pub quorum: u64, // minimum number of For + Against + Abstain votes before a proposal can be decided; snapshotted by each proposal at creation
+    pub execution_delay: u64, // seconds a Succeeded proposal waits before it can be executed; 0 executes immediately
+    pub veto_authority: Pubkey, // may cancel any non-terminal proposal; all zeros disables the veto
+    pub config_authority: Pubkey, // must sign direct config changes; defaults to the multisig creator
     pub bump: u8, // Bump seed for PDA   
+    pub threshold_kind: u8, // ThresholdKind: how the required number of For votes is computed
+    pub threshold_value: u8, // percentage of members (1-100) used when threshold_kind is Percentage
+    pub fail_when_unreachable: u8, // 1 fails a proposal as soon as the unvoted weight can no longer carry it to the threshold
+    pub require_unanimous: u8, // 1 replaces the threshold with every snapshotted member voting For
+    pub unanimous_abstain_fails: u8, // With require_unanimous, 1 fails a proposal on the first abstain instead of leaving it to expire
+    pub paused: u8, // 1 while the config authority's emergency pause halts voting, proposing and execution
+    pub allow_vote_change: u8, // 1 lets members change a cast vote with the vote's change flag; 0 rejects a second vote
+    pub type_thresholds: [u64; MAX_PROPOSAL_TYPES], // Absolute threshold per `ProposalType`, indexed by its value; 0 falls back to the general threshold
+    pub defeat_threshold: u64, // Against votes that fail a proposal; 0 uses the proposal's approval threshold
 }
 
+const _: () = assert!(core::mem::size_of::<MultisigConfig>() == MultisigConfig::LEN);
+
 impl MultisigConfig {
-    pub const LEN: usize = 8 + 8 + 8 + 1; // 32 bytes for creator, 1 byte for num_members, and 32 bytes for each member
+    /// Layout version written by this code
+    pub const VERSION: u8 = 4;
+
+    pub const LEN: usize = 1 + 1 + 6 + 8 + 8 + 8 + 8 + 8 + 32 + 32 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 8 * MAX_PROPOSAL_TYPES + 8; // version, governed_by_proposals, padding, min_threshold, max_proposal_duration, proposal_count, quorum, execution_delay, veto_authority, config_authority, bump, threshold_kind, threshold_value, fail_when_unreachable, require_unanimous, unanimous_abstain_fails, paused, allow_vote_change, type_thresholds and defeat_threshold
 
     pub fn from_account_info_unchecked(account_info: &AccountInfo) -> &mut Self {
         unsafe { &mut *(account_info.borrow_mut_data_unchecked().as_ptr() as *mut Self) }
     }
 
-    pub fn from_account_info(account_info: &AccountInfo) -> Result<&mut Self, pinocchio::program_error::ProgramError> {
+    /// Read-only view of the account, for instructions that don't change the config and
+    /// so don't need it writable
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<&Self, pinocchio::program_error::ProgramError> {
+        let config: &Self = load(unsafe { account_info.borrow_data_unchecked() })?;
+
+        if config.version != Self::VERSION {
+            return Err(MultisigError::UnsupportedAccountVersion.into());
+        }
+        Ok(config)
+    }
+
+    pub fn from_account_info_mut(account_info: &AccountInfo) -> Result<&mut Self, pinocchio::program_error::ProgramError> {
+        let config: &mut Self = load_mut(unsafe { account_info.borrow_mut_data_unchecked() })?;
+
+        if config.version != Self::VERSION {
+            return Err(MultisigError::UnsupportedAccountVersion.into());
+        }
+        Ok(config)
+    }
+
+    /// Stamps a freshly created account with the current `VERSION` and returns it
+    pub fn initialize(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
         if account_info.data_len() < Self::LEN {
-            return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+            return Err(ProgramError::InvalidAccountData);
+        }
+        unsafe { account_info.borrow_mut_data_unchecked() }[core::mem::offset_of!(MultisigConfig, version)] = Self::VERSION;
+        Self::from_account_info_mut(account_info)
+    }
+
+    /// Copy of the config, for read-only callers that keep it past the account borrow
+    pub fn load(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        Self::read(unsafe { account_info.borrow_data_unchecked() })
+    }
+
+    /// Decodes config account data, rejecting any version other than the current one
+    pub fn read(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let config: Self = bytemuck::pod_read_unaligned(&data[..Self::LEN]);
+        if config.version != Self::VERSION {
+            return Err(MultisigError::UnsupportedAccountVersion.into());
+        }
+        Ok(config)
+    }
+
+    /// Fails with `UnauthorizedConfigChange` once the config is `governed_by_proposals`, for
+    /// changes made outside a proposal executed by the multisig
+    pub fn require_direct_changes_allowed(&self) -> Result<(), ProgramError> {
+        if self.governed_by_proposals == 1 {
+            return Err(MultisigError::UnauthorizedConfigChange.into());
+        }
+        Ok(())
+    }
+
+    /// Succeeds if `config_authority` is among `signers` and has signed
+    pub fn check_config_authority(&self, signers: &[AccountInfo]) -> Result<(), ProgramError> {
+        if signers.iter().any(|signer| signer.is_signer() && signer.key() == &self.config_authority) {
+            return Ok(());
+        }
+        Err(MultisigError::UnauthorizedConfigChange.into())
+    }
+
+    /// Longest duration a new proposal may be created with: the configured
+    /// `max_proposal_duration`, never beyond the program-wide `MAX_PROPOSAL_DURATION`
+    pub fn proposal_duration_limit(&self) -> u64 {
+        match self.max_proposal_duration {
+            0 => MAX_PROPOSAL_DURATION,
+            limit => limit.min(MAX_PROPOSAL_DURATION),
+        }
+    }
+
+    /// Fails with `Paused` while the emergency pause is on
+    pub fn require_not_paused(&self) -> Result<(), ProgramError> {
+        if self.paused != 0 {
+            return Err(MultisigError::Paused.into());
         }
-        Ok(Self::from_account_info_unchecked(account_info))
+        Ok(())
     }
-}
\ No newline at end of file
+
+    /// Number of votes needed to decide a proposal for a multisig with `num_members` members
+    pub fn required_votes(&self, num_members: u64) -> Result<u64, ProgramError> {
+        match ThresholdKind::try_from(&self.threshold_kind)? {
+            ThresholdKind::Absolute => Ok(self.min_threshold),
+            ThresholdKind::Percentage => Ok(Self::percentage_of_members(num_members, self.threshold_value)),
+        }
+    }
+
+    /// Number of votes needed to decide a proposal of `proposal_type`: its own threshold
+    /// if one is set, the general one otherwise
+    pub fn required_votes_for(&self, proposal_type: u8, num_members: u64) -> Result<u64, ProgramError> {
+        match self.type_thresholds.get(proposal_type as usize) {
+            Some(&threshold) if threshold != 0 => Ok(threshold),
+            _ => self.required_votes(num_members),
+        }
+    }
+
+    /// Against votes that fail a proposal needing `required_votes` For votes to pass
+    pub fn defeat_votes(&self, required_votes: u64) -> u64 {
+        match self.defeat_threshold {
+            0 => required_votes,
+            threshold => threshold,
+        }
+    }
+
+    /// `ceil(num_members * percentage / 100)`, always rounding up so a percentage is never undershot
+    pub fn percentage_of_members(num_members: u64, percentage: u8) -> u64 {
+        num_members.saturating_mul(percentage as u64).div_ceil(100)
+    }
+}
+
+#[repr(u8)]
+pub enum ThresholdKind {
+    Absolute = 0,
+    Percentage = 1,
+}
+
+impl TryFrom<&u8> for ThresholdKind {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match *value {
+            0 => Ok(ThresholdKind::Absolute),
+            1 => Ok(ThresholdKind::Percentage),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_multisig_config {
+    use super::*;
+
+    #[test]
+    fn test_percentage_threshold_rounds_up() {
+        // 51% of 3 members is 1.53, which needs 2 votes
+        assert_eq!(MultisigConfig::percentage_of_members(3, 51), 2);
+        // 67% of 9 members is 6.03, which needs 7 votes: 6 of 9 is only 66.7%
+        assert_eq!(MultisigConfig::percentage_of_members(9, 67), 7);
+        // 66% of 9 members is 5.94, which needs 6 votes
+        assert_eq!(MultisigConfig::percentage_of_members(9, 66), 6);
+    }
+
+    #[test]
+    fn test_unknown_config_version_is_rejected() {
+        let mut config = MultisigConfig::zeroed();
+        config.version = MultisigConfig::VERSION;
+        config.min_threshold = 2;
+        assert_eq!(MultisigConfig::read(bytemuck::bytes_of(&config)).unwrap().min_threshold, 2);
+
+        config.version = MultisigConfig::VERSION + 1;
+        assert!(matches!(
+            MultisigConfig::read(bytemuck::bytes_of(&config)),
+            Err(ProgramError::Custom(code)) if code == MultisigError::UnsupportedAccountVersion as u32
+        ));
+    }
+
+    #[test]
+    fn test_type_threshold_overrides_general_threshold() {
+        let mut config = MultisigConfig::zeroed();
+        config.min_threshold = 1;
+        config.type_thresholds[1] = 3;
+
+        assert_eq!(config.required_votes_for(1, 5).unwrap(), 3);
+        assert_eq!(config.required_votes_for(0, 5).unwrap(), 1);
+        // Types without a slot fall back as well
+        assert_eq!(config.required_votes_for(MAX_PROPOSAL_TYPES as u8, 5).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_percentage_threshold_exact_division() {
+        assert_eq!(MultisigConfig::percentage_of_members(10, 50), 5);
+        assert_eq!(MultisigConfig::percentage_of_members(4, 100), 4);
+    }
+}
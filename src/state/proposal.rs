@@ -2,49 +2,525 @@ use pinocchio::{
     account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey
 };
 
+use bytemuck::{Pod, Zeroable};
+
+use crate::{error::MultisigError, events::ProposalSnapshot};
+
+use super::{is_empty_slot, load_mut, Multisig, MultisigConfig, VoteChoice, VoteState, MAX_MEMBERS, MAX_PROPOSAL_TYPES};
+
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 8; // Maximum accounts the stored instruction can reference
+pub const MAX_PROPOSAL_DATA_LEN: usize = 1024; // Maximum size of the stored instruction data, bounds the proposal's rent
+pub const MAX_PROPOSAL_PAYLOAD_LEN: usize = 40; // Largest typed payload (Transfer: recipient + amount)
+pub const MAX_PROPOSAL_OPTIONS: usize = 8; // Most options a Poll proposal can offer
+pub const MAX_PROPOSAL_DURATION: u64 = 30 * 24 * 60 * 60; // Longest a proposal can stay open, in seconds
+pub const MAX_EXECUTION_DELAY: u64 = 30 * 24 * 60 * 60; // Longest timelock a config can put between success and execution, in seconds
+pub const PROPOSAL_LINK_LEN: usize = 64; // Size of `ProposalState.link`
+pub const MAX_CPI_ACCOUNTS: usize = 6; // Most accounts an executed CPI may pass, bounding its compute
+pub const MAX_CPI_DATA_LEN: usize = 512; // Most instruction data an executed CPI may pass
+
+// The CPI limits are tighter than what a proposal can store, so they bound something
+const _: () = assert!(MAX_CPI_ACCOUNTS < MAX_PROPOSAL_ACCOUNTS);
+const _: () = assert!(MAX_CPI_DATA_LEN < MAX_PROPOSAL_DATA_LEN);
+
+/// Fixed part of a proposal account, read in place and followed by the stored instruction
+/// data. `#[repr(C)]` with explicit `_padding` fields, so it has no implicit padding.
 #[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
 pub struct ProposalState {
     pub proposal_id: u64, // Unique identifier for the proposal
     pub expiry: u64,// Adjust size as needed is it needed here?
-    pub result: ProposalStatus,
+    pub result: u8, // ProposalStatus, read and written through `status` and `set_status`
     pub bump: u8, // Bump seed for PDA
-    pub active_members: [Pubkey; 10], // Array to hold active members, adjust size as needed
-
-    //VOTE 0 - NOT VOTED
-    //VOTE 1 - FOR
-    //VOTE 2 - AGAINST
-    //VOTE 3 - ABSTAIN
-    pub votes:[u8; 10], //[0,3,2,1,3,0,0,0,2,1....]
+    pub active_member_count: u8, // Number of entries used in `active_members`
+    pub active_members: [Pubkey; MAX_MEMBERS], // Array to hold active members
+    pub _padding: [u8; 5], // Aligns active_weights to 8 bytes
+    pub active_weights: [u64; MAX_MEMBERS], // Vote weight of each snapshotted member, indexed like `active_members`; all zeros on older proposals
 
     // imo slot
     pub created_time: u64,
+    pub start_time: u64, // Votes are rejected before this time; set to created_time at creation
     // analysis period
+
+    pub executed: u8, // Set to 1 when execution starts, before any CPI; the status becomes Executed once it completes
+    pub num_accounts: u8, // Number of entries used in `accounts`
+    pub instruction_data_len: u16, // Size of the stored instruction data that follows this struct in the account
+    pub target_program: Pubkey, // Program invoked when the proposal is executed
+    pub accounts: [ProposalAccountMeta; MAX_PROPOSAL_ACCOUNTS], // Account metas of the stored instruction
+    pub proposer: Pubkey, // Member who created the proposal, the only one allowed to cancel it
+    pub nonce: u32, // Unique per proposal of the multisig, so accounts of a closed proposal at the same id are told apart; 0 on older proposals
+    pub for_votes: u64, // Running weighted tally, kept in sync with the `VoteReceipt`s
+    pub against_votes: u64,
+    pub abstain_votes: u64,
+    pub required_votes: u64, // Threshold snapshot taken at creation, 0 on proposals created before it existed
+    pub succeeded_at: u64, // Time the proposal first reached Succeeded, 0 until then
+    pub execution_delay: u64, // Timelock snapshot of the config's `execution_delay`, taken at creation
+    pub proposal_type: u8, // ProposalType: what executing the proposal does
+    pub payload: [u8; MAX_PROPOSAL_PAYLOAD_LEN], // Arguments of the typed action, `ProposalType::payload_len` bytes used
+    pub num_options: u8, // Options of a Poll proposal, votes choose 1..=num_options; 0 for For/Against/Abstain proposals
+    pub winning_option: u8, // Option a decided Poll settled on, 0 until then
+    pub _padding_options: [u8; 5], // Aligns option_votes to 8 bytes
+    pub option_votes: [u64; MAX_PROPOSAL_OPTIONS], // Running weighted tally of each Poll option, option i at index i - 1
+    pub link: [u8; PROPOSAL_LINK_LEN], // Opaque pointer to off-chain metadata (e.g. an IPFS CID), all zeros when none was given
+    pub last_voter: Pubkey, // Signer of the latest accepted vote, the delegate for a delegated vote; all zeros before the first
+    pub last_vote_slot: u64, // Slot of the latest accepted vote, 0 before the first
+    pub members_root: [u8; 32], // Snapshot of `Multisig.members_root`; when set, voters prove membership in it instead of `active_members`
+    pub root_member_count: u64, // Snapshot of `Multisig.root_member_count`
+    pub quorum: u64, // Quorum snapshot of the config's `quorum`, taken at creation
 }
 
 impl ProposalState {
-    pub const LEN: usize = 8 + 8 + 1 + 1 + 32 * 10 + 32 * 10 + 32 * 10 + 8 ; // Adjust size as needed
+    /// Size of the fixed part. The account holds `BASE_LEN + instruction_data_len` bytes,
+    /// the stored instruction data following the fixed part.
+    pub const BASE_LEN: usize = 8 + 8 + 1 + 1 + 1 + 32 * MAX_MEMBERS + 5 + 8 * MAX_MEMBERS + 8 + 8 + 1 + 1 + 2 + 32
+        + ProposalAccountMeta::LEN * MAX_PROPOSAL_ACCOUNTS + 32 + 4 + 8 + 8 + 8 + 8 + 8 + 8
+        + 1 + MAX_PROPOSAL_PAYLOAD_LEN + 1 + 1 + 5 + 8 * MAX_PROPOSAL_OPTIONS + PROPOSAL_LINK_LEN + 32 + 8 + 32 + 8 + 8;
 
     pub fn from_account_info_unchecked(account_info: &AccountInfo) -> &mut Self {
         unsafe { &mut *(account_info.borrow_mut_data_unchecked().as_ptr() as *mut Self) }
     }
 
     pub fn from_account_info(account_info: &AccountInfo) -> Result<&mut Self, pinocchio::program_error::ProgramError> {
-        if account_info.data_len() < Self::LEN {
-            return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+        let proposal: &mut Self = load_mut(unsafe { account_info.borrow_mut_data_unchecked() })?;
+        proposal.check_status()?;
+        Ok(proposal)
+    }
+
+    /// Like `from_account_info`, also returning the stored instruction data that follows
+    /// the fixed part, exactly `instruction_data_len` bytes long
+    pub fn from_account_info_with_data(account_info: &AccountInfo) -> Result<(&mut Self, &mut [u8]), ProgramError> {
+        let data = unsafe { account_info.borrow_mut_data_unchecked() };
+        if data.len() < Self::BASE_LEN {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        let (fixed, instruction_data) = data.split_at_mut(Self::BASE_LEN);
+        let proposal: &mut Self = load_mut(fixed)?;
+        proposal.check_status()?;
+
+        // Creation caps the length at `MAX_PROPOSAL_DATA_LEN`; execution bounds what it forwards
+        let instruction_data_len = proposal.instruction_data_len as usize;
+        if instruction_data_len > instruction_data.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((proposal, &mut instruction_data[..instruction_data_len]))
+    }
+
+    pub fn status(&self) -> Result<ProposalStatus, ProgramError> {
+        ProposalStatus::try_from(&self.result)
+    }
+
+    /// A stored `result` byte outside `ProposalStatus` means the account is corrupt
+    fn check_status(&self) -> Result<(), ProgramError> {
+        self.status().map(|_| ()).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn set_status(&mut self, status: ProposalStatus) {
+        self.result = status as u8;
+    }
+
+    /// Position of `member` in the `active_members` snapshot. Empty slots never match,
+    /// even for the all-zeros pubkey.
+    pub fn active_member_index(&self, member: &Pubkey) -> Option<usize> {
+        let active_member_count = (self.active_member_count as usize).min(MAX_MEMBERS);
+        self.active_members[..active_member_count]
+            .iter()
+            .position(|m| !is_empty_slot(m) && m == member)
+    }
+
+    /// True when the proposal was created with a members root: votes carry a proof of
+    /// membership in `members_root`, and every proven member votes with a weight of 1
+    pub fn uses_members_root(&self) -> bool {
+        self.members_root != [0u8; 32]
+    }
+
+    /// Vote weight of `member` as snapshotted at creation, so weight changes only apply to
+    /// proposals created after them. Proposals created before weights were snapshotted
+    /// fall back to the multisig's current weights. On a members root proposal every
+    /// member weighs 1, and membership must have been proven first.
+    pub fn weight_of(&self, member: &Pubkey, multisig: &Multisig) -> u64 {
+        if self.uses_members_root() {
+            return 1;
+        }
+
+        match self.active_member_index(member) {
+            Some(i) if self.active_weights[i] != 0 => self.active_weights[i],
+            _ => multisig.weight_of(member),
+        }
+    }
+
+    /// Snapshotted members who may vote on the proposal; empty slots in the snapshot aren't members
+    pub fn members_eligible(&self) -> u64 {
+        if self.uses_members_root() {
+            return self.root_member_count;
+        }
+
+        let active_member_count = (self.active_member_count as usize).min(MAX_MEMBERS);
+
+        self.active_members[..active_member_count]
+            .iter()
+            .filter(|member| !is_empty_slot(member))
+            .count() as u64
+    }
+
+    /// Eligible members `vote_state` records as having voted. Only a cast vote sets a
+    /// member's bit, so neither empty slots nor members who haven't voted yet count.
+    /// Members root proposals have no bitmap and count the votes instead.
+    pub fn members_voted(&self, vote_state: &VoteState) -> u64 {
+        if self.uses_members_root() {
+            return vote_state.vote_count;
+        }
+
+        let active_member_count = (self.active_member_count as usize).min(MAX_MEMBERS);
+
+        (0..active_member_count)
+            .filter(|&i| !is_empty_slot(&self.active_members[i]) && vote_state.has_voted(i))
+            .count() as u64
+    }
+
+    /// Combined vote weight of the snapshotted members, the most For votes the proposal can get
+    pub fn total_weight(&self, multisig: &Multisig) -> u64 {
+        if self.uses_members_root() {
+            return self.root_member_count;
+        }
+
+        let active_member_count = (self.active_member_count as usize).min(MAX_MEMBERS);
+
+        if self.active_weights[..active_member_count].iter().all(|&weight| weight == 0) {
+            return multisig.total_weight();
+        }
+
+        (0..active_member_count)
+            .filter(|&i| !is_empty_slot(&self.active_members[i]))
+            .fold(0u64, |total, i| total.saturating_add(self.active_weights[i]))
+    }
+
+    /// Votes needed to decide the proposal. Threshold changes only apply to proposals
+    /// created after them; proposals without a snapshot fall back to the live config's
+    /// threshold for their type.
+    pub fn effective_required_votes(&self, config: &MultisigConfig) -> Result<u64, ProgramError> {
+        match self.required_votes {
+            0 => config.required_votes_for(self.proposal_type, self.active_member_count as u64),
+            snapshot => Ok(snapshot),
+        }
+    }
+
+    /// Current For, Against and Abstain tallies and the status they project to at
+    /// `current_time`, without modifying the proposal. For votes reaching `required_votes`
+    /// pass it and Against votes reaching `defeat_votes` fail it. A terminal proposal
+    /// projects to its own status.
+    pub fn tally(&self, required_votes: u64, defeat_votes: u64, quorum: u64, current_time: u64) -> Result<(u64, u64, u64, ProposalStatus), ProgramError> {
+        let status = self.status()?;
+        // Abstain votes count toward quorum but never toward approval
+        let quorum_reached = self.total_votes()? >= quorum;
+
+        let projection = if status.is_terminal() {
+            status
+        } else if quorum_reached && self.for_votes >= required_votes {
+            ProposalStatus::Succeeded
+        } else if quorum_reached && self.against_votes >= defeat_votes {
+            ProposalStatus::Failed
+        } else if self.is_expired(current_time) {
+            ProposalStatus::Cancelled
+        } else {
+            status
+        };
+
+        Ok((self.for_votes, self.against_votes, self.abstain_votes, projection))
+    }
+
+    /// Status a non-terminal proposal projects to when approval must be unanimous: it
+    /// succeeds once For carries the whole `total_weight` and fails on the first Against.
+    /// An abstain rules unanimity out, failing the proposal if `abstain_fails` and otherwise
+    /// leaving it Active until it expires.
+    pub fn unanimous_projection(&self, total_weight: u64, abstain_fails: bool, current_time: u64) -> Result<ProposalStatus, ProgramError> {
+        let status = self.status()?;
+
+        Ok(if status.is_terminal() {
+            status
+        } else if self.against_votes > 0 || (abstain_fails && self.abstain_votes > 0) {
+            ProposalStatus::Failed
+        } else if self.for_votes >= total_weight {
+            ProposalStatus::Succeeded
+        } else if self.is_expired(current_time) {
+            ProposalStatus::Cancelled
+        } else {
+            status
+        })
+    }
+
+    /// Most For votes the proposal can still end with: the For votes cast plus the weight
+    /// not yet cast. Against and Abstain votes can never add to it.
+    pub fn remaining_possible_for_votes(&self, total_weight: u64) -> Result<u64, ProgramError> {
+        let unvoted = total_weight.saturating_sub(self.total_votes()?);
+        Ok(self.for_votes.saturating_add(unvoted))
+    }
+
+    /// True while the For votes plus the weight not yet cast could still reach `required_votes`
+    pub fn approval_reachable(&self, required_votes: u64, total_weight: u64) -> Result<bool, ProgramError> {
+        Ok(self.remaining_possible_for_votes(total_weight)? >= required_votes)
+    }
+
+    /// Moves the proposal to a terminal `status`. Finalizing to the status it already has
+    /// is a no-op, but a terminal status is never reassigned to a different one.
+    pub fn finalize(&mut self, status: ProposalStatus) -> Result<(), ProgramError> {
+        let current = self.status()?;
+        if current.is_terminal() {
+            if current != status {
+                return Err(MultisigError::ProposalNotActive.into());
+            }
+            return Ok(());
+        }
+        self.set_status(status);
+        Ok(())
+    }
+
+    /// True for Poll proposals, whose votes pick one of `num_options` options
+    pub fn is_poll(&self) -> bool {
+        self.num_options != 0
+    }
+
+    /// Tallies of the options a Poll offers, empty for other proposals
+    pub fn poll_options(&self) -> &[u64] {
+        &self.option_votes[..(self.num_options as usize).min(MAX_PROPOSAL_OPTIONS)]
+    }
+
+    /// Fails with `InvalidVoteChoice` unless `choice` is a valid vote on this proposal:
+    /// an option of a Poll, or For, Against or Abstain otherwise. 0 is never valid.
+    pub fn validate_choice(&self, choice: u8) -> Result<(), ProgramError> {
+        let valid = match self.is_poll() {
+            true => (1..=self.poll_options().len()).contains(&(choice as usize)),
+            false => VoteChoice::try_from(choice).is_ok(),
+        };
+
+        if !valid {
+            return Err(MultisigError::InvalidVoteChoice.into());
+        }
+        Ok(())
+    }
+
+    /// Running tally that the raw `choice` byte counts toward, an option tally on Polls
+    pub fn choice_tally_mut(&mut self, choice: u8) -> Result<&mut u64, ProgramError> {
+        self.validate_choice(choice)?;
+
+        if self.is_poll() {
+            return Ok(&mut self.option_votes[choice as usize - 1]);
+        }
+        Ok(self.tally_mut(VoteChoice::try_from(choice)?))
+    }
+
+    /// Status a non-terminal Poll projects to, with the option it settles on. The first
+    /// option to reach `required_votes` once quorum is met wins; at expiry the plurality
+    /// option does.
+    pub fn poll_projection(&self, required_votes: u64, quorum: u64, current_time: u64) -> Result<(ProposalStatus, u8), ProgramError> {
+        let status = self.status()?;
+        if status.is_terminal() {
+            return Ok((status, self.winning_option));
+        }
+
+        let options = self.poll_options();
+        let total_votes = options
+            .iter()
+            .try_fold(0u64, |total, &votes| total.checked_add(votes))
+            .ok_or(MultisigError::ArithmeticOverflow)?;
+
+        // Votes only ever go to one option at a time, so at most the option just voted for
+        // can have crossed the threshold
+        if total_votes >= quorum {
+            if let Some(i) = options.iter().position(|&votes| votes >= required_votes) {
+                return Ok((ProposalStatus::Succeeded, i as u8 + 1));
+            }
+        }
+
+        if self.is_expired(current_time) {
+            return Ok(self.plurality());
+        }
+
+        Ok((status, 0))
+    }
+
+    /// Outcome of a Poll decided by plurality: the option with the most votes succeeds, and
+    /// a tie for the lead, or no votes at all, fails
+    pub fn plurality(&self) -> (ProposalStatus, u8) {
+        let options = self.poll_options();
+        let leading = options.iter().copied().max().unwrap_or(0);
+
+        match options.iter().filter(|&&votes| votes == leading).count() {
+            1 if leading > 0 => {
+                let i = options.iter().position(|&votes| votes == leading).unwrap();
+                (ProposalStatus::Succeeded, i as u8 + 1)
+            },
+            _ => (ProposalStatus::Failed, 0),
+        }
+    }
+
+    /// Finalizes a non-terminal proposal past its expiry: Cancelled, or for a Poll the
+    /// plurality outcome. Returns the status it ends in.
+    pub fn resolve_expired(&mut self) -> Result<ProposalStatus, ProgramError> {
+        let current = self.status()?;
+        if current.is_terminal() {
+            return Ok(current);
+        }
+
+        let (status, winning_option) = match self.is_poll() {
+            true => self.plurality(),
+            false => (ProposalStatus::Cancelled, 0),
+        };
+
+        self.finalize(status)?;
+        self.winning_option = winning_option;
+        Ok(status)
+    }
+
+    /// Running tally that `choice` counts toward
+    pub fn tally_mut(&mut self, choice: VoteChoice) -> &mut u64 {
+        match choice {
+            VoteChoice::For => &mut self.for_votes,
+            VoteChoice::Against => &mut self.against_votes,
+            VoteChoice::Abstain => &mut self.abstain_votes,
         }
-        Ok(Self::from_account_info_unchecked(account_info))
+    }
+
+    /// Adds `weight` to the tally of `choice`, failing instead of wrapping around
+    pub fn add_to_tally(&mut self, choice: VoteChoice, weight: u64) -> Result<(), ProgramError> {
+        let tally = self.tally_mut(choice);
+        *tally = tally.checked_add(weight).ok_or(MultisigError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// For + Against + Abstain, failing instead of wrapping around
+    pub fn total_votes(&self) -> Result<u64, ProgramError> {
+        self.for_votes
+            .checked_add(self.against_votes)
+            .and_then(|total| total.checked_add(self.abstain_votes))
+            .ok_or(MultisigError::ArithmeticOverflow.into())
+    }
+
+    /// Voting stays open through `expiry` itself and closes the second after
+    pub fn is_expired(&self, now: u64) -> bool {
+        now > self.expiry
+    }
+
+    /// Earliest time a Succeeded proposal can be executed
+    pub fn executable_at(&self) -> u64 {
+        self.succeeded_at.saturating_add(self.execution_delay)
+    }
+
+    /// Seconds elapsed since the proposal was created, zero if `now` is earlier
+    pub fn age(&self, now: u64) -> u64 {
+        now.saturating_sub(self.created_time)
+    }
+
+    /// Compact `ProposalSnapshot` of the id, status, tallies, expiry and executed flag,
+    /// as logged with `sol_log_data`
+    pub fn serialize_for_log(&self) -> [u8; ProposalSnapshot::LEN] {
+        ProposalSnapshot::new(self).serialize()
     }
 }
 
+const _: () = assert!(core::mem::size_of::<ProposalState>() == ProposalState::BASE_LEN);
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: u8, // 1 if the account signs the stored instruction
+    pub is_writable: u8, // 1 if the account is writable in the stored instruction
+}
+
+impl ProposalAccountMeta {
+    pub const LEN: usize = 32 + 1 + 1;
+}
+
+/// Stored as `ProposalState.result` and logged as a number, so the discriminants are part
+/// of the account layout and must never be reordered. Bytes outside them are rejected when
+/// the proposal is loaded.
 #[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum ProposalStatus {
     Draft = 0,
     Active = 1,
     Failed = 2,
     Succeeded = 3,
     Cancelled = 4,
+    Executed = 5, // A Succeeded proposal whose action has run
 }
 
+impl core::fmt::Display for ProposalStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            ProposalStatus::Draft => "Draft",
+            ProposalStatus::Active => "Active",
+            ProposalStatus::Failed => "Failed",
+            ProposalStatus::Succeeded => "Succeeded",
+            ProposalStatus::Cancelled => "Cancelled",
+            ProposalStatus::Executed => "Executed",
+        };
+        f.write_str(name)
+    }
+}
+
+impl ProposalStatus {
+    /// Failed, Cancelled and Executed proposals never change status again, and a Succeeded
+    /// one only moves on to Executed
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, ProposalStatus::Failed | ProposalStatus::Succeeded | ProposalStatus::Cancelled | ProposalStatus::Executed)
+    }
+}
+
+/// What a proposal does once executed. ArbitraryCpi invokes the stored instruction,
+/// the other types apply a built-in action described by `ProposalState.payload`.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum ProposalType {
+    ArbitraryCpi = 0,
+    Transfer = 1, // [recipient: Pubkey][lamports: u64], paid from the treasury
+    AddMember = 2, // [member: Pubkey]
+    RemoveMember = 3, // [member: Pubkey]
+    ChangeThreshold = 4, // [new_threshold: u64]
+    CloseMultisig = 5, // [rent_recipient: Pubkey], authorizes `CloseMultisig` rather than being executed
+    Poll = 6, // [num_options: u8], decides between options and has no action to execute
+    ConfigChange = 7, // [min_threshold: u64][quorum: u64][execution_delay: u64]
+}
+
+// Every type has a slot for its own threshold in `MultisigConfig.type_thresholds`
+const _: () = assert!((ProposalType::ConfigChange as usize) < MAX_PROPOSAL_TYPES);
+
+impl ProposalType {
+    /// Exact number of payload bytes the type expects
+    pub fn payload_len(&self) -> usize {
+        match self {
+            ProposalType::ArbitraryCpi => 0,
+            ProposalType::Transfer => 32 + 8,
+            ProposalType::AddMember | ProposalType::RemoveMember | ProposalType::CloseMultisig => 32,
+            ProposalType::ChangeThreshold => 8,
+            ProposalType::Poll => 1,
+            ProposalType::ConfigChange => 8 + 8 + 8,
+        }
+    }
+
+    /// False for the types `ExecuteProposal` refuses, whose success is the whole outcome
+    pub fn is_executable(&self) -> bool {
+        !matches!(self, ProposalType::CloseMultisig | ProposalType::Poll)
+    }
+}
+
+impl TryFrom<&u8> for ProposalType {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match *value {
+            0 => Ok(ProposalType::ArbitraryCpi),
+            1 => Ok(ProposalType::Transfer),
+            2 => Ok(ProposalType::AddMember),
+            3 => Ok(ProposalType::RemoveMember),
+            4 => Ok(ProposalType::ChangeThreshold),
+            5 => Ok(ProposalType::CloseMultisig),
+            6 => Ok(ProposalType::Poll),
+            7 => Ok(ProposalType::ConfigChange),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
 
 impl TryFrom<&u8> for ProposalStatus {
     type Error = ProgramError;
@@ -56,7 +532,197 @@ impl TryFrom<&u8> for ProposalStatus {
             2 => Ok(ProposalStatus::Failed),
             3 => Ok(ProposalStatus::Succeeded),
             4 => Ok(ProposalStatus::Cancelled),
+            5 => Ok(ProposalStatus::Executed),
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
-}
\ No newline at end of file
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_proposal_state {
+    use super::*;
+
+    #[test]
+    fn test_proposal_state_bytes_round_trip() {
+        let mut proposal = ProposalState::zeroed();
+        proposal.proposal_id = 42;
+        proposal.expiry = 1_000;
+        proposal.set_status(ProposalStatus::Succeeded);
+        proposal.active_member_count = 1;
+        proposal.active_members[0] = [7u8; 32];
+        proposal.created_time = 500;
+        proposal.executed = 1;
+        proposal.accounts[0].is_writable = 1;
+        proposal.for_votes = 3;
+        proposal.required_votes = 2;
+
+        let bytes = bytemuck::bytes_of(&proposal).to_vec();
+        assert_eq!(bytes.len(), ProposalState::BASE_LEN);
+
+        let decoded: ProposalState = bytemuck::pod_read_unaligned(&bytes);
+        assert_eq!(decoded.proposal_id, 42);
+        assert_eq!(decoded.expiry, 1_000);
+        assert!(matches!(decoded.status(), Ok(ProposalStatus::Succeeded)));
+        assert_eq!(decoded.active_members[0], [7u8; 32]);
+        assert_eq!(decoded.created_time, 500);
+        assert_eq!(decoded.executed, 1);
+        assert_eq!(decoded.accounts[0].is_writable, 1);
+        assert_eq!(decoded.for_votes, 3);
+        assert_eq!(decoded.required_votes, 2);
+    }
+
+    #[test]
+    fn test_status_byte_encoding_round_trips() {
+        let statuses = [
+            ProposalStatus::Draft,
+            ProposalStatus::Active,
+            ProposalStatus::Failed,
+            ProposalStatus::Succeeded,
+            ProposalStatus::Cancelled,
+            ProposalStatus::Executed,
+        ];
+
+        for (byte, status) in statuses.into_iter().enumerate() {
+            assert_eq!(status as u8, byte as u8);
+            assert_eq!(ProposalStatus::try_from(&(byte as u8)), Ok(status));
+
+            let mut proposal = ProposalState::zeroed();
+            proposal.set_status(status);
+            assert_eq!(proposal.status(), Ok(status));
+        }
+
+        assert_eq!(ProposalStatus::Succeeded.to_string(), "Succeeded");
+        assert!(ProposalStatus::try_from(&(statuses.len() as u8)).is_err());
+        assert!(ProposalStatus::try_from(&u8::MAX).is_err());
+    }
+
+    #[test]
+    fn test_tally_overflow_is_an_error() {
+        let mut proposal = ProposalState::zeroed();
+
+        assert!(proposal.add_to_tally(VoteChoice::For, u64::MAX).is_ok());
+        assert_eq!(
+            proposal.add_to_tally(VoteChoice::For, 1),
+            Err(ProgramError::Custom(MultisigError::ArithmeticOverflow as u32)),
+        );
+        assert_eq!(proposal.for_votes, u64::MAX);
+
+        proposal.add_to_tally(VoteChoice::Against, u64::MAX).unwrap();
+        assert_eq!(
+            proposal.total_votes(),
+            Err(ProgramError::Custom(MultisigError::ArithmeticOverflow as u32)),
+        );
+    }
+
+    #[test]
+    fn test_tally_with_mixed_votes() {
+        let mut proposal = ProposalState::zeroed();
+        proposal.set_status(ProposalStatus::Active);
+        proposal.expiry = 1_000;
+        for choice in [VoteChoice::For, VoteChoice::Against, VoteChoice::For, VoteChoice::Abstain] {
+            proposal.add_to_tally(choice, 1).unwrap();
+        }
+
+        let (for_votes, against_votes, abstain_votes, projection) = proposal.tally(2, 2, 0, 500).unwrap();
+        assert_eq!((for_votes, against_votes, abstain_votes), (2, 1, 1));
+        assert!(projection == ProposalStatus::Succeeded);
+
+        // Not enough For votes, and quorum requires every vote to be cast
+        assert!(proposal.tally(3, 3, 5, 500).unwrap().3 == ProposalStatus::Active);
+        // A lower defeat threshold fails the proposal on its single Against vote
+        assert!(proposal.tally(3, 1, 0, 500).unwrap().3 == ProposalStatus::Failed);
+        // Undecided past expiry
+        assert!(proposal.tally(3, 3, 0, 1_001).unwrap().3 == ProposalStatus::Cancelled);
+        // Tallying never modifies the proposal
+        assert!(matches!(proposal.status(), Ok(ProposalStatus::Active)));
+    }
+
+    #[test]
+    fn test_against_and_abstain_votes_never_count_as_possible_for_votes() {
+        let mut proposal = ProposalState::zeroed();
+        proposal.add_to_tally(VoteChoice::For, 1).unwrap();
+        proposal.add_to_tally(VoteChoice::Against, 2).unwrap();
+        proposal.add_to_tally(VoteChoice::Abstain, 1).unwrap();
+
+        // 1 For plus the 2 of 6 weight not yet cast
+        assert_eq!(proposal.remaining_possible_for_votes(6).unwrap(), 3);
+        assert!(proposal.approval_reachable(3, 6).unwrap());
+        assert!(!proposal.approval_reachable(4, 6).unwrap());
+    }
+
+    #[test]
+    fn test_age_counts_from_created_time() {
+        let mut proposal = ProposalState::zeroed();
+        proposal.created_time = 1_000;
+
+        assert_eq!(proposal.age(1_000), 0);
+        assert_eq!(proposal.age(1_600), 600);
+        // A clock behind the creation time never underflows
+        assert_eq!(proposal.age(900), 0);
+    }
+
+    #[test]
+    fn test_finalize_never_reassigns_terminal_status() {
+        let mut proposal = ProposalState::zeroed();
+        proposal.set_status(ProposalStatus::Active);
+
+        assert!(proposal.finalize(ProposalStatus::Succeeded).is_ok());
+        // Finalizing again to the same status is a no-op
+        assert!(proposal.finalize(ProposalStatus::Succeeded).is_ok());
+        assert!(proposal.finalize(ProposalStatus::Failed).is_err());
+        assert!(proposal.finalize(ProposalStatus::Cancelled).is_err());
+        assert!(matches!(proposal.status(), Ok(ProposalStatus::Succeeded)));
+    }
+
+    #[test]
+    fn test_poll_plurality_wins_at_expiry() {
+        let mut proposal = ProposalState::zeroed();
+        proposal.set_status(ProposalStatus::Active);
+        proposal.num_options = 4;
+        proposal.option_votes[..4].copy_from_slice(&[1, 1, 2, 0]);
+
+        assert!(matches!(proposal.resolve_expired(), Ok(ProposalStatus::Succeeded)));
+        assert_eq!(proposal.winning_option, 3);
+    }
+
+    #[test]
+    fn test_poll_tied_plurality_fails() {
+        let mut proposal = ProposalState::zeroed();
+        proposal.set_status(ProposalStatus::Active);
+        proposal.num_options = 3;
+        proposal.option_votes[..3].copy_from_slice(&[2, 0, 2]);
+
+        assert!(matches!(proposal.resolve_expired(), Ok(ProposalStatus::Failed)));
+        assert_eq!(proposal.winning_option, 0);
+
+        // Votes outside the offered options are never valid
+        assert!(proposal.validate_choice(3).is_ok());
+        assert!(proposal.validate_choice(0).is_err());
+        assert!(proposal.validate_choice(4).is_err());
+    }
+
+    #[test]
+    fn test_members_voted_counts_only_cast_votes() {
+        let mut proposal = ProposalState::zeroed();
+        // Slot 1 of the snapshot is empty, e.g. a member removed before the snapshot was compacted
+        proposal.active_member_count = 4;
+        proposal.active_members[0] = [1u8; 32];
+        proposal.active_members[2] = [3u8; 32];
+        proposal.active_members[3] = [4u8; 32];
+
+        let mut vote_state = VoteState::zeroed();
+        assert_eq!(proposal.members_eligible(), 3);
+        assert_eq!(proposal.members_voted(&vote_state), 0);
+
+        vote_state.mark_voted(0).unwrap();
+        vote_state.mark_voted(3).unwrap();
+        // Bits for the empty slot and past the snapshot are never counted as votes
+        vote_state.mark_voted(1).unwrap();
+        vote_state.mark_voted(4).unwrap();
+
+        assert_eq!(proposal.members_voted(&vote_state), 2);
+        assert_eq!(proposal.members_eligible(), 3);
+    }
+}
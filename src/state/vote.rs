@@ -1,27 +1,144 @@
-use pinocchio::{
-    account_info::AccountInfo, 
-    pubkey::Pubkey
-};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
 
+use bytemuck::{Pod, Zeroable};
+
+use pinocchio::pubkey::Pubkey;
+
+use super::{load_mut, MAX_MEMBERS};
+
+/// Account layout, read in place: `#[repr(C)]` with explicit `_padding` fields, so it has
+/// no implicit padding.
 #[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
 pub struct VoteState {
-    pub has_permission: bool, // Indicates if the account has permission to vote
+    pub has_permission: u8, // 1 once the program created and initialized the account; 0 is never a valid vote state
+    pub _padding: [u8; 3], // Aligns proposal_nonce to 4 bytes
+    pub proposal_nonce: u32, // `ProposalState.nonce` of the proposal the votes belong to
     pub vote_count: u64, // proposal counter
     pub bump: u8, // Bump seed for PDA   
-    pub votes: [u8; 10], // Array to hold votes, adjust size as needed
+    pub _padding_end: [u8; 7], // Rounds the size up to the 8 byte alignment
+    pub voted: [u8; MAX_MEMBERS / 8], // Bit i is set once the member at `ProposalState.active_members[i]` voted
 }
 
+const _: () = assert!(core::mem::size_of::<VoteState>() == VoteState::LEN);
+
+// `voted` has a bit for every slot of `ProposalState.active_members`, so no member index
+// can fall past the end of the bitmap
+const _: () = assert!(MAX_MEMBERS % 8 == 0);
+
 impl VoteState {
-    pub const LEN: usize = 1 + 8 + 1; // 1 byte for has_permission, 8 bytes for vote_count, and 1 byte for bump
+    pub const LEN: usize = 1 + 3 + 4 + 8 + 1 + 7 + MAX_MEMBERS / 8; // has_permission, padding, proposal_nonce, vote_count, bump, padding and voted
+
+    /// Size of vote states created before the `voted` bitmap existed
+    pub const LEGACY_LEN: usize = Self::LEN - MAX_MEMBERS / 8;
 
     pub fn from_account_info_unchecked(account_info: &AccountInfo) -> &mut Self {
         unsafe { &mut *(account_info.borrow_mut_data_unchecked().as_ptr() as *mut Self) }
     }
 
     pub fn from_account_info(account_info: &AccountInfo) -> Result<&mut Self, pinocchio::program_error::ProgramError> {
-        if account_info.data_len() < Self::LEN {
-            return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+        load_mut(unsafe { account_info.borrow_mut_data_unchecked() })
+    }
+
+    /// True if the snapshotted member at `index` has voted. Out of range indices never have.
+    pub fn has_voted(&self, index: usize) -> bool {
+        self.voted
+            .get(index / 8)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    /// Records that the snapshotted member at `index` has voted
+    pub fn mark_voted(&mut self, index: usize) -> Result<(), ProgramError> {
+        let byte = self.voted.get_mut(index / 8).ok_or(ProgramError::InvalidArgument)?;
+        *byte |= 1 << (index % 8);
+        Ok(())
+    }
+
+    /// Forgets the vote of the snapshotted member at `index`, e.g. when it is retracted
+    pub fn clear_voted(&mut self, index: usize) -> Result<(), ProgramError> {
+        let byte = self.voted.get_mut(index / 8).ok_or(ProgramError::InvalidArgument)?;
+        *byte &= !(1 << (index % 8));
+        Ok(())
+    }
+}
+
+/// One member's vote on one proposal, at the PDA `[b"receipt", proposal_state, voter]`.
+///
+/// Votes are keyed by the voter's pubkey rather than a position in the member list, so
+/// adding or removing multisig members can never shift a recorded vote onto someone else.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct VoteReceipt {
+    pub voter: Pubkey, // Member whose vote this is, the delegator for a delegated vote
+    pub timestamp: u64, // Unix timestamp of the latest cast or change
+    pub choice: u8, // `VoteChoice` as u8, or the option voted for on a Poll
+    pub bump: u8, // Bump seed for PDA
+    pub delegated: u8, // 1 if a delegate cast the latest choice for `voter`, 0 if `voter` did
+    pub _padding: [u8; 1], // Aligns proposal_nonce to 4 bytes
+    pub proposal_nonce: u32, // `ProposalState.nonce` of the proposal voted on
+}
+
+const _: () = assert!(core::mem::size_of::<VoteReceipt>() == VoteReceipt::LEN);
+
+impl VoteReceipt {
+    pub const LEN: usize = 32 + 8 + 1 + 1 + 1 + 1 + 4; // voter, timestamp, choice, bump, delegated, padding and proposal_nonce
+
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        load_mut(unsafe { account_info.borrow_mut_data_unchecked() })
+    }
+}
+
+/// A cast vote. Stored as its `u8` value in `VoteReceipt.choice`; 0 is never a valid choice.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum VoteChoice {
+    For = 1,
+    Against = 2,
+    Abstain = 3,
+}
+
+impl TryFrom<u8> for VoteChoice {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(VoteChoice::For),
+            2 => Ok(VoteChoice::Against),
+            3 => Ok(VoteChoice::Abstain),
+            _ => Err(ProgramError::InvalidInstructionData),
         }
-        Ok(Self::from_account_info_unchecked(account_info))
     }
-}
\ No newline at end of file
+}
+
+// -------------------------- TESTING -----------------------------
+
+#[cfg(test)]
+mod testing_vote_state {
+    use super::*;
+
+    #[test]
+    fn test_voted_bitmap_tracks_each_index() {
+        let mut vote_state = VoteState::zeroed();
+        let voted = [0, 7, 8, 13, MAX_MEMBERS - 1];
+
+        for index in voted {
+            vote_state.mark_voted(index).unwrap();
+        }
+
+        for index in 0..MAX_MEMBERS {
+            assert_eq!(vote_state.has_voted(index), voted.contains(&index), "index {}", index);
+        }
+
+        // Marking twice is harmless, and indices past the member capacity are rejected
+        vote_state.mark_voted(7).unwrap();
+        assert!(vote_state.has_voted(7));
+        assert!(!vote_state.has_voted(MAX_MEMBERS));
+        assert_eq!(vote_state.mark_voted(MAX_MEMBERS), Err(ProgramError::InvalidArgument));
+
+        // Clearing one vote leaves its neighbours in the same byte
+        vote_state.clear_voted(8).unwrap();
+        assert!(!vote_state.has_voted(8));
+        assert!(vote_state.has_voted(13));
+        assert_eq!(vote_state.clear_voted(MAX_MEMBERS), Err(ProgramError::InvalidArgument));
+    }
+}